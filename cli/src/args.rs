@@ -19,6 +19,9 @@ pub enum Subcommands {
         /// A path where we'll save the report in its JSON form.
         #[arg(long, default_value = DEFAULT_FILE_NAME)]
         save_path: PathBuf,
+
+        #[command(flatten)]
+        shared: SharedArgs,
     },
     /// Uploads a hardware report to the web.
     Upload {
@@ -50,8 +53,35 @@ pub enum Subcommands {
 /// These are flattened, meaning this struct doesn't actually appear for users.
 #[derive(Clone, Debug, clap::Args)]
 pub struct SharedArgs {
-    // TODO: add options:
-    //    - use mac address
+    /// How identifying values, like MAC addresses and serial numbers, are
+    /// placed into the report.
+    #[arg(long, value_enum, default_value_t = RedactionArg::Redact)]
+    pub redaction: RedactionArg,
+
+    /// How long to measure the processor's power draw for, in milliseconds.
+    ///
+    /// This usually needs administrator rights. When not passed, power draw
+    /// isn't measured.
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..=10_000))]
+    pub power_sample_ms: Option<u64>,
+}
+
+/// How identifying values are placed into the report.
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum RedactionArg {
+    /// Leave them out.
+    Redact,
+    /// Include them as-is.
+    Keep,
+}
+
+impl From<RedactionArg> for libghr::Redaction {
+    fn from(value: RedactionArg) -> Self {
+        match value {
+            RedactionArg::Redact => Self::Redact,
+            RedactionArg::Keep => Self::Keep,
+        }
+    }
 }
 
 /// makes a "style" for clap. that's just the colors we want to use
@@ -1,11 +1,14 @@
 use crate::{
-    args::{Args, Subcommands},
+    args::{Args, SharedArgs, Subcommands},
     log::setup_logging,
     subcommands::{save, upload},
 };
-use libghr::Report;
+use libghr::{report::components::Health, Report};
+
+use std::time::Duration;
 
 use clap::Parser as _;
+use colored::Colorize as _;
 
 mod args;
 mod log;
@@ -33,7 +36,7 @@ async fn main() {
 
     // run the command the user asked for
     match args.subcommands {
-        Subcommands::Save { save_path } => save::run(&save_path).await,
+        Subcommands::Save { save_path, shared } => save::run(&save_path, &shared).await,
         Subcommands::Upload {
             server,
             confirm_without_prompt,
@@ -44,7 +47,49 @@ async fn main() {
     .expect("a subcommand failed to execute");
 }
 
-/// Creates a report with the default settings.
-async fn get_report() -> anyhow::Result<Report> {
-    Ok(Report::new().await?)
+/// Creates a report with the settings the user asked for.
+async fn get_report(shared: &SharedArgs) -> anyhow::Result<Report> {
+    let report = Report::builder()
+        .redaction(shared.redaction.into())
+        .power_sampling(shared.power_sample_ms.map(Duration::from_millis))
+        .build()
+        .await?;
+
+    print_unhealthy(&report);
+    Ok(report)
+}
+
+/// Tells the user about any components that don't look healthy.
+fn print_unhealthy(report: &Report) {
+    for component in &report.components {
+        let Some(status) = component.status() else {
+            continue;
+        };
+
+        let name = component
+            .id()
+            .or(component.class())
+            .unwrap_or_else(|| format!("{:?} device", component.bus()));
+        let reasons = status.reasons.unwrap_or_default().join(" ");
+
+        match status.health {
+            Health::Warning => {
+                println!(
+                    "{}{} {}",
+                    "Warning: ".yellow(),
+                    name.bright_yellow(),
+                    reasons.yellow()
+                )
+            }
+            Health::Critical => {
+                println!(
+                    "{}{} {}",
+                    "Critical: ".red(),
+                    name.bright_red(),
+                    reasons.red()
+                )
+            }
+            _ => {}
+        }
+    }
 }
@@ -1,14 +1,14 @@
 use std::path::Path;
 
-use crate::log::latest_log_location;
+use crate::{args::SharedArgs, get_report, log::latest_log_location};
 use libghr::Report;
 
 use colored::Colorize as _;
 use tokio::io::AsyncWriteExt as _;
 
 /// Saves the `Report` to disk as JSON.
-pub async fn run(save_path: &Path) -> anyhow::Result<()> {
-    let report = Report::new().await?;
+pub async fn run(save_path: &Path, shared: &SharedArgs) -> anyhow::Result<()> {
+    let report = get_report(shared).await?;
 
     // we already print a success message if we write to disk.
     //
@@ -8,10 +8,10 @@ pub async fn run(
     server: String,
     confirm_without_prompt: bool,
     _save_path: Option<PathBuf>,
-    _shared: SharedArgs,
+    shared: SharedArgs,
 ) -> anyhow::Result<()> {
     // grab a report
-    let report = get_report().await?;
+    let report = get_report(&shared).await?;
 
     // make sure the user actually wants to upload this
     if !confirm_without_prompt {
@@ -1,6 +1,7 @@
 "use client";
 
 import Icon from "@mdi/react";
+import type { Health } from "../../../../../../shared/bindings/Health";
 import { type CmpType, icon } from "../../cmp_icon/page";
 import styles from "./tile.module.scss";
 
@@ -8,16 +9,28 @@ export interface CmpTileProps {
 	name: string;
 	cmp: CmpType;
 	value: string;
+	health?: Health;
 }
 
 export const CmpTile = (props: CmpTileProps) => {
 	const icon_path: string = icon(props.cmp);
 
+	// unhealthy components get highlighted
+	let class_name: string = styles.cmp_tile;
+	if (props.health === "Warning") {
+		class_name = `${class_name} ${styles.cmp_tile_warning}`;
+	} else if (props.health === "Critical") {
+		class_name = `${class_name} ${styles.cmp_tile_critical}`;
+	}
+
 	return (
-		<div className={styles.cmp_tile}>
+		<div className={class_name}>
 			<Icon path={icon_path} />
 			<p className={styles.cmp_tile_name}>NAME: {props.name}</p>
 			<p className={styles.cmp_tile_value}>VALUE: {props.value}</p>
+			{props.health === "Warning" || props.health === "Critical" ? (
+				<p className={styles.cmp_tile_health}>HEALTH: {props.health}</p>
+			) : null}
 		</div>
 	);
 };
@@ -1,6 +1,7 @@
 import type { ComponentDescription } from "../../../../shared/bindings/ComponentDescription";
 import type { ComponentInfo } from "../../../../shared/bindings/ComponentInfo";
 import type { CpuDescription } from "../../../../shared/bindings/CpuDescription";
+import type { Health } from "../../../../shared/bindings/Health";
 import type { Report } from "../../../../shared/bindings/Report";
 import type { WrappedReportTs } from "../../../../shared/bindings/WrappedReportTs";
 import { CmpType, icon } from "../components/cmp_icon/page";
@@ -26,14 +27,28 @@ export default function ReportPage(wrapped_report: WrappedReportTs) {
 				name={general.id || "no id found"}
 				cmp={CmpType.Cpu}
 				value={value_string}
+				health={general.status?.health}
 			/>,
 		);
 	}
 
+	const unhealthy_components = [];
+
+	for (const [cmp, health] of unhealthy(report)) {
+		const reasons: string = cmp.status?.reasons?.join(" ") || "";
+
+		unhealthy_components.push(
+			<p>
+				{health}: {cmp.id || cmp.class || "unknown component"} {reasons}
+			</p>,
+		);
+	}
+
 	return (
 		<div>
 			<p>{wrapped_report.id}</p>
 			<p>{computer_name}</p>
+			{unhealthy_components}
 			{cpu_components}
 		</div>
 	);
@@ -58,3 +73,22 @@ function cpu_descriptions(report: Report): [ComponentInfo, CpuDescription][] {
 
 	return processors;
 }
+
+/**
+ *
+ * @param report A `Report` containing info about the system.
+ * @returns A list of components with warnings or critical problems.
+ */
+function unhealthy(report: Report): [ComponentInfo, Health][] {
+	const components: [ComponentInfo, Health][] = [];
+
+	for (const cmp of report.components) {
+		const health = cmp.status?.health;
+
+		if (health === "Warning" || health === "Critical") {
+			components.push([cmp, health]);
+		}
+	}
+
+	return components;
+}
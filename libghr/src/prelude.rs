@@ -3,21 +3,63 @@ pub(crate) mod internal {
     pub(crate) use crate::{
         error::{GhrError, GhrResult},
         report::{
+            components::audio::{AudioCodec, AudioDescription, AudioPcm, PcmDirection},
+            components::bluetooth::{BluetoothDescription, BluetoothFeature},
+            components::camera::{CameraCapability, CameraDescription, CameraKind},
             components::cpu::{Cache, CpuDescription, Frequency},
             components::gpu::GpuDescription,
-            components::nic::NicDescription,
-            components::psu::PowerSupplyDescription,
+            components::input::{InputDescription, InputKind, InputSwitch},
+            components::nic::{
+                Duplex, NicDescription, NicKind, NicState, WifiBand, WifiStandard,
+                WirelessDescription,
+            },
+            components::pci::{
+                AerCounters, PciBar, PciBarKind, PciDescription, PciLink, PciPowerState,
+                RuntimeStatus, SrIov,
+            },
+            components::psu::{BatteryStatus, PowerSupplyDescription},
             components::ram::RamDescription,
+            components::sensors::{Sensor, SensorKind},
             components::storage::{
-                StorageConnector, StorageDescription, StorageKind, StorageUsage,
+                StorageConnector, StorageDescription, StorageKind, StorageLink, StorageQueue,
+                StorageUsage, WriteCacheMode, ZonedModel,
+            },
+            components::thunderbolt::{
+                ThunderboltAuthorization, ThunderboltDescription, ThunderboltKind, ThunderboltLink,
+                ThunderboltSecurity,
             },
+            components::typec::{
+                AltMode, AltModeKind, DataRole, Pdo, PowerDelivery, PowerOperationMode, PowerRole,
+                TypeCCable, TypeCDescription, TypeCPartner, TypeCPortType,
+            },
+            components::usb::{UsbDescription, UsbInterface, UsbSpeed},
             components::{
-                ComponentBus, ComponentDescription, ComponentInfo, ComponentStatus, Removability,
+                ComponentBus, ComponentDescription, ComponentInfo, ComponentStatus, FirmwareState,
+                Health, Removability, Rfkill,
             },
             machine::MachineInfo,
-            os::OperatingSystemInfo,
+            os::{DisplayServer, InitSystem, Libc, LibcKind, OperatingSystemInfo, OsRelease},
+            privacy::{Redaction, Sensitive},
             system_config::{
-                sleep::{Sleep, SleepMode},
+                acpi::{
+                    Acpi, AcpiIommu, AcpiTable, Bgrt, BootArchFlag, Fadt, FadtFlag, Hpet,
+                    IommuKind, LowPowerIdleState, Madt, PmProfile,
+                },
+                firmware::{
+                    DeviceFirmware, EsrtEntry, EsrtKind, Firmware, FirmwareDeviceKind,
+                    SystemFirmware, UpdateStatus,
+                },
+                kernel::{Kernel, KernelModule, Taint},
+                limits::{CgroupVersion, IoLimit, ResourceLimits},
+                memory::{
+                    Ecc, EdacDimm, HugepagePool, Memory, MemoryController, NumaNode, SwapDevice,
+                    ThpMode, TransparentHugepages, ZramDevice, Zswap,
+                },
+                powercap::{PowerDomain, PowerLimit, PowerLimitKind, PowerZone, Powercap},
+                security::{BootMode, Lockdown, MemoryEncryption, Security, Tpm},
+                sleep::{HibernationMode, MemSleep, S0ixResidency, Sleep, SleepMode, WakeupSource},
+                thermal::{CoolingDevice, Thermal, ThermalZone, TripPoint},
+                virtualization::{Cloud, CloudProvider, Container, Hypervisor, Virtualization},
                 SystemConfInfo,
             },
             util::unit_to_mibiunits,
@@ -35,7 +77,10 @@ pub(crate) mod internal {
         convert_to_pci_class, convert_to_pci_names,
     };
     #[cfg(target_os = "linux")]
-    pub(crate) use crate::report::util::linux::{sysfs_value, sysfs_value_opt, Civ};
+    pub(crate) use crate::report::util::linux::{
+        cpu_flags, current_choice, driver_module, driver_name, numbered_entries, parse_choices,
+        parse_cpu_list, rfkill, runtime_status, subsystem, sysfs_value, sysfs_value_opt, Civ,
+    };
 
     // export logger creating fn for unit tests
     #[cfg(test)]
@@ -54,6 +99,6 @@ pub(crate) mod internal {
 pub(super) mod public {
     pub use crate::{
         error::{GhrError, GhrResult},
-        report::Report,
+        report::{privacy::Redaction, Report, ReportBuilder},
     };
 }
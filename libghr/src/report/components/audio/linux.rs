@@ -0,0 +1,430 @@
+use std::path::Path;
+
+use futures::StreamExt as _;
+
+use crate::{prelude::internal::*, report::components::usb::linux::usb_vendor_and_id};
+
+/// finds info about all sound cards on the system.
+#[tracing::instrument]
+pub(crate) async fn get() -> GhrResult<Vec<ComponentInfo>> {
+    all("/proc/asound", "/sys/class/sound").await
+}
+
+/// gets info about every card listed in `asound_path`, which is usually
+/// `/proc/asound`.
+///
+/// `sound_path` should be the `sysfs` sound class, so we can find out what
+/// each card is attached to.
+#[tracing::instrument]
+async fn all(
+    asound_path: impl AsRef<Path> + std::fmt::Debug,
+    sound_path: impl AsRef<Path> + std::fmt::Debug,
+) -> GhrResult<Vec<ComponentInfo>> {
+    let (asound_path, sound_path) = (asound_path.as_ref(), sound_path.as_ref());
+    let cards = sysfs_value::<String>(asound_path.join("cards")).await?;
+
+    let mut infos = Vec::new();
+    for card in parse_cards(&cards) {
+        let index = card.index;
+        infos.push(
+            one(
+                card,
+                &asound_path.join(format!("card{index}")),
+                &sound_path.join(format!("card{index}")),
+            )
+            .await,
+        );
+    }
+
+    Ok(infos)
+}
+
+/// grabs info about one sound card.
+///
+/// `asound_path` is like `/proc/asound/card0`, while `sysfs_path` is like
+/// `/sys/class/sound/card0`.
+#[tracing::instrument]
+async fn one(card: Card, asound_path: &Path, sysfs_path: &Path) -> ComponentInfo {
+    let device_path = sysfs_path.join("device");
+
+    let (subsystem, driver, entries) = futures::join! {
+        subsystem(&device_path),
+        driver_name(&device_path),
+        entries(asound_path),
+    };
+
+    // the card's device tells us what it's plugged into
+    let (bus, id, class, vendor_id) = match subsystem.as_deref() {
+        Some("pci") => {
+            let civ = Civ::new(sysfs_path).await;
+            (ComponentBus::Pci, civ.id, civ.class, civ.vendor)
+        }
+        Some("usb") => {
+            // the card belongs to a usb interface, so the vendor and product
+            // are on its parent
+            let usb_device = async_fs::canonicalize(&device_path)
+                .await
+                .ok()
+                .and_then(|path| path.parent().map(Path::to_path_buf));
+            let (vendor_id, id) = match usb_device {
+                Some(usb_device) => usb_vendor_and_id(&usb_device).await,
+                None => (None, None),
+            };
+            (ComponentBus::Usb, id, Some("Audio".into()), vendor_id)
+        }
+        other => {
+            tracing::debug!("Sound card is on an unknown bus. (subsystem: {other:?})");
+            (
+                ComponentBus::Sys,
+                card.short_name.clone(),
+                Some("Audio".into()),
+                None,
+            )
+        }
+    };
+
+    let (codecs, pcms, displays) = futures::join! {
+        codecs(asound_path, &entries),
+        pcms(asound_path, &entries),
+        displays(asound_path, &entries),
+    };
+
+    ComponentInfo {
+        bus,
+        id,
+        class,
+        vendor_id,
+        status: None,
+        desc: ComponentDescription::AudioDescription(AudioDescription {
+            index: Some(card.index),
+            card_id: card.id,
+            name: card.long_name.or(card.short_name),
+            driver,
+            card_driver: card.driver,
+            codecs,
+            pcms,
+            displays,
+        }),
+    }
+}
+
+/// one card from `/proc/asound/cards`.
+#[derive(Debug, PartialEq)]
+struct Card {
+    index: u32,
+    id: Option<String>,
+    driver: Option<String>,
+    short_name: Option<String>,
+    long_name: Option<String>,
+}
+
+/// parses the contents of `/proc/asound/cards`.
+///
+/// each card takes two lines, like:
+///
+/// ```text
+///  0 [PCH            ]: HDA-Intel - HDA Intel PCH
+///                       HDA Intel PCH at 0x6001120000 irq 147
+/// ```
+fn parse_cards(cards: &str) -> Vec<Card> {
+    let mut parsed: Vec<Card> = Vec::new();
+
+    for line in cards.lines().filter(|line| !line.trim().is_empty()) {
+        match parse_card_header(line) {
+            Some(card) => parsed.push(card),
+
+            // any other line holds the last card's long name. it can have
+            // no spaces, or even start with a number!
+            None => {
+                if let Some(card) = parsed.last_mut() {
+                    card.long_name = non_empty(line);
+                }
+            }
+        }
+    }
+
+    parsed
+}
+
+/// parses the first line of a card, like
+/// ` 0 [PCH            ]: HDA-Intel - HDA Intel PCH`.
+///
+/// that's an index, then ` [`, the card's id, and `]:`. lines without that
+/// shape aren't headers.
+fn parse_card_header(line: &str) -> Option<Card> {
+    let line = line.trim_start();
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let index = line[..digits].parse::<u32>().ok()?;
+
+    let (id, rest) = line[digits..].strip_prefix(" [")?.split_once("]:")?;
+    let (driver, short_name) = rest.split_once(" - ").unwrap_or((rest, ""));
+
+    Some(Card {
+        index,
+        id: non_empty(id),
+        driver: non_empty(driver),
+        short_name: non_empty(short_name),
+        long_name: None,
+    })
+}
+
+/// trims `s`, returning `None` if nothing is left.
+fn non_empty(s: &str) -> Option<String> {
+    let s = s.trim();
+    (!s.is_empty()).then(|| s.to_string())
+}
+
+/// lists the names of the files in a card's `procfs` directory, sorted.
+#[tracing::instrument]
+async fn entries(path: &Path) -> Vec<String> {
+    let Ok(dir) = async_fs::read_dir(path).await else {
+        return Vec::new();
+    };
+
+    let mut entries = dir
+        .filter_map(|entry| async { entry.ok() })
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .await;
+    entries.sort();
+    entries
+}
+
+/// reads the card's hd audio codecs, from files like `codec#0`.
+#[tracing::instrument(skip(entries))]
+async fn codecs(path: &Path, entries: &[String]) -> Option<Vec<AudioCodec>> {
+    let mut codecs = Vec::new();
+    for entry in entries.iter().filter(|e| e.starts_with("codec#")) {
+        if let Some(contents) = sysfs_value_opt::<String>(path.join(entry)).await {
+            codecs.push(parse_codec(&contents));
+        }
+    }
+
+    (!codecs.is_empty()).then_some(codecs)
+}
+
+/// parses the header of a codec file.
+fn parse_codec(contents: &str) -> AudioCodec {
+    let mut codec = AudioCodec {
+        name: None,
+        address: None,
+        vendor_id: None,
+        subsystem_id: None,
+        revision_id: None,
+    };
+
+    for line in contents.lines() {
+        let Some((key, value)) = line.split_once(": ") else {
+            continue;
+        };
+
+        match key {
+            "Codec" => codec.name = non_empty(value),
+            "Address" => codec.address = value.trim().parse().ok(),
+            "Vendor Id" => codec.vendor_id = non_empty(value),
+            "Subsystem Id" => codec.subsystem_id = non_empty(value),
+            "Revision Id" => codec.revision_id = non_empty(value),
+            _ => (),
+        }
+    }
+
+    codec
+}
+
+/// reads the card's pcm devices, from directories like `pcm0p`.
+#[tracing::instrument(skip(entries))]
+async fn pcms(path: &Path, entries: &[String]) -> Option<Vec<AudioPcm>> {
+    let mut pcms = Vec::new();
+    for entry in entries {
+        let Some(rest) = entry.strip_prefix("pcm") else {
+            continue;
+        };
+
+        let direction = match rest.chars().last() {
+            Some('p') => PcmDirection::Playback,
+            Some('c') => PcmDirection::Capture,
+            _ => continue,
+        };
+        let Ok(device) = rest[..rest.len() - 1].parse::<u32>() else {
+            continue;
+        };
+
+        let info = sysfs_value_opt::<String>(path.join(entry).join("info"))
+            .await
+            .unwrap_or_default();
+        let (mut id, mut name) = (None, None);
+        for line in info.lines() {
+            if let Some(value) = line.strip_prefix("id: ") {
+                id = non_empty(value);
+            } else if let Some(value) = line.strip_prefix("name: ") {
+                name = non_empty(value);
+            }
+        }
+
+        // hdmi and displayport outputs are named after their connector
+        let digital_display = [&id, &name].into_iter().flatten().any(|s| {
+            s.split_whitespace()
+                .any(|word| word == "HDMI" || word == "DP" || word.starts_with("DisplayPort"))
+        });
+
+        pcms.push(AudioPcm {
+            device,
+            name: name.or(id),
+            direction,
+            digital_display,
+        });
+    }
+
+    (!pcms.is_empty()).then_some(pcms)
+}
+
+/// finds the displays connected to the card's hdmi/dp outputs, using their
+/// ELD (EDID-like data) files, like `eld#2.0`.
+#[tracing::instrument(skip(entries))]
+async fn displays(path: &Path, entries: &[String]) -> Option<Vec<String>> {
+    let mut displays = Vec::new();
+    for entry in entries.iter().filter(|e| e.starts_with("eld#")) {
+        let Some(eld) = sysfs_value_opt::<String>(path.join(entry)).await else {
+            continue;
+        };
+
+        let mut present = false;
+        let mut name = None;
+        for line in eld.lines() {
+            let Some((key, value)) = line.split_once(char::is_whitespace) else {
+                continue;
+            };
+
+            match key {
+                "monitor_present" => present = value.trim() == "1",
+                "monitor_name" => name = non_empty(value),
+                _ => (),
+            }
+        }
+
+        if present {
+            displays.push(name.unwrap_or_else(|| "Unknown".into()));
+        }
+    }
+
+    (!displays.is_empty()).then_some(displays)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn check_parse_cards() {
+        let cards = parse_cards(concat!(
+            " 0 [PCH            ]: HDA-Intel - HDA Intel PCH\n",
+            "                      HDA Intel PCH at 0x6001120000 irq 147\n",
+            "10 [sofhdadsp      ]: sof-hda-dsp - sof-hda-dsp\n",
+            "                      LENOVO-21CBCTO1WW-ThinkPadX1Carbon10th\n",
+            "11 [Device         ]: USB-Audio - USB Audio Device\n",
+            "                      2020 Generic USB Audio Device at usb-0000:00:14.0-2, full speed\n",
+        ));
+
+        assert_eq!(cards.len(), 3);
+        assert_eq!(
+            cards[0],
+            Card {
+                index: 0,
+                id: Some("PCH".into()),
+                driver: Some("HDA-Intel".into()),
+                short_name: Some("HDA Intel PCH".into()),
+                long_name: Some("HDA Intel PCH at 0x6001120000 irq 147".into()),
+            }
+        );
+        assert_eq!(cards[1].index, 10);
+        assert_eq!(cards[1].id.as_deref(), Some("sofhdadsp"));
+
+        // long names without spaces, or that start with a number, still
+        // belong to their card
+        assert_eq!(
+            cards[1].long_name.as_deref(),
+            Some("LENOVO-21CBCTO1WW-ThinkPadX1Carbon10th")
+        );
+        assert_eq!(cards[2].index, 11);
+        assert_eq!(
+            cards[2].long_name.as_deref(),
+            Some("2020 Generic USB Audio Device at usb-0000:00:14.0-2, full speed")
+        );
+    }
+
+    #[tokio::test]
+    async fn check_hda_card() {
+        let all = all(asound_path(), sound_path()).await.unwrap();
+        assert_eq!(all.len(), 2);
+
+        let info = &all[0];
+        assert_eq!(info.bus(), ComponentBus::Pci);
+        assert_eq!(info.vendor_id().unwrap(), "Intel Corporation");
+        assert_eq!(
+            info.id().unwrap(),
+            "Alder Lake PCH-P High Definition Audio Controller"
+        );
+
+        let ComponentDescription::AudioDescription(desc) = info.desc() else {
+            panic!("wrong desc");
+        };
+
+        assert_eq!(desc.card_id.unwrap(), "PCH");
+        assert_eq!(desc.driver.unwrap(), "snd_hda_intel");
+        assert_eq!(desc.card_driver.unwrap(), "HDA-Intel");
+
+        // the codecs are what actually matter for hd audio
+        let codecs = desc.codecs.unwrap();
+        assert_eq!(codecs.len(), 2);
+        assert_eq!(codecs[0].name.as_deref(), Some("Realtek ALC257"));
+        assert_eq!(codecs[0].vendor_id.as_deref(), Some("0x10ec0257"));
+        assert_eq!(codecs[0].subsystem_id.as_deref(), Some("0x17aa22fa"));
+        assert_eq!(codecs[1].address, Some(2));
+
+        let pcms = desc.pcms.unwrap();
+        assert_eq!(pcms.len(), 4);
+        assert_eq!(
+            pcms.iter()
+                .filter(|pcm| pcm.direction == PcmDirection::Capture)
+                .count(),
+            1
+        );
+        assert_eq!(pcms.iter().filter(|pcm| pcm.digital_display).count(), 2);
+
+        // only one of the two outputs has a monitor plugged in
+        assert_eq!(desc.displays.unwrap(), vec!["DELL U2720Q".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn check_usb_card() {
+        let all = all(asound_path(), sound_path()).await.unwrap();
+        let info = &all[1];
+
+        assert_eq!(info.bus(), ComponentBus::Usb);
+        assert_eq!(info.vendor_id().unwrap(), "Lenovo");
+        assert_eq!(info.id().unwrap(), "ThinkPad Thunderbolt 3 Dock Audio");
+
+        let ComponentDescription::AudioDescription(desc) = info.desc() else {
+            panic!("wrong desc");
+        };
+
+        assert_eq!(desc.driver.unwrap(), "snd-usb-audio");
+        assert_eq!(desc.card_driver.unwrap(), "USB-Audio");
+        assert!(desc.codecs.is_none());
+        assert!(desc.displays.is_none());
+        assert_eq!(desc.pcms.unwrap().len(), 2);
+    }
+
+    #[tracing::instrument]
+    fn asound_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs/proc/asound"))
+    }
+
+    #[tracing::instrument]
+    fn sound_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs/sys/class/sound"))
+    }
+}
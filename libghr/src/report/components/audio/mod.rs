@@ -0,0 +1,94 @@
+//! audio: sound cards and their codecs
+
+use crate::prelude::internal::*;
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+/// A description of a sound card.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct AudioDescription {
+    /// The card's number, like `0` for `card0`.
+    pub index: Option<u32>,
+
+    /// The card's short ID, like `PCH`.
+    pub card_id: Option<String>,
+
+    /// The card's full name, like `HDA Intel PCH at 0x6001120000 irq 147`.
+    pub name: Option<String>,
+
+    /// The kernel driver bound to the card's device, like `snd_hda_intel`.
+    pub driver: Option<String>,
+
+    /// The sound driver's name for the card type, like `HDA-Intel` or
+    /// `USB-Audio`.
+    pub card_driver: Option<String>,
+
+    /// The codec chips on the card.
+    ///
+    /// Only HD Audio cards have these.
+    pub codecs: Option<Vec<AudioCodec>>,
+
+    /// The card's playback and capture devices.
+    pub pcms: Option<Vec<AudioPcm>>,
+
+    /// The names of displays connected to the card's HDMI or DisplayPort
+    /// outputs.
+    pub displays: Option<Vec<String>>,
+}
+
+/// An HD Audio codec chip, like a Realtek ALC257.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct AudioCodec {
+    /// The codec's name, like `Realtek ALC257`.
+    pub name: Option<String>,
+
+    /// The codec's address on the HD Audio link.
+    pub address: Option<u32>,
+
+    /// The codec's vendor and device ID, like `0x10ec0257`.
+    pub vendor_id: Option<String>,
+
+    /// The codec's subsystem ID, which usually identifies the laptop model.
+    pub subsystem_id: Option<String>,
+
+    /// The codec's revision ID.
+    pub revision_id: Option<String>,
+}
+
+/// A PCM device, which is a single playback or capture stream.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct AudioPcm {
+    /// The device's number on its card.
+    pub device: u32,
+
+    /// The device's name, like `ALC257 Analog` or `HDMI 0`.
+    pub name: Option<String>,
+
+    /// Whether the device plays or records sound.
+    pub direction: PcmDirection,
+
+    /// Whether this is an HDMI or DisplayPort output.
+    pub digital_display: bool,
+}
+
+/// The direction of a PCM device.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+pub enum PcmDirection {
+    Playback,
+    Capture,
+}
+
+/// Finds and returns info about sound cards on the system.
+#[tracing::instrument]
+pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
+    #[cfg(target_os = "linux")]
+    return linux::get().await;
+
+    // TODO: use `Win32_SoundDevice` on windows
+    #[cfg(target_os = "windows")]
+    return Ok(Vec::new());
+}
@@ -0,0 +1,555 @@
+//! a tiny bluetooth management (and raw hci) client.
+//!
+//! `sysfs` only tells us that a controller exists. its versions and features
+//! live in the kernel's bluetooth stack, so we speak just enough of its
+//! sockets here. none of these requests need root, but raw hci commands only
+//! work while the controller is powered.
+
+use std::os::fd::{AsRawFd as _, FromRawFd as _, OwnedFd};
+
+use crate::prelude::internal::*;
+
+// sockets and options from `net/bluetooth/bluetooth.h` and `hci.h`
+const BTPROTO_HCI: libc::c_int = 1;
+const SOL_HCI: libc::c_int = 0;
+const HCI_FILTER: libc::c_int = 2;
+const HCI_DEV_NONE: u16 = 0xffff;
+const HCI_CHANNEL_RAW: u16 = 0;
+const HCI_CHANNEL_CONTROL: u16 = 3;
+
+// packets and events from `net/bluetooth/hci.h`
+const HCI_COMMAND_PKT: u8 = 0x01;
+const HCI_EVENT_PKT: u8 = 0x04;
+const HCI_EV_CMD_COMPLETE: u8 = 0x0e;
+const HCI_EV_CMD_STATUS: u8 = 0x0f;
+const HCI_OP_READ_LOCAL_VERSION: u16 = 0x1001;
+
+// commands and events from `net/bluetooth/mgmt.h`
+const MGMT_OP_READ_INFO: u16 = 0x0004;
+const MGMT_EV_CMD_COMPLETE: u16 = 0x0001;
+const MGMT_EV_CMD_STATUS: u16 = 0x0002;
+
+// `MGMT_SETTING_*` bits
+const MGMT_SETTING_POWERED: u32 = 1 << 0;
+const MGMT_SETTING_BREDR: u32 = 1 << 7;
+const MGMT_SETTING_LE: u32 = 1 << 9;
+const MGMT_SETTING_SECURE_CONN: u32 = 1 << 11;
+const MGMT_SETTING_WIDEBAND_SPEECH: u32 = 1 << 17;
+const MGMT_SETTING_CIS_CENTRAL: u32 = 1 << 18;
+const MGMT_SETTING_CIS_PERIPHERAL: u32 = 1 << 19;
+const MGMT_SETTING_ISO_BROADCASTER: u32 = 1 << 20;
+
+/// how many unrelated messages we'll skip while waiting for a reply
+const MAX_MESSAGES: usize = 16;
+
+/// `struct sockaddr_hci`
+#[repr(C)]
+struct SockaddrHci {
+    family: libc::sa_family_t,
+    dev: u16,
+    channel: u16,
+}
+
+/// `struct hci_filter`
+#[repr(C)]
+struct HciFilter {
+    type_mask: u32,
+    event_mask: [u32; 2],
+    opcode: u16,
+}
+
+/// what the management interface knows about a controller.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct ControllerInfo {
+    pub(crate) hci_version: u8,
+    pub(crate) supported_settings: u32,
+    pub(crate) current_settings: u32,
+}
+
+impl ControllerInfo {
+    /// lists what the controller supports.
+    #[tracing::instrument(skip(self))]
+    pub(crate) fn features(&self) -> Vec<BluetoothFeature> {
+        let settings = self.supported_settings;
+        let mut features = Vec::new();
+
+        if settings & MGMT_SETTING_BREDR != 0 {
+            features.push(BluetoothFeature::BrEdr);
+        }
+        if settings & MGMT_SETTING_LE != 0 {
+            features.push(BluetoothFeature::LowEnergy);
+        }
+        if settings & MGMT_SETTING_SECURE_CONN != 0 {
+            features.push(BluetoothFeature::SecureConnections);
+        }
+        if settings & MGMT_SETTING_WIDEBAND_SPEECH != 0 {
+            features.push(BluetoothFeature::WidebandSpeech);
+        }
+
+        // le audio needs connected isochronous streams in either role
+        if settings & (MGMT_SETTING_CIS_CENTRAL | MGMT_SETTING_CIS_PERIPHERAL) != 0 {
+            features.push(BluetoothFeature::LeAudio);
+        }
+        if settings & MGMT_SETTING_ISO_BROADCASTER != 0 {
+            features.push(BluetoothFeature::LeAudioBroadcast);
+        }
+
+        features
+    }
+
+    /// whether the controller is turned on.
+    pub(crate) fn powered(&self) -> bool {
+        self.current_settings & MGMT_SETTING_POWERED != 0
+    }
+}
+
+/// the versions a controller reports about itself.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct LocalVersion {
+    pub(crate) hci_version: u8,
+    pub(crate) lmp_version: u8,
+}
+
+/// asks the kernel's bluetooth stack about its controllers.
+///
+/// collectors take one of these so tests can answer with fixture data
+/// instead of the host's real controllers. its requests block, so they're
+/// sent from another thread.
+pub(crate) trait Hci: std::fmt::Debug + Clone + Send + 'static {
+    /// gets what the management interface knows about controller `index`.
+    fn controller_info(&self, index: u16) -> Option<ControllerInfo>;
+
+    /// gets the versions controller `index` reports about itself.
+    fn local_version(&self, index: u16) -> Option<LocalVersion>;
+}
+
+/// asks the kernel over bluetooth sockets.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Sockets;
+
+impl Hci for Sockets {
+    fn controller_info(&self, index: u16) -> Option<ControllerInfo> {
+        controller_info(index)
+    }
+
+    fn local_version(&self, index: u16) -> Option<LocalVersion> {
+        local_version(index)
+    }
+}
+
+/// answers for the controllers in the test fixtures.
+#[cfg(test)]
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Fixture;
+
+#[cfg(test)]
+impl Hci for Fixture {
+    fn controller_info(&self, index: u16) -> Option<ControllerInfo> {
+        // an intel ax211 (bluetooth 5.3), powered on
+        (index == 0).then_some(ControllerInfo {
+            hci_version: 12,
+            supported_settings: 0x003e_feff,
+            current_settings: 0x0002_0ad1,
+        })
+    }
+
+    fn local_version(&self, index: u16) -> Option<LocalVersion> {
+        (index == 0).then_some(LocalVersion {
+            hci_version: 12,
+            lmp_version: 12,
+        })
+    }
+}
+
+/// one message from the kernel, checked against the request we sent.
+#[derive(Debug, PartialEq)]
+enum Reply<'a> {
+    /// the reply's parameters.
+    Done(&'a [u8]),
+    /// the request failed with the given status.
+    Failed(u8),
+    /// a message about something else.
+    Other,
+}
+
+/// asks the management interface about the controller with the given index.
+#[tracing::instrument]
+fn controller_info(index: u16) -> Option<ControllerInfo> {
+    let socket = open(HCI_DEV_NONE, HCI_CHANNEL_CONTROL)?;
+
+    // a `struct mgmt_hdr` with no parameters
+    let mut request = Vec::with_capacity(6);
+    request.extend(MGMT_OP_READ_INFO.to_le_bytes());
+    request.extend(index.to_le_bytes());
+    request.extend(0_u16.to_le_bytes());
+
+    let reply = request_reply(&socket, &request, |message| {
+        mgmt_reply(message, index, MGMT_OP_READ_INFO)
+    })?;
+    parse_info(&reply)
+}
+
+/// asks the controller with the given index for its versions.
+#[tracing::instrument]
+fn local_version(index: u16) -> Option<LocalVersion> {
+    let socket = open(index, HCI_CHANNEL_RAW)?;
+
+    // only listen for command replies
+    let filter = HciFilter {
+        type_mask: 1 << HCI_EVENT_PKT,
+        event_mask: [(1 << HCI_EV_CMD_COMPLETE) | (1 << HCI_EV_CMD_STATUS), 0],
+        opcode: 0,
+    };
+    // SAFETY: `filter` is a valid `struct hci_filter` for its size
+    let result = unsafe {
+        libc::setsockopt(
+            socket.as_raw_fd(),
+            SOL_HCI,
+            HCI_FILTER,
+            core::ptr::addr_of!(filter).cast(),
+            core::mem::size_of::<HciFilter>() as libc::socklen_t,
+        )
+    };
+    if result < 0 {
+        tracing::debug!(
+            "Failed to set the HCI socket filter. (err: {})",
+            std::io::Error::last_os_error()
+        );
+        return None;
+    }
+
+    let mut request = vec![HCI_COMMAND_PKT];
+    request.extend(HCI_OP_READ_LOCAL_VERSION.to_le_bytes());
+    request.push(0);
+
+    let reply = request_reply(&socket, &request, |message| {
+        hci_reply(message, HCI_OP_READ_LOCAL_VERSION)
+    })?;
+    parse_local_version(&reply)
+}
+
+/// converts a bluetooth (hci or lmp) version number to the spec version,
+/// like `5.3`.
+#[tracing::instrument]
+pub(crate) fn version_name(version: u8) -> Option<&'static str> {
+    Some(match version {
+        0 => "1.0b",
+        1 => "1.1",
+        2 => "1.2",
+        3 => "2.0",
+        4 => "2.1",
+        5 => "3.0",
+        6 => "4.0",
+        7 => "4.1",
+        8 => "4.2",
+        9 => "5.0",
+        10 => "5.1",
+        11 => "5.2",
+        12 => "5.3",
+        13 => "5.4",
+        14 => "6.0",
+        15 => "6.1",
+        other => {
+            tracing::debug!("Unknown Bluetooth version `{other}`.");
+            return None;
+        }
+    })
+}
+
+/// parses the reply to `MGMT_OP_READ_INFO`.
+///
+/// it starts with the controller's address (six bytes), then its version,
+/// manufacturer, and settings.
+#[tracing::instrument(skip(data))]
+fn parse_info(data: &[u8]) -> Option<ControllerInfo> {
+    Some(ControllerInfo {
+        hci_version: *data.get(6)?,
+        supported_settings: read_u32(data.get(9..13)?)?,
+        current_settings: read_u32(data.get(13..17)?)?,
+    })
+}
+
+/// parses the reply to `HCI_OP_READ_LOCAL_VERSION`, after its status.
+///
+/// that's the hci version and revision, then the lmp version, manufacturer,
+/// and subversion.
+#[tracing::instrument(skip(data))]
+fn parse_local_version(data: &[u8]) -> Option<LocalVersion> {
+    Some(LocalVersion {
+        hci_version: *data.first()?,
+        lmp_version: *data.get(3)?,
+    })
+}
+
+/// checks whether a management event is the reply to `opcode` on the
+/// controller at `index`.
+#[tracing::instrument(skip(message))]
+fn mgmt_reply(message: &[u8], index: u16, opcode: u16) -> Reply<'_> {
+    // a `struct mgmt_hdr`, then its parameters
+    let (Some(event), Some(event_index), Some(len)) = (
+        message.get(0..2).and_then(read_u16),
+        message.get(2..4).and_then(read_u16),
+        message.get(4..6).and_then(read_u16),
+    ) else {
+        return Reply::Other;
+    };
+    let Some(params) = message.get(6..6 + usize::from(len)) else {
+        tracing::warn!("Got a malformed Bluetooth management event.");
+        return Reply::Other;
+    };
+
+    // both replies start with the opcode and status
+    let (Some(reply_opcode), Some(status)) = (params.get(0..2).and_then(read_u16), params.get(2))
+    else {
+        return Reply::Other;
+    };
+    if event_index != index || reply_opcode != opcode {
+        return Reply::Other;
+    }
+
+    match (event, *status) {
+        (MGMT_EV_CMD_COMPLETE, 0) => Reply::Done(&params[3..]),
+        (MGMT_EV_CMD_COMPLETE | MGMT_EV_CMD_STATUS, status) => Reply::Failed(status),
+        _ => Reply::Other,
+    }
+}
+
+/// checks whether an hci event packet is the reply to `opcode`.
+#[tracing::instrument(skip(message))]
+fn hci_reply(message: &[u8], opcode: u16) -> Reply<'_> {
+    let (Some(HCI_EVENT_PKT), Some(event), Some(len)) = (
+        message.first().copied(),
+        message.get(1).copied(),
+        message.get(2).copied(),
+    ) else {
+        return Reply::Other;
+    };
+    let Some(params) = message.get(3..3 + usize::from(len)) else {
+        tracing::warn!("Got a malformed HCI event.");
+        return Reply::Other;
+    };
+
+    match event {
+        // the number of allowed commands, the opcode, then the reply (which
+        // starts with its status)
+        HCI_EV_CMD_COMPLETE => match (params.get(1..3).and_then(read_u16), params.get(3)) {
+            (Some(reply_opcode), Some(0)) if reply_opcode == opcode => Reply::Done(&params[4..]),
+            (Some(reply_opcode), Some(status)) if reply_opcode == opcode => Reply::Failed(*status),
+            _ => Reply::Other,
+        },
+
+        // the status, the number of allowed commands, then the opcode. a
+        // zero status just means "working on it"
+        HCI_EV_CMD_STATUS => match (params.first(), params.get(2..4).and_then(read_u16)) {
+            (Some(status), Some(reply_opcode)) if *status != 0 && reply_opcode == opcode => {
+                Reply::Failed(*status)
+            }
+            _ => Reply::Other,
+        },
+
+        _ => Reply::Other,
+    }
+}
+
+/// opens a bluetooth socket on the given device and channel.
+#[tracing::instrument]
+fn open(dev: u16, channel: u16) -> Option<OwnedFd> {
+    // SAFETY: `socket` has no preconditions
+    let fd = unsafe {
+        libc::socket(
+            libc::AF_BLUETOOTH,
+            libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+            BTPROTO_HCI,
+        )
+    };
+    if fd < 0 {
+        tracing::debug!(
+            "Failed to open a Bluetooth socket. (err: {})",
+            std::io::Error::last_os_error()
+        );
+        return None;
+    }
+
+    // SAFETY: we just created this fd, and nothing else owns it
+    let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    // don't wait forever on a controller that never answers
+    let timeout = libc::timeval {
+        tv_sec: 1,
+        tv_usec: 0,
+    };
+    // SAFETY: `timeout` is a valid `struct timeval` for its size
+    unsafe {
+        libc::setsockopt(
+            socket.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_RCVTIMEO,
+            core::ptr::addr_of!(timeout).cast(),
+            core::mem::size_of::<libc::timeval>() as libc::socklen_t,
+        )
+    };
+
+    let addr = SockaddrHci {
+        family: libc::AF_BLUETOOTH as libc::sa_family_t,
+        dev,
+        channel,
+    };
+    // SAFETY: `addr` is a valid `struct sockaddr_hci` for its size
+    let result = unsafe {
+        libc::bind(
+            socket.as_raw_fd(),
+            core::ptr::addr_of!(addr).cast(),
+            core::mem::size_of::<SockaddrHci>() as libc::socklen_t,
+        )
+    };
+    if result < 0 {
+        tracing::debug!(
+            "Failed to bind a Bluetooth socket. (dev: {dev}, channel: {channel}, err: {})",
+            std::io::Error::last_os_error()
+        );
+        return None;
+    }
+
+    Some(socket)
+}
+
+/// sends `request`, then waits for a message that `check` says is its reply.
+#[tracing::instrument(skip(socket, request, check))]
+fn request_reply(
+    socket: &OwnedFd,
+    request: &[u8],
+    check: impl Fn(&[u8]) -> Reply<'_>,
+) -> Option<Vec<u8>> {
+    // SAFETY: `request` is valid for its length
+    let sent = unsafe {
+        libc::send(
+            socket.as_raw_fd(),
+            request.as_ptr().cast(),
+            request.len(),
+            0,
+        )
+    };
+    if sent < 0 {
+        tracing::debug!(
+            "Failed to send a Bluetooth request. (err: {})",
+            std::io::Error::last_os_error()
+        );
+        return None;
+    }
+
+    let mut buf = vec![0_u8; 1024];
+    for _ in 0..MAX_MESSAGES {
+        // SAFETY: `buf` is valid for its length
+        let received =
+            unsafe { libc::recv(socket.as_raw_fd(), buf.as_mut_ptr().cast(), buf.len(), 0) };
+        let Ok(received) = usize::try_from(received) else {
+            tracing::debug!(
+                "Failed to receive a Bluetooth reply. (err: {})",
+                std::io::Error::last_os_error()
+            );
+            return None;
+        };
+
+        match check(&buf[..received]) {
+            Reply::Done(data) => return Some(data.to_vec()),
+            Reply::Failed(status) => {
+                tracing::debug!("Bluetooth request failed. (status: {status})");
+                return None;
+            }
+            Reply::Other => continue,
+        }
+    }
+
+    tracing::debug!("Gave up waiting for a Bluetooth reply.");
+    None
+}
+
+fn read_u16(data: &[u8]) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(0..2)?.try_into().ok()?))
+}
+
+fn read_u32(data: &[u8]) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(0..4)?.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// the `MGMT_OP_READ_INFO` reply from an intel ax211 (bluetooth 5.3),
+    /// powered on
+    fn ax211_info() -> Vec<u8> {
+        let mut params = vec![0x04, 0x00, 0x00]; // opcode, then status
+        params.extend([0x5e, 0x1a, 0x5b, 0x0a, 0x1e, 0xa4]); // address
+        params.push(12); // version
+        params.extend(2_u16.to_le_bytes()); // manufacturer (intel)
+        params.extend(0x003e_feff_u32.to_le_bytes()); // supported settings
+        params.extend(0x0002_0ad1_u32.to_le_bytes()); // current settings
+        params.extend([0x0c, 0x01, 0x1c]); // class of device
+        params.extend([0; 249 + 11]); // names
+
+        let mut message = Vec::new();
+        message.extend(MGMT_EV_CMD_COMPLETE.to_le_bytes());
+        message.extend(0_u16.to_le_bytes());
+        message.extend((params.len() as u16).to_le_bytes());
+        message.extend(params);
+        message
+    }
+
+    #[test]
+    fn check_controller_info() {
+        let message = ax211_info();
+        let Reply::Done(data) = mgmt_reply(&message, 0, MGMT_OP_READ_INFO) else {
+            panic!("not a reply");
+        };
+
+        let info = parse_info(data).unwrap();
+        assert_eq!(info.hci_version, 12);
+        assert!(info.powered());
+        assert_eq!(
+            info.features(),
+            vec![
+                BluetoothFeature::BrEdr,
+                BluetoothFeature::LowEnergy,
+                BluetoothFeature::SecureConnections,
+                BluetoothFeature::WidebandSpeech,
+                BluetoothFeature::LeAudio,
+                BluetoothFeature::LeAudioBroadcast,
+            ]
+        );
+
+        // replies for other controllers aren't ours
+        assert_eq!(mgmt_reply(&message, 1, MGMT_OP_READ_INFO), Reply::Other);
+    }
+
+    #[test]
+    fn check_mgmt_failure() {
+        // `MGMT_STATUS_INVALID_INDEX`, for a controller that doesn't exist
+        let message = [0x02, 0x00, 0x05, 0x00, 0x03, 0x00, 0x04, 0x00, 0x11];
+        assert_eq!(
+            mgmt_reply(&message, 5, MGMT_OP_READ_INFO),
+            Reply::Failed(0x11)
+        );
+    }
+
+    #[test]
+    fn check_local_version() {
+        // a command complete event with bluetooth 5.3 for both
+        let message = [
+            0x04, 0x0e, 0x0c, 0x01, 0x01, 0x10, 0x00, 0x0c, 0x00, 0x01, 0x0c, 0x02, 0x00, 0x00,
+            0x01,
+        ];
+        let Reply::Done(data) = hci_reply(&message, HCI_OP_READ_LOCAL_VERSION) else {
+            panic!("not a reply");
+        };
+
+        let version = parse_local_version(data).unwrap();
+        assert_eq!(version.hci_version, 12);
+        assert_eq!(version.lmp_version, 12);
+        assert_eq!(version_name(version.lmp_version), Some("5.3"));
+
+        // pending commands aren't done yet
+        let pending = [0x04, 0x0f, 0x04, 0x00, 0x01, 0x01, 0x10];
+        assert_eq!(hci_reply(&pending, HCI_OP_READ_LOCAL_VERSION), Reply::Other);
+    }
+}
@@ -0,0 +1,202 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    prelude::internal::*,
+    report::components::{self, usb::linux::usb_vendor_and_id, Context},
+};
+
+mod hci;
+
+use hci::Hci;
+
+/// finds info about the bluetooth controllers on the system.
+#[tracing::instrument(skip(context))]
+pub(crate) async fn get(context: &Context) -> GhrResult<Vec<ComponentInfo>> {
+    all("/sys/class/bluetooth", hci::Sockets, context).await
+}
+
+/// gets info about every controller in the given class directory.
+#[tracing::instrument(skip(context))]
+async fn all(
+    path: impl AsRef<Path> + std::fmt::Debug,
+    hci: impl Hci,
+    context: &Context,
+) -> GhrResult<Vec<ComponentInfo>> {
+    let mut controllers = Vec::new();
+    for entry in components::linux::devices(path).await? {
+        let path = entry.path();
+
+        // connections to other devices show up here too, like `hci0:256`
+        let is_controller = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix("hci"))
+            .is_some_and(|index| index.parse::<u16>().is_ok());
+
+        if is_controller {
+            controllers.push(one(&path, hci.clone(), context).await);
+        }
+    }
+
+    Ok(controllers)
+}
+
+/// grabs info about the controller at `path`, like `/sys/class/bluetooth/hci0`.
+#[tracing::instrument(skip(context))]
+async fn one(path: &Path, hci: impl Hci, context: &Context) -> ComponentInfo {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string());
+    let index = name
+        .as_deref()
+        .and_then(|name| name.strip_prefix("hci"))
+        .and_then(|index| index.parse::<u16>().ok());
+
+    let device_path = path.join("device");
+    let (subsystem, driver, rfkill, status) = futures::join! {
+        subsystem(&device_path),
+        driver_name(&device_path),
+        rfkill(path),
+        components::linux::status(&device_path, context),
+    };
+
+    // the controller's device says what it's plugged into
+    let canonical = async_fs::canonicalize(&device_path).await.ok();
+    let (bus, device, id, vendor_id) = match subsystem.as_deref() {
+        // controllers are usb interfaces, so the ids are on their parent
+        Some("usb") => {
+            let usb_device = canonical
+                .as_deref()
+                .and_then(Path::parent)
+                .map(Path::to_path_buf);
+            let (vendor_id, id) = match &usb_device {
+                Some(usb_device) => usb_vendor_and_id(usb_device).await,
+                None => (None, None),
+            };
+            (ComponentBus::Usb, file_name(usb_device), id, vendor_id)
+        }
+        Some("pci") => {
+            let civ = Civ::new(path).await;
+            (ComponentBus::Pci, file_name(canonical), civ.id, civ.vendor)
+        }
+        // sdio ids aren't in any database we have, so keep them raw
+        Some("sdio") => {
+            let (vendor_id, id) = futures::join! {
+                sysfs_value_opt::<String>(device_path.join("vendor")),
+                sysfs_value_opt::<String>(device_path.join("device")),
+            };
+            (
+                ComponentBus::Other("SDIO".into()),
+                file_name(canonical),
+                id,
+                vendor_id,
+            )
+        }
+        // uart controllers (like most qualcomm and broadcom chips on arm)
+        Some("serial") => (ComponentBus::Serial, file_name(canonical), None, None),
+        other => {
+            tracing::debug!("Bluetooth controller is on an unknown bus. (subsystem: {other:?})");
+            (ComponentBus::Unknown, file_name(canonical), None, None)
+        }
+    };
+
+    // the kernel's bluetooth stack knows the rest
+    let (info, version) = match index {
+        Some(index) => {
+            blocking::unblock(move || (hci.controller_info(index), hci.local_version(index))).await
+        }
+        None => (None, None),
+    };
+
+    let hci_version = info
+        .as_ref()
+        .map(|info| info.hci_version)
+        .or(version.as_ref().map(|version| version.hci_version))
+        .and_then(hci::version_name);
+    let lmp_version = version
+        .as_ref()
+        .and_then(|version| hci::version_name(version.lmp_version));
+    let features = info
+        .as_ref()
+        .map(|info| info.features())
+        .filter(|features| !features.is_empty());
+
+    ComponentInfo {
+        bus,
+        id,
+        class: Some("Bluetooth".into()),
+        vendor_id,
+        status: status.assess(),
+        desc: ComponentDescription::BluetoothDescription(BluetoothDescription {
+            name,
+            device,
+            driver,
+            hci_version: hci_version.map(String::from),
+            lmp_version: lmp_version.map(String::from),
+            features,
+            powered: info.as_ref().map(|info| info.powered()),
+            rfkill,
+        }),
+    }
+}
+
+/// grabs the last part of a path, like `3-10` or `0000:00:14.3`.
+fn file_name(path: Option<PathBuf>) -> Option<String> {
+    path?
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn check_bluetooth() {
+        let controllers = all(bluetooth_path(), hci::Fixture, &Context::default())
+            .await
+            .unwrap();
+
+        // the connection in there isn't a controller
+        assert_eq!(controllers.len(), 1);
+        let info = &controllers[0];
+
+        assert_eq!(info.bus(), ComponentBus::Usb);
+        assert_eq!(info.vendor_id().as_deref(), Some("Intel Corp."));
+        assert_eq!(info.id().as_deref(), Some("AX211 Bluetooth"));
+
+        let ComponentDescription::BluetoothDescription(desc) = info.desc() else {
+            panic!("wrong desc");
+        };
+
+        assert_eq!(desc.name.as_deref(), Some("hci0"));
+        assert_eq!(desc.device.as_deref(), Some("3-10"));
+        assert_eq!(desc.driver.as_deref(), Some("btusb"));
+        assert_eq!(
+            desc.rfkill,
+            Some(Rfkill {
+                soft_blocked: true,
+                hard_blocked: false,
+            })
+        );
+
+        // the rest comes from the bluetooth stack
+        assert_eq!(desc.hci_version.as_deref(), Some("5.3"));
+        assert_eq!(desc.lmp_version.as_deref(), Some("5.3"));
+        assert_eq!(desc.powered, Some(true));
+        assert!(desc
+            .features
+            .is_some_and(|features| features.contains(&BluetoothFeature::LeAudio)));
+
+        // its driver is bound
+        assert_eq!(info.status().unwrap().driver_bound, Some(true));
+    }
+
+    #[tracing::instrument]
+    fn bluetooth_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!(
+            "{root}/tests/assets/linux/sysfs/sys/class/bluetooth"
+        ))
+    }
+}
@@ -0,0 +1,90 @@
+//! bluetooth: bluetooth controllers (radios)
+
+use crate::prelude::internal::*;
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+/// A description of a Bluetooth controller.
+///
+/// Controllers are usually part of a USB, PCI, or SDIO device (often shared
+/// with a Wi-Fi card) that's reported on its own. This describes the
+/// Bluetooth side of it.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct BluetoothDescription {
+    /// The controller's name, like `hci0`.
+    pub name: Option<String>,
+
+    /// The device the controller is part of, like the USB port path `3-10`
+    /// or the PCI address `0000:00:14.3`.
+    pub device: Option<String>,
+
+    /// The kernel driver bound to the controller's device, like `btusb`.
+    pub driver: Option<String>,
+
+    /// The version of the Bluetooth spec the controller's host interface
+    /// (HCI) follows, like `5.3`.
+    pub hci_version: Option<String>,
+
+    /// The version of the Bluetooth spec the controller's radio (link
+    /// manager) follows, like `5.3`.
+    pub lmp_version: Option<String>,
+
+    /// What the controller can do.
+    pub features: Option<Vec<BluetoothFeature>>,
+
+    /// Whether the controller is turned on.
+    pub powered: Option<bool>,
+
+    /// Whether the controller's radio is blocked.
+    pub rfkill: Option<Rfkill>,
+}
+
+/// Something a Bluetooth controller supports.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    TypeScript,
+)]
+#[non_exhaustive]
+pub enum BluetoothFeature {
+    /// "Classic" Bluetooth, used by most headphones and older devices.
+    BrEdr,
+    /// Bluetooth Low Energy.
+    LowEnergy,
+    /// Secure Connections pairing.
+    SecureConnections,
+    /// Wideband speech (mSBC), for better call audio.
+    WidebandSpeech,
+    /// LE Audio, which uses connected isochronous streams.
+    LeAudio,
+    /// LE Audio broadcasts (Auracast).
+    LeAudioBroadcast,
+}
+
+/// Gets info about the system's Bluetooth controllers.
+#[tracing::instrument]
+pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
+    get_with(&super::Context::load().await).await
+}
+
+/// does the same as [`get`], looking devices up in the report's shared
+/// `context`.
+#[tracing::instrument(skip(context))]
+#[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+pub(crate) async fn get_with(context: &super::Context) -> GhrResult<Vec<ComponentInfo>> {
+    #[cfg(target_os = "linux")]
+    return linux::get(context).await;
+
+    // TODO: use `Win32_PnPEntity` with the `Bluetooth` class on windows
+    #[cfg(target_os = "windows")]
+    return Ok(Vec::new());
+}
@@ -0,0 +1,487 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    prelude::internal::*,
+    report::components::{self, usb::linux::usb_vendor_and_id, Context},
+};
+
+mod v4l2;
+
+use v4l2::V4l2;
+
+/// drivers for image processors that read from mipi sensors, like intel's
+/// ipu6.
+///
+/// these are matched by prefix, since some have a suffix for each part, like
+/// `intel_ipu6.isys`.
+const MIPI_DRIVERS: &[&str] = &[
+    "intel-ipu",
+    "intel_ipu",
+    "ipu3-",
+    "rkisp1",
+    "rkcif",
+    "qcom-camss",
+    "sun6i-csi",
+    "imx7-csi",
+    "imx-mipi-csis",
+    "bcm2835-unicam",
+    "unicam",
+];
+
+/// finds info about the cameras (and other video devices) on the system.
+#[tracing::instrument(skip(context))]
+pub(crate) async fn get(context: &Context) -> GhrResult<Vec<ComponentInfo>> {
+    all(
+        "/sys/class/video4linux",
+        "/sys/class/media",
+        "/dev",
+        v4l2::Ioctl,
+        context,
+    )
+    .await
+}
+
+/// one video4linux node, like `video0` or `v4l-subdev1`.
+#[derive(Debug)]
+struct Node {
+    /// the node's name in `dev_path`.
+    name: String,
+    /// what the driver calls the node, like `Intel IPU6 ISYS Capture 0`.
+    label: Option<String>,
+    /// the node's `V4L2_CAP_*` bits.
+    caps: Option<u32>,
+}
+
+/// the nodes (and media controller) belonging to one device.
+#[derive(Debug, Default)]
+struct Group {
+    nodes: Vec<Node>,
+    model: Option<String>,
+}
+
+/// gets info about every video device.
+///
+/// `v4l_path` and `media_path` should be the `video4linux` and `media`
+/// classes in `sysfs`, and the device nodes should be in `dev_path`.
+#[tracing::instrument(skip(context))]
+async fn all(
+    v4l_path: impl AsRef<Path> + std::fmt::Debug,
+    media_path: impl AsRef<Path> + std::fmt::Debug,
+    dev_path: impl AsRef<Path> + std::fmt::Debug,
+    v4l2: impl V4l2,
+    context: &Context,
+) -> GhrResult<Vec<ComponentInfo>> {
+    let dev_path = dev_path.as_ref();
+
+    // group the nodes by the device they belong to, remembering which device
+    // each node number (like `81:3`) is on
+    let mut groups: BTreeMap<PathBuf, Group> = BTreeMap::new();
+    let mut owners: BTreeMap<(u32, u32), PathBuf> = BTreeMap::new();
+    for entry in components::linux::devices(v4l_path).await? {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        let Ok(device) = async_fs::canonicalize(path.join("device")).await else {
+            tracing::debug!("Video node `{name}` has no device.");
+            continue;
+        };
+
+        // sub-devices (like sensors) don't answer `VIDIOC_QUERYCAP`. the
+        // ioctl blocks, so keep it off the executor
+        let caps = if name.starts_with("video") {
+            let (v4l2, node) = (v4l2.clone(), dev_path.join(&name));
+            blocking::unblock(move || v4l2.device_caps(&node)).await
+        } else {
+            None
+        };
+        let (label, number) = futures::join! {
+            sysfs_value_opt::<String>(path.join("name")),
+            dev_number(&path),
+        };
+
+        if let Some(number) = number {
+            owners.insert(number, device.clone());
+        }
+
+        groups
+            .entry(device)
+            .or_default()
+            .nodes
+            .push(Node { name, label, caps });
+    }
+
+    // media controllers name the whole device, like `Intel IPU6 ISYS`. their
+    // graph also has the nodes of other devices that are part of the same
+    // camera, like a mipi sensor's, so we remember which device hosts those
+    let mut hosts: BTreeMap<PathBuf, PathBuf> = BTreeMap::new();
+    for entry in components::linux::devices(media_path)
+        .await
+        .unwrap_or_default()
+    {
+        let path = entry.path();
+        let (device, model) = futures::join! {
+            async_fs::canonicalize(path.join("device")),
+            sysfs_value_opt::<String>(path.join("model")),
+        };
+        let Ok(device) = device else {
+            continue;
+        };
+
+        if let (Some(group), Some(model)) = (groups.get_mut(&device), model) {
+            group.model.get_or_insert(model);
+        }
+
+        let graph = {
+            let (v4l2, node) = (v4l2.clone(), dev_path.join(entry.file_name()));
+            blocking::unblock(move || v4l2.media_devnodes(&node)).await
+        };
+        for number in graph.unwrap_or_default() {
+            if let Some(owner) = owners.get(&number).filter(|owner| **owner != device) {
+                hosts.insert(owner.clone(), device.clone());
+            }
+        }
+    }
+
+    let mut cameras = Vec::new();
+    for (device, mut group) in groups {
+        group.nodes.sort_by(|a, b| a.name.cmp(&b.name));
+        let camera = one(&device, group, context).await;
+        cameras.push((device, camera));
+    }
+
+    // mipi sensors are i2c devices of their own, but they're really part of
+    // the camera behind the image processor whose graph they're in
+    let (sensors, mut cameras): (Vec<_>, Vec<_>) = cameras
+        .into_iter()
+        .partition(|(_, camera)| camera.bus == ComponentBus::Other("I2C".into()));
+
+    for (device, sensor) in sensors {
+        let name = match &sensor.desc {
+            ComponentDescription::CameraDescription(desc) => desc.name.clone(),
+            _ => None,
+        };
+        let host = hosts
+            .get(&device)
+            .and_then(|host| cameras.iter().position(|(device, _)| device == host));
+
+        match (host, name) {
+            (Some(host), Some(name)) => {
+                if let ComponentDescription::CameraDescription(desc) = &mut cameras[host].1.desc {
+                    desc.sensors.get_or_insert_with(Vec::new).push(name);
+                }
+            }
+
+            // sensors we can't place are all we know about their camera
+            _ => cameras.push((device, sensor)),
+        }
+    }
+
+    Ok(cameras.into_iter().map(|(_, camera)| camera).collect())
+}
+
+/// reads the device number of a node, like `81:3`.
+#[tracing::instrument]
+async fn dev_number(path: &Path) -> Option<(u32, u32)> {
+    let number = sysfs_value_opt::<String>(path.join("dev")).await?;
+    let (major, minor) = number.split_once(':')?;
+
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+/// describes the device at `device` using its video nodes.
+#[tracing::instrument(skip(context))]
+async fn one(device: &Path, group: Group, context: &Context) -> ComponentInfo {
+    let (subsystem, driver, status) = futures::join! {
+        subsystem(device),
+        driver_name(device),
+        components::linux::status(device, context),
+    };
+
+    // the device says what it's plugged into
+    let (bus, location, id, vendor_id) = match subsystem.as_deref() {
+        // cameras are usb interfaces, so the ids are on their parent
+        Some("usb") => {
+            let usb_device = device.parent();
+            let (vendor_id, id) = match usb_device {
+                Some(usb_device) => usb_vendor_and_id(usb_device).await,
+                None => (None, None),
+            };
+            (ComponentBus::Usb, file_name(usb_device), id, vendor_id)
+        }
+        Some("pci") => {
+            let (id, vendor_id) = pci_names(device).await;
+            (ComponentBus::Pci, file_name(Some(device)), id, vendor_id)
+        }
+        // newer image processors split into "auxiliary" devices under their
+        // pci device
+        Some("auxiliary") => {
+            let pci_device = device.parent();
+            let (id, vendor_id) = match pci_device {
+                Some(pci_device) => pci_names(pci_device).await,
+                None => (None, None),
+            };
+            (ComponentBus::Pci, file_name(pci_device), id, vendor_id)
+        }
+        Some("i2c") => (
+            ComponentBus::Other("I2C".into()),
+            file_name(Some(device)),
+            None,
+            None,
+        ),
+        Some("platform") => (ComponentBus::Sys, file_name(Some(device)), None, None),
+        other => {
+            tracing::debug!("Video device is on an unknown bus. (subsystem: {other:?})");
+            (ComponentBus::Unknown, file_name(Some(device)), None, None)
+        }
+    };
+
+    // combine what each node can do
+    let mut capabilities = group
+        .nodes
+        .iter()
+        .filter_map(|node| node.caps)
+        .flat_map(v4l2::capabilities)
+        .collect::<Vec<_>>();
+    capabilities.sort();
+    capabilities.dedup();
+
+    let kind = kind(subsystem.as_deref(), driver.as_deref(), &capabilities);
+
+    // sensors call themselves by their chip and address, like
+    // `ov01a10 1-0036`
+    let name = if subsystem.as_deref() == Some("i2c") {
+        group
+            .nodes
+            .iter()
+            .find_map(|node| node.label.as_deref()?.split_whitespace().next())
+            .map(String::from)
+    } else {
+        group
+            .model
+            .or_else(|| group.nodes.iter().find_map(|node| node.label.clone()))
+    };
+
+    let class = match kind {
+        CameraKind::Uvc | CameraKind::Mipi => "Camera",
+        CameraKind::Codec => "Video Codec",
+        _ => "Video",
+    };
+
+    ComponentInfo {
+        bus,
+        id,
+        class: Some(class.into()),
+        vendor_id,
+        status: status.assess(),
+        desc: ComponentDescription::CameraDescription(CameraDescription {
+            name,
+            kind,
+            driver,
+            device: location,
+            nodes: Some(group.nodes.into_iter().map(|node| node.name).collect()),
+            capabilities: (!capabilities.is_empty()).then_some(capabilities),
+            sensors: None,
+        }),
+    }
+}
+
+/// figures out what kind of video device this is.
+#[tracing::instrument]
+fn kind(
+    subsystem: Option<&str>,
+    driver: Option<&str>,
+    capabilities: &[CameraCapability],
+) -> CameraKind {
+    let is_mipi_driver =
+        driver.is_some_and(|driver| MIPI_DRIVERS.iter().any(|mipi| driver.starts_with(mipi)));
+
+    if driver == Some("uvcvideo") {
+        CameraKind::Uvc
+    } else if subsystem == Some("i2c") || is_mipi_driver {
+        CameraKind::Mipi
+    } else if capabilities.contains(&CameraCapability::MemoryToMemory)
+        && !capabilities.contains(&CameraCapability::VideoCapture)
+    {
+        CameraKind::Codec
+    } else {
+        CameraKind::Other
+    }
+}
+
+/// grabs the name and vendor of the pci device at `path`.
+#[tracing::instrument]
+async fn pci_names(path: &Path) -> (Option<String>, Option<String>) {
+    let (id, vendor_id) = futures::join! {
+        sysfs_value_opt::<String>(path.join("device")),
+        sysfs_value_opt::<String>(path.join("vendor")),
+    };
+
+    convert_to_pci_names(id, vendor_id)
+}
+
+/// grabs the last part of a path, like `3-6` or `0000:00:05.0`.
+fn file_name(path: Option<&Path>) -> Option<String> {
+    path?
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn check_cameras() {
+        let cameras = all(
+            v4l_path(),
+            media_path(),
+            "/dev",
+            v4l2::Fixture,
+            &Context::default(),
+        )
+        .await
+        .unwrap();
+
+        // the sensor is folded into the ipu6
+        assert_eq!(cameras.len(), 3);
+
+        let descs = cameras
+            .iter()
+            .map(|camera| match camera.desc() {
+                ComponentDescription::CameraDescription(desc) => desc,
+                _ => panic!("wrong desc"),
+            })
+            .collect::<Vec<_>>();
+
+        // the webcam
+        let (webcam, webcam_desc) = cameras
+            .iter()
+            .zip(&descs)
+            .find(|(_, desc)| desc.kind == CameraKind::Uvc)
+            .unwrap();
+        assert_eq!(webcam.bus(), ComponentBus::Usb);
+        assert_eq!(webcam.id().as_deref(), Some("Lenovo Integrated Camera"));
+        assert_eq!(
+            webcam_desc.name.as_deref(),
+            Some("Integrated Camera: Integrated C")
+        );
+        assert_eq!(webcam_desc.driver.as_deref(), Some("uvcvideo"));
+        assert_eq!(webcam_desc.device.as_deref(), Some("3-6"));
+        assert_eq!(
+            webcam_desc.nodes,
+            Some(vec!["video0".into(), "video1".into()])
+        );
+        assert_eq!(
+            webcam_desc.capabilities,
+            Some(vec![
+                CameraCapability::VideoCapture,
+                CameraCapability::MetadataCapture
+            ])
+        );
+        assert!(webcam_desc.sensors.is_none());
+
+        // and the mipi camera
+        let (ipu, ipu_desc) = cameras
+            .iter()
+            .zip(&descs)
+            .find(|(_, desc)| desc.kind == CameraKind::Mipi)
+            .unwrap();
+        assert_eq!(ipu.bus(), ComponentBus::Pci);
+        assert_eq!(ipu.id().as_deref(), Some("JasperLake IPU"));
+        assert_eq!(ipu_desc.name.as_deref(), Some("Intel IPU6 ISYS"));
+        assert_eq!(ipu_desc.driver.as_deref(), Some("intel_ipu6.isys"));
+        assert_eq!(ipu_desc.device.as_deref(), Some("0000:00:05.0"));
+        assert_eq!(ipu_desc.sensors, Some(vec!["ov01a10".into()]));
+        assert_eq!(
+            ipu_desc.capabilities,
+            Some(vec![CameraCapability::VideoCapture])
+        );
+
+        // the codec only works from memory to memory
+        let (codec, codec_desc) = cameras
+            .iter()
+            .zip(&descs)
+            .find(|(_, desc)| desc.kind == CameraKind::Codec)
+            .unwrap();
+        assert_eq!(codec.bus(), ComponentBus::Sys);
+        assert_eq!(codec.class.as_deref(), Some("Video Codec"));
+        assert_eq!(codec_desc.name.as_deref(), Some("vicodec-source"));
+        assert_eq!(codec_desc.device.as_deref(), Some("vicodec.0"));
+        assert_eq!(
+            codec_desc.capabilities,
+            Some(vec![CameraCapability::MemoryToMemory])
+        );
+    }
+
+    #[tokio::test]
+    async fn check_unplaced_sensor() {
+        /// knows every node, but no media graphs
+        #[derive(Clone, Debug)]
+        struct NoGraphs;
+
+        impl V4l2 for NoGraphs {
+            fn device_caps(&self, path: &Path) -> Option<u32> {
+                v4l2::Fixture.device_caps(path)
+            }
+
+            fn media_devnodes(&self, _: &Path) -> Option<Vec<(u32, u32)>> {
+                None
+            }
+        }
+
+        let cameras = all(
+            v4l_path(),
+            media_path(),
+            "/dev",
+            NoGraphs,
+            &Context::default(),
+        )
+        .await
+        .unwrap();
+
+        // without a graph, the sensor isn't given to the ipu6
+        assert_eq!(cameras.len(), 4);
+        assert!(cameras.iter().all(|camera| match camera.desc() {
+            ComponentDescription::CameraDescription(desc) => desc.sensors.is_none(),
+            _ => false,
+        }));
+
+        let sensor = cameras
+            .iter()
+            .find(|camera| camera.bus() == ComponentBus::Other("I2C".into()))
+            .unwrap();
+        assert_eq!(sensor.class.as_deref(), Some("Camera"));
+    }
+
+    #[test]
+    fn check_kind() {
+        assert_eq!(kind(Some("usb"), Some("uvcvideo"), &[]), CameraKind::Uvc);
+        assert_eq!(
+            kind(
+                Some("platform"),
+                Some("hantro-vpu"),
+                &[CameraCapability::MemoryToMemory]
+            ),
+            CameraKind::Codec
+        );
+        assert_eq!(kind(Some("pci"), Some("ipu3-cio2"), &[]), CameraKind::Mipi);
+        assert_eq!(kind(Some("pci"), Some("cx23885"), &[]), CameraKind::Other);
+    }
+
+    #[tracing::instrument]
+    fn v4l_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!(
+            "{root}/tests/assets/linux/sysfs/sys/class/video4linux"
+        ))
+    }
+
+    #[tracing::instrument]
+    fn media_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs/sys/class/media"))
+    }
+}
@@ -0,0 +1,295 @@
+//! asks video4linux device nodes what they can do.
+//!
+//! `sysfs` only knows a node's name, so we open it and use
+//! `VIDIOC_QUERYCAP`. that doesn't start streaming (or turn on any lights),
+//! but it does need access to the node, which is usually given to the
+//! `video` group or the logged-in user.
+//!
+//! media controllers are asked for their graph the same way, which says
+//! which nodes (like a sensor's) make up one camera.
+
+use std::{
+    os::{fd::AsRawFd as _, unix::fs::OpenOptionsExt as _},
+    path::Path,
+};
+
+use crate::prelude::internal::*;
+
+/// `_IOR('V', 0, struct v4l2_capability)`
+const VIDIOC_QUERYCAP: libc::c_ulong = 0x8068_5600;
+/// `_IOWR('|', 0x04, struct media_v2_topology)`
+const MEDIA_IOC_G_TOPOLOGY: libc::c_ulong = 0xc0d8_7c04;
+
+// `V4L2_CAP_*` bits from `linux/videodev2.h`
+const V4L2_CAP_VIDEO_CAPTURE: u32 = 0x0000_0001;
+const V4L2_CAP_VIDEO_OUTPUT: u32 = 0x0000_0002;
+const V4L2_CAP_VIDEO_CAPTURE_MPLANE: u32 = 0x0000_1000;
+const V4L2_CAP_VIDEO_OUTPUT_MPLANE: u32 = 0x0000_2000;
+const V4L2_CAP_VIDEO_M2M_MPLANE: u32 = 0x0000_4000;
+const V4L2_CAP_VIDEO_M2M: u32 = 0x0000_8000;
+const V4L2_CAP_META_CAPTURE: u32 = 0x0080_0000;
+const V4L2_CAP_DEVICE_CAPS: u32 = 0x8000_0000;
+
+/// `struct v4l2_capability`
+#[repr(C)]
+struct V4l2Capability {
+    driver: [u8; 16],
+    card: [u8; 32],
+    bus_info: [u8; 32],
+    version: u32,
+    capabilities: u32,
+    device_caps: u32,
+    reserved: [u32; 3],
+}
+
+/// `struct media_v2_topology`
+#[repr(C)]
+struct MediaV2Topology {
+    topology_version: u64,
+    num_entities: u32,
+    reserved1: u32,
+    ptr_entities: u64,
+    num_interfaces: u32,
+    reserved2: u32,
+    ptr_interfaces: u64,
+    num_pads: u32,
+    reserved3: u32,
+    ptr_pads: u64,
+    num_links: u32,
+    reserved4: u32,
+    ptr_links: u64,
+    reserved: [u64; 18],
+}
+
+/// `struct media_v2_interface`, with its `devnode` member
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct MediaV2Interface {
+    id: u32,
+    intf_type: u32,
+    flags: u32,
+    reserved: [u32; 9],
+    major: u32,
+    minor: u32,
+    raw: [u32; 14],
+}
+
+/// asks video4linux and media controller nodes about themselves.
+///
+/// collectors take one of these so tests can answer with fixture data
+/// instead of the host's real devices. its ioctls block, so they're sent
+/// from another thread.
+pub(crate) trait V4l2: std::fmt::Debug + Clone + Send + 'static {
+    /// gets the capability bits of the video node at `path`, like
+    /// `/dev/video0`.
+    fn device_caps(&self, path: &Path) -> Option<u32>;
+
+    /// gets the device numbers (like `(81, 3)`) of every node in the graph
+    /// of the media controller at `path`, like `/dev/media0`.
+    fn media_devnodes(&self, path: &Path) -> Option<Vec<(u32, u32)>>;
+}
+
+/// asks the kernel with ioctls on the device nodes.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Ioctl;
+
+impl V4l2 for Ioctl {
+    fn device_caps(&self, path: &Path) -> Option<u32> {
+        device_caps(path)
+    }
+
+    fn media_devnodes(&self, path: &Path) -> Option<Vec<(u32, u32)>> {
+        media_devnodes(path)
+    }
+}
+
+/// answers for the nodes in the test fixtures.
+#[cfg(test)]
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Fixture;
+
+#[cfg(test)]
+impl V4l2 for Fixture {
+    fn device_caps(&self, path: &Path) -> Option<u32> {
+        match path.file_name()?.to_str()? {
+            // the webcam's capture and metadata nodes
+            "video0" => Some(0x0420_0001),
+            "video1" => Some(0x0480_0000),
+            // the ipu6's capture node
+            "video2" => Some(0x0420_1000),
+            // and `vicodec`'s encoder
+            "video3" => Some(0x0420_4000),
+            _ => None,
+        }
+    }
+
+    fn media_devnodes(&self, path: &Path) -> Option<Vec<(u32, u32)>> {
+        match path.file_name()?.to_str()? {
+            "media0" => Some(vec![(81, 0), (81, 1)]),
+            // the ipu6's graph includes the sensor's sub-device
+            "media1" => Some(vec![(81, 2), (81, 3), (81, 4)]),
+            _ => None,
+        }
+    }
+}
+
+/// opens a device node without waiting on it.
+#[tracing::instrument]
+fn open(path: &Path) -> Option<std::fs::File> {
+    std::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open(path)
+        .inspect_err(|e| tracing::debug!("Couldn't open device node. (err: {e})"))
+        .ok()
+}
+
+/// grabs the capability bits of the video node at `path`, like `/dev/video0`.
+#[tracing::instrument]
+fn device_caps(path: &Path) -> Option<u32> {
+    let file = open(path)?;
+
+    // SAFETY: `V4l2Capability` is plain old data, so all zeroes is valid
+    let mut caps: V4l2Capability = unsafe { core::mem::zeroed() };
+
+    // SAFETY: `caps` is a valid `struct v4l2_capability`, which is what
+    // `VIDIOC_QUERYCAP` writes
+    let result = unsafe {
+        libc::ioctl(
+            file.as_raw_fd(),
+            VIDIOC_QUERYCAP as _,
+            core::ptr::addr_of_mut!(caps),
+        )
+    };
+    if result < 0 {
+        tracing::debug!(
+            "`VIDIOC_QUERYCAP` failed. (err: {})",
+            std::io::Error::last_os_error()
+        );
+        return None;
+    }
+
+    // `capabilities` covers every node of the device, while `device_caps` is
+    // just this one. old drivers only have the former
+    if caps.capabilities & V4L2_CAP_DEVICE_CAPS != 0 {
+        Some(caps.device_caps)
+    } else {
+        Some(caps.capabilities)
+    }
+}
+
+/// lists the device numbers of the nodes in the graph of the media controller
+/// at `path`, like `/dev/media0`.
+#[tracing::instrument]
+fn media_devnodes(path: &Path) -> Option<Vec<(u32, u32)>> {
+    let file = open(path)?;
+
+    // SAFETY: `MediaV2Topology` is plain old data, so all zeroes is valid
+    let mut topology: MediaV2Topology = unsafe { core::mem::zeroed() };
+
+    // with no buffers, the kernel just tells us how many interfaces there are
+    //
+    // SAFETY: `topology` is a valid `struct media_v2_topology`, which is what
+    // `MEDIA_IOC_G_TOPOLOGY` reads and writes
+    let result = unsafe {
+        libc::ioctl(
+            file.as_raw_fd(),
+            MEDIA_IOC_G_TOPOLOGY as _,
+            core::ptr::addr_of_mut!(topology),
+        )
+    };
+    if result < 0 {
+        tracing::debug!(
+            "`MEDIA_IOC_G_TOPOLOGY` failed. (err: {})",
+            std::io::Error::last_os_error()
+        );
+        return None;
+    }
+
+    // then we ask again with room for them
+    //
+    // SAFETY: `MediaV2Interface` is plain old data, so all zeroes is valid
+    let mut interfaces: Vec<MediaV2Interface> =
+        vec![unsafe { core::mem::zeroed() }; topology.num_interfaces as usize];
+    topology.num_entities = 0;
+    topology.num_pads = 0;
+    topology.num_links = 0;
+    topology.ptr_interfaces = interfaces.as_mut_ptr() as u64;
+
+    // SAFETY: `ptr_interfaces` points to `num_interfaces` interfaces, and the
+    // other arrays are null
+    let result = unsafe {
+        libc::ioctl(
+            file.as_raw_fd(),
+            MEDIA_IOC_G_TOPOLOGY as _,
+            core::ptr::addr_of_mut!(topology),
+        )
+    };
+    if result < 0 {
+        tracing::debug!(
+            "`MEDIA_IOC_G_TOPOLOGY` failed. (err: {})",
+            std::io::Error::last_os_error()
+        );
+        return None;
+    }
+
+    // the graph can shrink between the two calls
+    interfaces.truncate(topology.num_interfaces as usize);
+
+    Some(
+        interfaces
+            .iter()
+            .map(|interface| (interface.major, interface.minor))
+            .collect(),
+    )
+}
+
+/// lists the capabilities in a node's capability bits.
+#[tracing::instrument]
+pub(crate) fn capabilities(caps: u32) -> Vec<CameraCapability> {
+    [
+        (
+            V4L2_CAP_VIDEO_CAPTURE | V4L2_CAP_VIDEO_CAPTURE_MPLANE,
+            CameraCapability::VideoCapture,
+        ),
+        (
+            V4L2_CAP_VIDEO_OUTPUT | V4L2_CAP_VIDEO_OUTPUT_MPLANE,
+            CameraCapability::VideoOutput,
+        ),
+        (
+            V4L2_CAP_VIDEO_M2M | V4L2_CAP_VIDEO_M2M_MPLANE,
+            CameraCapability::MemoryToMemory,
+        ),
+        (V4L2_CAP_META_CAPTURE, CameraCapability::MetadataCapture),
+    ]
+    .into_iter()
+    .filter(|(bits, _)| caps & bits != 0)
+    .map(|(_, capability)| capability)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_capabilities() {
+        // a uvc webcam's capture node: capture and streaming
+        assert_eq!(
+            capabilities(0x0420_0001),
+            vec![CameraCapability::VideoCapture]
+        );
+
+        // and its metadata node
+        assert_eq!(
+            capabilities(0x0480_0000),
+            vec![CameraCapability::MetadataCapture]
+        );
+
+        // a stateless decoder, like `hantro-vpu`
+        assert_eq!(
+            capabilities(0x0420_4000),
+            vec![CameraCapability::MemoryToMemory]
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! camera: webcams, mipi cameras, and other video4linux devices
+
+use crate::prelude::internal::*;
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+/// A description of a camera (or other video capture device).
+///
+/// One camera often has many device nodes, like a capture node and a
+/// metadata node. These are grouped together here.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct CameraDescription {
+    /// The device's name, like `Integrated Camera: Integrated C`.
+    pub name: Option<String>,
+
+    /// What kind of device this is.
+    pub kind: CameraKind,
+
+    /// The kernel driver bound to the device, like `uvcvideo`.
+    pub driver: Option<String>,
+
+    /// The device the camera is part of, like the USB port path `3-6` or the
+    /// PCI address `0000:00:05.0`.
+    pub device: Option<String>,
+
+    /// The device's video4linux nodes, like `video0`.
+    pub nodes: Option<Vec<String>>,
+
+    /// What the device's nodes can do.
+    pub capabilities: Option<Vec<CameraCapability>>,
+
+    /// The image sensors behind a MIPI camera, like `ov01a10`.
+    pub sensors: Option<Vec<String>>,
+}
+
+/// A kind of video device.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum CameraKind {
+    /// A USB Video Class webcam, which works with most apps as-is.
+    Uvc,
+    /// A MIPI camera, where an image processor (like Intel's IPU6) reads
+    /// from a separate sensor. These usually need `libcamera` (or a vendor's
+    /// camera stack) to work.
+    Mipi,
+    /// A hardware video encoder or decoder.
+    Codec,
+    /// Something else, like a capture card or TV tuner.
+    Other,
+}
+
+/// Something a video device can do.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    TypeScript,
+)]
+#[non_exhaustive]
+pub enum CameraCapability {
+    /// Capture video, like a webcam.
+    VideoCapture,
+    /// Output video.
+    VideoOutput,
+    /// Process video from memory into memory, like a codec.
+    MemoryToMemory,
+    /// Capture metadata about the video, like timestamps.
+    MetadataCapture,
+}
+
+/// Gets info about the system's cameras and video devices.
+#[tracing::instrument]
+pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
+    get_with(&super::Context::load().await).await
+}
+
+/// does the same as [`get`], looking devices up in the report's shared
+/// `context`.
+#[tracing::instrument(skip(context))]
+#[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+pub(crate) async fn get_with(context: &super::Context) -> GhrResult<Vec<ComponentInfo>> {
+    #[cfg(target_os = "linux")]
+    return linux::get(context).await;
+
+    // TODO: use `Win32_PnPEntity` with the `Camera` class on windows
+    #[cfg(target_os = "windows")]
+    return Ok(Vec::new());
+}
@@ -8,7 +8,7 @@ use std::{
 use async_fs as fs;
 use futures::TryStreamExt;
 
-use crate::prelude::internal::*;
+use crate::{prelude::internal::*, report::components};
 
 use super::{Cache, CpuDescription, Frequency};
 
@@ -39,6 +39,7 @@ pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
     }
 
     // make each processor into a ComponentInfo
+    let sockets = cpus.len();
     let mut components = Vec::new();
     for (cpu_id, cores) in cpus {
         let mut cpu_best_speeds = Frequency {
@@ -91,13 +92,16 @@ pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
             cores: Some(cpu_cores),
         });
 
+        // and its temperatures
+        let status = components::linux::cpu_status(cpu_id as usize, sockets).await;
+
         // finally, push it to the list
         components.push(ComponentInfo {
             bus,
             id,
             class: None,
             vendor_id,
-            status: None,
+            status,
             desc,
         });
     }
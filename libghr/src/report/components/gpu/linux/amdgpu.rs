@@ -1,9 +1,12 @@
 use std::path::Path;
 
-use crate::prelude::internal::*;
+use crate::{
+    prelude::internal::*,
+    report::components::{self, Context},
+};
 
-#[tracing::instrument]
-pub(super) async fn gpu(gpu: &Path) -> GhrResult<ComponentInfo> {
+#[tracing::instrument(skip(context))]
+pub(super) async fn gpu(gpu: &Path, context: &Context) -> GhrResult<ComponentInfo> {
     let gpu = gpu.join("device");
 
     // grab some id info about the gpu
@@ -20,10 +23,11 @@ pub(super) async fn gpu(gpu: &Path) -> GhrResult<ComponentInfo> {
     );
 
     // and now some device info
-    let (video_memory, clock_speed, video_memory_speed) = futures::join! {
+    let (video_memory, clock_speed, video_memory_speed, status) = futures::join! {
         sysfs_value_opt::<u64>(gpu.join("mem_info_vram_total")),
         gpu_clock(&gpu),
-        gpu_mem_clock(&gpu)
+        gpu_mem_clock(&gpu),
+        components::linux::status(&gpu, context),
     };
 
     // map units to mibiunits
@@ -41,7 +45,7 @@ pub(super) async fn gpu(gpu: &Path) -> GhrResult<ComponentInfo> {
         id,
         class,
         vendor_id,
-        status: None,
+        status: status.assess(),
         desc: ComponentDescription::GpuDescription(gpu_info),
     })
 }
@@ -118,7 +122,7 @@ mod tests {
         logger();
 
         let path = amdgpu_path();
-        _ = gpu(&path).await.unwrap();
+        _ = gpu(&path, &Context::default()).await.unwrap();
     }
 
     #[tokio::test]
@@ -126,7 +130,7 @@ mod tests {
         logger();
 
         let path = amdgpu_path();
-        let info = gpu(&path).await.unwrap();
+        let info = gpu(&path, &Context::default()).await.unwrap();
 
         // class
         assert_eq!(
@@ -155,7 +159,7 @@ mod tests {
         logger();
 
         let path = amdgpu_path();
-        let info = gpu(&path).await.unwrap();
+        let info = gpu(&path, &Context::default()).await.unwrap();
 
         // make sure we saw a gpu here
         let ComponentDescription::GpuDescription(specs) = info.desc else {
@@ -1,25 +1,30 @@
 use std::path::Path;
 
-use crate::prelude::internal::*;
+use crate::{
+    prelude::internal::*,
+    report::components::{self, Context},
+};
 
 // TODO: find out what all gpus on linux should provide.
 //
 // (see `asahi-drm` for a nice, readable impl)
 
-#[tracing::instrument]
-pub(super) async fn gpu(path: &Path) -> GhrResult<ComponentInfo> {
+#[tracing::instrument(skip(context))]
+pub(super) async fn gpu(path: &Path, context: &Context) -> GhrResult<ComponentInfo> {
     // grab class, name, and vendor
     let (class, id, vendor_id) = {
         let civ = Civ::new(path).await;
         (civ.class, civ.id, civ.vendor)
     };
 
+    let status = components::linux::status(&path.join("device"), context).await;
+
     Ok(ComponentInfo {
         bus: ComponentBus::Pci,
         id,
         class,
         vendor_id,
-        status: None,
+        status: status.assess(),
         desc: ComponentDescription::GpuDescription(GpuDescription {
             clock_speed: None,
             video_memory: None,
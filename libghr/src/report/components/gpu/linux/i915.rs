@@ -1,9 +1,12 @@
 use std::path::Path;
 
-use crate::prelude::internal::*;
+use crate::{
+    prelude::internal::*,
+    report::components::{self, Context},
+};
 
-#[tracing::instrument]
-pub(super) async fn gpu(gpu: &Path) -> GhrResult<ComponentInfo> {
+#[tracing::instrument(skip(context))]
+pub(super) async fn gpu(gpu: &Path, context: &Context) -> GhrResult<ComponentInfo> {
     // grab some id info about the gpu
     let (id, vendor_id, class) = futures::join! {
         sysfs_value_opt::<String>(gpu.join("device/device")),
@@ -27,12 +30,15 @@ pub(super) async fn gpu(gpu: &Path) -> GhrResult<ComponentInfo> {
         video_memory_speed: None, // -> look at the source code?
     };
 
+    // discrete cards have their own sensors
+    let status = components::linux::status(&gpu.join("device"), context).await;
+
     Ok(ComponentInfo {
         bus: ComponentBus::Pci,
         id,
         class,
         vendor_id,
-        status: None,
+        status: status.assess(),
         desc: ComponentDescription::GpuDescription(gpu_info),
     })
 }
@@ -2,7 +2,7 @@ use async_fs::{self, read_link, DirEntry};
 use futures::TryStreamExt;
 use regex::Regex;
 
-use crate::prelude::internal::*;
+use crate::{prelude::internal::*, report::components::Context};
 
 /// a static path where gpus reprs are placed by the kernel/drivers
 const GPU_LISTING: &str = "/sys/class/drm";
@@ -11,8 +11,8 @@ mod amdgpu;
 mod generic;
 mod i915;
 
-#[tracing::instrument]
-pub(super) async fn get() -> GhrResult<Vec<ComponentInfo>> {
+#[tracing::instrument(skip(context))]
+pub(super) async fn get(context: &Context) -> GhrResult<Vec<ComponentInfo>> {
     // grab devices from the system
     let devices = devices().await?;
 
@@ -33,8 +33,8 @@ pub(super) async fn get() -> GhrResult<Vec<ComponentInfo>> {
         // based on the driver, pick an implementation to use
         tracing::debug!("parsing gpu with `{driver}` driver at `{path_str}`...",);
         let info: Result<ComponentInfo, GhrError> = match driver.to_ascii_lowercase().as_str() {
-            "amdgpu" => amdgpu::gpu(&path).await,
-            "i915" => i915::gpu(&path).await,
+            "amdgpu" => amdgpu::gpu(&path, context).await,
+            "i915" => i915::gpu(&path, context).await,
             "nvidia" => {
                 // ignore nvidia gpus; they're located with `nvml`, not `sysfs`
                 //
@@ -46,7 +46,7 @@ pub(super) async fn get() -> GhrResult<Vec<ComponentInfo>> {
                     "No information about this generic device. An \
                 empty output will result for this entry. (driver: {driver}, path: {path_str})"
                 );
-                generic::gpu(&path).await
+                generic::gpu(&path, context).await
             }
         };
 
@@ -26,6 +26,14 @@ pub struct GpuDescription {
 #[tracing::instrument]
 /// Gets information about the system's GPU(s).
 pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
+    get_with(&super::Context::load().await).await
+}
+
+/// does the same as [`get`], looking devices up in the report's shared
+/// `context`.
+#[tracing::instrument(skip(context))]
+#[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+pub(crate) async fn get_with(context: &super::Context) -> GhrResult<Vec<ComponentInfo>> {
     let mut gpus = Vec::new();
 
     // only run if the platform has nvidia
@@ -35,7 +43,7 @@ pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
     }
 
     #[cfg(target_os = "linux")]
-    if let Ok(mut linux_gpus) = linux::get().await {
+    if let Ok(mut linux_gpus) = linux::get(context).await {
         gpus.append(&mut linux_gpus);
     }
 
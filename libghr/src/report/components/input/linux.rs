@@ -0,0 +1,466 @@
+use std::path::Path;
+
+use crate::prelude::internal::*;
+
+/// finds info about all input devices on the system.
+#[tracing::instrument]
+pub(crate) async fn get() -> GhrResult<Vec<ComponentInfo>> {
+    all("/proc/bus/input/devices", "/sys").await
+}
+
+/// gets info about every input device listed in `devices_path`, which is
+/// usually `/proc/bus/input/devices`.
+///
+/// devices that aren't any known kind, like power buttons and audio jacks,
+/// are skipped.
+#[tracing::instrument]
+async fn all(
+    devices_path: impl AsRef<Path> + std::fmt::Debug,
+    sys_path: impl AsRef<Path> + std::fmt::Debug,
+) -> GhrResult<Vec<ComponentInfo>> {
+    let devices = sysfs_value::<String>(devices_path).await?;
+
+    let mut infos = Vec::new();
+    for device in parse_devices(&devices) {
+        if let Some(info) = one(device, sys_path.as_ref()).await {
+            infos.push(info);
+        }
+    }
+
+    Ok(infos)
+}
+
+/// grabs info about one input device.
+#[tracing::instrument(skip(sys_path))]
+async fn one(mut device: Device, sys_path: &Path) -> Option<ComponentInfo> {
+    // prefer the capabilities in `sysfs`, falling back to the ones we parsed
+    let input_path = device
+        .sysfs
+        .as_ref()
+        .map(|sysfs| sys_path.join(sysfs.trim_start_matches('/')));
+    let mut driver = None;
+    if let Some(ref input_path) = input_path {
+        capabilities(input_path, &mut device.caps).await;
+        driver = driver_name(input_path.join("device")).await;
+    }
+
+    let kinds = classify(&device.caps);
+    if kinds.is_empty() {
+        tracing::debug!("Skipping unknown input device. (name: {:?})", device.name);
+        return None;
+    }
+
+    let switches = kinds
+        .contains(&InputKind::Switch)
+        .then(|| switches(&device.caps));
+
+    // hid devices use usb vendor ids, even when they're not on usb
+    let bus_type = device.bus_type.unwrap_or_default();
+    let vendor = match bus_type {
+        BUS_USB | BUS_BLUETOOTH | BUS_I2C => device.vendor_id.and_then(|id| {
+            use usb_ids::FromId as _;
+            usb_ids::Vendor::from_id(id).map(|v| v.name().to_string())
+        }),
+        _ => None,
+    };
+
+    Some(ComponentInfo {
+        bus: bus(bus_type),
+        id: device.name.clone(),
+        class: Some("Input Device".into()),
+        vendor_id: vendor,
+        status: None,
+        desc: ComponentDescription::InputDescription(InputDescription {
+            name: device.name,
+            kinds,
+            switches,
+            vendor_id: device.vendor_id,
+            product_id: device.product_id,
+            phys: device.phys,
+            handlers: (!device.handlers.is_empty()).then_some(device.handlers),
+            driver,
+        }),
+    })
+}
+
+// bus types, from `linux/input.h`
+const BUS_PCI: u16 = 0x01;
+const BUS_USB: u16 = 0x03;
+const BUS_BLUETOOTH: u16 = 0x05;
+const BUS_VIRTUAL: u16 = 0x06;
+const BUS_I8042: u16 = 0x11;
+const BUS_RS232: u16 = 0x13;
+const BUS_I2C: u16 = 0x18;
+const BUS_HOST: u16 = 0x19;
+const BUS_SPI: u16 = 0x1c;
+const BUS_RMI: u16 = 0x1d;
+
+/// converts an input bus type into a component bus.
+fn bus(bus_type: u16) -> ComponentBus {
+    match bus_type {
+        BUS_PCI => ComponentBus::Pci,
+        BUS_USB => ComponentBus::Usb,
+        BUS_I8042 => ComponentBus::Ps2,
+        BUS_RS232 => ComponentBus::Serial,
+        BUS_HOST => ComponentBus::Sys,
+        BUS_BLUETOOTH => ComponentBus::Other("Bluetooth".into()),
+        BUS_VIRTUAL => ComponentBus::Other("Virtual".into()),
+        BUS_I2C => ComponentBus::Other("I2C".into()),
+        BUS_SPI => ComponentBus::Other("SPI".into()),
+        BUS_RMI => ComponentBus::Other("RMI".into()),
+        _ => ComponentBus::Unknown,
+    }
+}
+
+/// one device from `/proc/bus/input/devices`.
+#[derive(Debug, Default, PartialEq)]
+struct Device {
+    bus_type: Option<u16>,
+    vendor_id: Option<u16>,
+    product_id: Option<u16>,
+    name: Option<String>,
+    phys: Option<String>,
+    sysfs: Option<String>,
+    handlers: Vec<String>,
+    caps: Capabilities,
+}
+
+/// the event types and codes a device can send.
+#[derive(Debug, Default, PartialEq)]
+struct Capabilities {
+    ev: Bitmap,
+    key: Bitmap,
+    rel: Bitmap,
+    abs: Bitmap,
+    sw: Bitmap,
+    prop: Bitmap,
+}
+
+/// a kernel bitmap, like `3803078f800d001 feffffdfffefffff`.
+///
+/// each word is a `long`, with the most significant word first.
+#[derive(Debug, Default, PartialEq)]
+struct Bitmap(Vec<u64>);
+
+impl Bitmap {
+    /// parses a bitmap from `sysfs` or `procfs`.
+    fn parse(s: &str) -> Self {
+        Self(
+            s.split_whitespace()
+                .rev()
+                .map(|word| u64::from_str_radix(word, 16).unwrap_or_default())
+                .collect(),
+        )
+    }
+
+    /// checks if `bit` is set.
+    fn has(&self, bit: usize) -> bool {
+        let width = std::ffi::c_long::BITS as usize;
+        self.0
+            .get(bit / width)
+            .is_some_and(|word| word & (1 << (bit % width)) != 0)
+    }
+}
+
+/// parses the contents of `/proc/bus/input/devices`.
+///
+/// each device is a block of lines, like:
+///
+/// ```text
+/// I: Bus=0011 Vendor=0001 Product=0001 Version=ab83
+/// N: Name="AT Translated Set 2 keyboard"
+/// P: Phys=isa0060/serio0/input0
+/// S: Sysfs=/devices/platform/i8042/serio0/input/input3
+/// H: Handlers=sysrq kbd leds event3
+/// B: EV=120013
+/// ```
+fn parse_devices(devices: &str) -> Vec<Device> {
+    let mut parsed = Vec::new();
+    let mut device = Device::default();
+
+    for line in devices.lines().chain(std::iter::once("")) {
+        // devices are split by blank lines
+        if line.trim().is_empty() {
+            if device != Device::default() {
+                parsed.push(std::mem::take(&mut device));
+            }
+            continue;
+        }
+
+        let Some((kind, rest)) = line.split_once(": ") else {
+            continue;
+        };
+
+        match kind {
+            "I" => {
+                for (key, value) in rest.split_whitespace().filter_map(|f| f.split_once('=')) {
+                    let value = u16::from_str_radix(value, 16).ok();
+                    match key {
+                        "Bus" => device.bus_type = value,
+                        "Vendor" => device.vendor_id = value,
+                        "Product" => device.product_id = value,
+                        _ => (),
+                    }
+                }
+            }
+            "N" => {
+                device.name = rest
+                    .strip_prefix("Name=")
+                    .map(|name| name.trim_matches('"').to_string())
+                    .filter(|name| !name.is_empty())
+            }
+            "P" => {
+                device.phys = rest
+                    .strip_prefix("Phys=")
+                    .map(str::to_string)
+                    .filter(|phys| !phys.is_empty())
+            }
+            "S" => device.sysfs = rest.strip_prefix("Sysfs=").map(str::to_string),
+            "H" => {
+                device.handlers = rest
+                    .strip_prefix("Handlers=")
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect()
+            }
+            "B" => {
+                let Some((name, bitmap)) = rest.split_once('=') else {
+                    continue;
+                };
+                let bitmap = Bitmap::parse(bitmap);
+                match name {
+                    "EV" => device.caps.ev = bitmap,
+                    "KEY" => device.caps.key = bitmap,
+                    "REL" => device.caps.rel = bitmap,
+                    "ABS" => device.caps.abs = bitmap,
+                    "SW" => device.caps.sw = bitmap,
+                    "PROP" => device.caps.prop = bitmap,
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    parsed
+}
+
+/// reads the capability bitmaps of the input device at `path`, like
+/// `/sys/class/input/input3`.
+///
+/// any bitmaps that can't be read are left alone.
+#[tracing::instrument(skip(caps))]
+async fn capabilities(path: &Path, caps: &mut Capabilities) {
+    let caps_path = path.join("capabilities");
+    let (ev, key, rel, abs, sw, prop) = futures::join! {
+        sysfs_value_opt::<String>(caps_path.join("ev")),
+        sysfs_value_opt::<String>(caps_path.join("key")),
+        sysfs_value_opt::<String>(caps_path.join("rel")),
+        sysfs_value_opt::<String>(caps_path.join("abs")),
+        sysfs_value_opt::<String>(caps_path.join("sw")),
+        sysfs_value_opt::<String>(path.join("properties")),
+    };
+
+    for (bitmap, value) in [
+        (&mut caps.ev, ev),
+        (&mut caps.key, key),
+        (&mut caps.rel, rel),
+        (&mut caps.abs, abs),
+        (&mut caps.sw, sw),
+        (&mut caps.prop, prop),
+    ] {
+        if let Some(value) = value {
+            *bitmap = Bitmap::parse(&value);
+        }
+    }
+}
+
+// event types and codes, from `linux/input-event-codes.h`
+const EV_KEY: usize = 0x01;
+const EV_REL: usize = 0x02;
+const EV_ABS: usize = 0x03;
+const EV_SW: usize = 0x05;
+
+const INPUT_PROP_DIRECT: usize = 0x01;
+
+const BTN_LEFT: usize = 0x110;
+const BTN_JOYSTICK: usize = 0x120;
+const BTN_GAMEPAD: usize = 0x130;
+const BTN_TOOL_PEN: usize = 0x140;
+const BTN_TOOL_FINGER: usize = 0x145;
+const BTN_TOUCH: usize = 0x14a;
+const BTN_STYLUS: usize = 0x14b;
+const BTN_TRIGGER_HAPPY: usize = 0x2c0;
+
+const REL_X: usize = 0x00;
+const REL_Y: usize = 0x01;
+
+const ABS_X: usize = 0x00;
+const ABS_Y: usize = 0x01;
+const ABS_MT_POSITION_X: usize = 0x35;
+const ABS_MT_POSITION_Y: usize = 0x36;
+
+const SW_LID: usize = 0x00;
+const SW_TABLET_MODE: usize = 0x01;
+
+/// figures out what a device is from its capabilities.
+///
+/// this follows the same rules as udev's `input_id` builtin.
+fn classify(caps: &Capabilities) -> Vec<InputKind> {
+    let mut kinds = Vec::new();
+    let (key, abs) = (caps.ev.has(EV_KEY), caps.ev.has(EV_ABS));
+
+    let has_key = |code| key && caps.key.has(code);
+    let has_abs_coords = abs
+        && ((caps.abs.has(ABS_X) && caps.abs.has(ABS_Y))
+            || (caps.abs.has(ABS_MT_POSITION_X) && caps.abs.has(ABS_MT_POSITION_Y)));
+    let has_rel_coords = caps.ev.has(EV_REL) && caps.rel.has(REL_X) && caps.rel.has(REL_Y);
+    let is_direct = caps.prop.has(INPUT_PROP_DIRECT);
+
+    let has_stylus = has_key(BTN_TOOL_PEN) || has_key(BTN_STYLUS);
+    let has_finger = has_key(BTN_TOOL_FINGER) && !has_key(BTN_TOOL_PEN);
+    let has_mouse_button = has_key(BTN_LEFT);
+    let has_joystick_buttons = (BTN_JOYSTICK..BTN_GAMEPAD + 16)
+        .chain(BTN_TRIGGER_HAPPY..BTN_TRIGGER_HAPPY + 40)
+        .any(has_key);
+
+    if has_abs_coords {
+        if has_stylus {
+            kinds.push(InputKind::Tablet);
+        } else if has_finger && !is_direct {
+            kinds.push(InputKind::Touchpad);
+        } else if has_mouse_button {
+            // like the absolute "tablet" mice in virtual machines
+            kinds.push(InputKind::Mouse);
+        } else if has_key(BTN_TOUCH) || is_direct {
+            kinds.push(InputKind::Touchscreen);
+        } else if has_joystick_buttons {
+            kinds.push(InputKind::Joystick);
+        }
+    } else if has_rel_coords && has_mouse_button {
+        kinds.push(InputKind::Mouse);
+    } else if has_joystick_buttons {
+        kinds.push(InputKind::Joystick);
+    }
+
+    // full keyboards have all the keys from escape to `s`
+    if key && (1..32).all(|code| caps.key.has(code)) {
+        kinds.push(InputKind::Keyboard);
+    }
+
+    if !switches(caps).is_empty() {
+        kinds.push(InputKind::Switch);
+    }
+
+    kinds
+}
+
+/// finds the switches a device reports.
+fn switches(caps: &Capabilities) -> Vec<InputSwitch> {
+    if !caps.ev.has(EV_SW) {
+        return Vec::new();
+    }
+
+    [
+        (SW_LID, InputSwitch::Lid),
+        (SW_TABLET_MODE, InputSwitch::TabletMode),
+    ]
+    .into_iter()
+    .filter(|(code, _)| caps.sw.has(*code))
+    .map(|(_, switch)| switch)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{collections::HashMap, path::PathBuf};
+
+    #[test]
+    fn check_bitmap() {
+        let bitmap = Bitmap::parse("402000000 3803078f800d001 feffffdfffefffff fffffffffffffffe");
+
+        assert!(!bitmap.has(0));
+        assert!(bitmap.has(1));
+        assert!(!bitmap.has(64 + 20));
+        assert!(bitmap.has(192 + 25));
+        assert!(!bitmap.has(1000));
+    }
+
+    #[tokio::test]
+    async fn check_input_devices() {
+        let all = all(devices_path(), sys_path()).await.unwrap();
+
+        let kinds = all
+            .iter()
+            .filter_map(|info| match info.desc() {
+                ComponentDescription::InputDescription(desc) => {
+                    Some((desc.name.clone()?, (info.bus(), desc)))
+                }
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+
+        // the power button isn't listed
+        assert_eq!(kinds.len(), 8);
+
+        let kind = |name: &str| kinds[name].1.kinds.clone();
+        assert_eq!(kind("AT Translated Set 2 keyboard"), [InputKind::Keyboard]);
+        assert_eq!(kind("TPPS/2 Elan TrackPoint"), [InputKind::Mouse]);
+        assert_eq!(kind("ELAN901C:00 04F3:2C68"), [InputKind::Touchscreen]);
+        assert_eq!(kind("ELAN901C:00 04F3:2C68 Stylus"), [InputKind::Tablet]);
+        assert_eq!(
+            kind("Microsoft Xbox Series S|X Controller"),
+            [InputKind::Joystick]
+        );
+
+        // the keyboard and trackpoint are on ps/2
+        assert_eq!(kinds["AT Translated Set 2 keyboard"].0, ComponentBus::Ps2);
+        assert_eq!(kinds["TPPS/2 Elan TrackPoint"].0, ComponentBus::Ps2);
+
+        let lid = &kinds["Lid Switch"].1;
+        assert_eq!(lid.kinds, [InputKind::Switch]);
+        assert_eq!(lid.switches.clone().unwrap(), [InputSwitch::Lid]);
+        assert_eq!(
+            kinds["Intel HID switches"].1.switches.clone().unwrap(),
+            [InputSwitch::TabletMode]
+        );
+    }
+
+    #[tokio::test]
+    async fn check_i2c_touchpad() {
+        let all = all(devices_path(), sys_path()).await.unwrap();
+        let info = all
+            .iter()
+            .find(|info| info.id().as_deref() == Some("SYNA8018:00 06CB:CE26 Touchpad"))
+            .unwrap();
+
+        assert_eq!(info.bus(), ComponentBus::Other("I2C".into()));
+        assert_eq!(info.vendor_id().unwrap(), "Synaptics, Inc.");
+
+        let ComponentDescription::InputDescription(desc) = info.desc() else {
+            panic!("wrong desc");
+        };
+
+        assert_eq!(desc.kinds, [InputKind::Touchpad]);
+        assert_eq!(desc.vendor_id, Some(0x06cb));
+        assert_eq!(desc.product_id, Some(0xce26));
+        assert_eq!(desc.driver.unwrap(), "hid-multitouch");
+        assert_eq!(desc.handlers.unwrap(), ["mouse0", "event9"]);
+    }
+
+    #[tracing::instrument]
+    fn devices_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!(
+            "{root}/tests/assets/linux/sysfs/proc/bus/input/devices"
+        ))
+    }
+
+    #[tracing::instrument]
+    fn sys_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs/sys"))
+    }
+}
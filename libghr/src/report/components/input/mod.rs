@@ -0,0 +1,80 @@
+//! input: keyboards, mice, touchpads, and other input devices
+
+use crate::prelude::internal::*;
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+/// A description of an input device, like a keyboard or touchpad.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct InputDescription {
+    /// The device's name, like `SYNA8018:00 06CB:CE26 Touchpad`.
+    pub name: Option<String>,
+
+    /// What the device is, based on the events it can send.
+    ///
+    /// Some devices are more than one thing, like keyboards with a built-in
+    /// touchpad.
+    pub kinds: Vec<InputKind>,
+
+    /// The switches this device reports, if it's a switch.
+    pub switches: Option<Vec<InputSwitch>>,
+
+    /// The device's vendor ID on its bus.
+    ///
+    /// For USB, Bluetooth, and I2C-HID devices, this is a USB vendor ID.
+    pub vendor_id: Option<u16>,
+
+    /// The device's product ID on its bus.
+    pub product_id: Option<u16>,
+
+    /// Where the device is physically connected, like `isa0060/serio0/input0`.
+    pub phys: Option<String>,
+
+    /// The kernel handlers attached to the device, like `kbd` or `event3`.
+    pub handlers: Option<Vec<String>>,
+
+    /// The kernel driver behind the device, like `hid-multitouch` or
+    /// `psmouse`.
+    pub driver: Option<String>,
+}
+
+/// A kind of input device.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum InputKind {
+    /// A full keyboard.
+    Keyboard,
+    /// A mouse, trackball, or pointing stick.
+    Mouse,
+    Touchpad,
+    Touchscreen,
+    /// A drawing tablet, or a stylus on a touchscreen.
+    Tablet,
+    /// A joystick or gamepad.
+    Joystick,
+    /// A switch, like a laptop's lid.
+    Switch,
+}
+
+/// A switch reported by an input device.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum InputSwitch {
+    /// Whether a laptop's lid is closed.
+    Lid,
+    /// Whether a convertible is folded into a tablet.
+    TabletMode,
+}
+
+/// Finds and returns info about the system's input devices.
+#[tracing::instrument]
+pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
+    #[cfg(target_os = "linux")]
+    return linux::get().await;
+
+    // TODO: use `Win32_Keyboard` and `Win32_PointingDevice` on windows
+    #[cfg(target_os = "windows")]
+    return Ok(Vec::new());
+}
@@ -2,18 +2,28 @@ use futures::{FutureExt as _, StreamExt};
 
 use crate::prelude::internal::*;
 
+pub mod audio;
+pub mod bluetooth;
+pub mod camera;
 pub mod cpu;
 pub mod gpu;
+pub mod input;
 pub mod nic;
 pub mod pci;
 pub mod psu;
 pub mod ram;
+pub mod sensors;
 pub mod storage;
+pub mod thunderbolt;
+pub mod typec;
 pub mod usb;
 
 #[tracing::instrument]
 /// Grabs any known components (devices) on the system.
 pub async fn get_components() -> GhrResult<Vec<ComponentInfo>> {
+    // sources every device looks itself up in are only read once
+    let context = Context::load().await;
+
     // add components to the set. this prevents a stack overflow on a shared
     // await point!
     //
@@ -22,12 +32,18 @@ pub async fn get_components() -> GhrResult<Vec<ComponentInfo>> {
     let mut futures = futures::stream::iter([
         cpu::get().boxed_local(),
         usb::get().boxed_local(),
-        pci::get().boxed_local(),
+        pci::get_with(&context).boxed_local(),
         ram::get().boxed_local(),
-        gpu::get().boxed_local(),
-        psu::get().boxed_local(),
-        storage::get().boxed_local(),
-        nic::get().boxed_local(),
+        gpu::get_with(&context).boxed_local(),
+        psu::get_with(&context).boxed_local(),
+        storage::get_with(&context).boxed_local(),
+        nic::get_with(&context).boxed_local(),
+        typec::get().boxed_local(),
+        thunderbolt::get().boxed_local(),
+        audio::get().boxed_local(),
+        input::get().boxed_local(),
+        bluetooth::get_with(&context).boxed_local(),
+        camera::get_with(&context).boxed_local(),
     ])
     .buffer_unordered(3);
 
@@ -51,9 +67,15 @@ pub async fn get_components() -> GhrResult<Vec<ComponentInfo>> {
     // do...
 
     // iterate over each future in the stream.
+    //
+    // a collector failing (like when a machine has no type-c ports) shouldn't
+    // hide the results of the others, so we just log the error and move on
     let mut components = Vec::new();
-    while let Some(comp) = futures.next().await.and_then(|fut| fut.ok()) {
-        components.push(comp);
+    while let Some(res) = futures.next().await {
+        match res {
+            Ok(comp) => components.push(comp),
+            Err(e) => tracing::warn!("A component collector failed. (err: {e})"),
+        }
     }
 
     Ok(components
@@ -63,6 +85,34 @@ pub async fn get_components() -> GhrResult<Vec<ComponentInfo>> {
         .collect())
 }
 
+/// what the collectors share while creating one report.
+///
+/// some sources (like the kernel log) describe every device at once, so
+/// they're read once here instead of once per device.
+#[derive(Debug, Default)]
+pub(crate) struct Context {
+    /// the firmware files the kernel couldn't load, by device name (like
+    /// `0000:00:14.3` or `hci0`)
+    #[cfg(target_os = "linux")]
+    pub(crate) missing_firmware: std::collections::BTreeMap<String, Vec<String>>,
+
+    /// the sensors watching each device, by the device's real path
+    #[cfg(target_os = "linux")]
+    pub(crate) sensors: std::collections::BTreeMap<std::path::PathBuf, Vec<Sensor>>,
+}
+
+impl Context {
+    /// reads the shared sources from the running system.
+    #[tracing::instrument]
+    pub(crate) async fn load() -> Self {
+        #[cfg(target_os = "linux")]
+        return linux::context("/").await;
+
+        #[cfg(target_os = "windows")]
+        return Self::default();
+    }
+}
+
 /// A representation of any single component in the machine.
 #[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
 #[non_exhaustive]
@@ -114,7 +164,7 @@ impl ComponentInfo {
     /// Gets the class for this component.
     #[tracing::instrument(skip(self))]
     pub fn class(&self) -> Option<String> {
-        self.id.clone()
+        self.class.clone()
     }
 
     /// Gets the vendor (manufacturer) name for this component.
@@ -136,6 +186,23 @@ impl ComponentInfo {
     pub fn desc(&self) -> ComponentDescription {
         self.desc.clone()
     }
+
+    /// applies the redaction policy to the component's identifying values.
+    #[tracing::instrument(skip(self))]
+    pub(crate) fn redact(&mut self, redaction: Redaction) {
+        let sensitive = match &mut self.desc {
+            ComponentDescription::NicDescription(nic) => nic.mac_address.as_mut(),
+            ComponentDescription::PowerSupplyDescription(PowerSupplyDescription::Battery {
+                serial,
+                ..
+            }) => serial.as_mut(),
+            _ => None,
+        };
+
+        if let Some(sensitive) = sensitive {
+            sensitive.redact(redaction);
+        }
+    }
 }
 
 /// The bus a component is on.
@@ -157,14 +224,219 @@ pub enum ComponentBus {
     Scsi,
     Ide,
 
+    // also carries pcie, usb, and displayport
+    Thunderbolt,
+
     Other(String),
     Unknown,
 }
 
 /// Information about the health of the component.
+#[derive(
+    Clone, Debug, Default, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript,
+)]
+#[non_exhaustive]
+pub struct ComponentStatus {
+    /// An overall assessment of the component's health.
+    pub health: Health,
+
+    /// Why the component isn't healthy, like `No driver is bound.`
+    pub reasons: Option<Vec<String>>,
+
+    /// Whether a kernel driver is bound to the component.
+    ///
+    /// Devices whose firmware failed to load usually don't have one.
+    pub driver_bound: Option<bool>,
+
+    /// The kernel module that drives the component, like `amdgpu`.
+    ///
+    /// Drivers built into the kernel don't have one.
+    pub module: Option<String>,
+
+    /// The version of the firmware the component is running, when its driver
+    /// reports one.
+    pub firmware_version: Option<String>,
+
+    /// Whether the component's firmware was loaded.
+    ///
+    /// Failures come from the kernel log, which usually needs administrator
+    /// rights to read.
+    pub firmware: Option<FirmwareState>,
+
+    /// The component's runtime power management status.
+    pub runtime_status: Option<RuntimeStatus>,
+
+    /// Whether the component's link (like PCIe) has fewer lanes than it's
+    /// able to use.
+    ///
+    /// Slower links aren't counted, since many devices slow down on purpose
+    /// to save power.
+    pub link_degraded: Option<bool>,
+
+    /// How many errors the component has reported.
+    pub errors: Option<u64>,
+
+    /// Readings from the component's sensors, like its temperature.
+    pub sensors: Option<Vec<Sensor>>,
+}
+
+impl ComponentStatus {
+    /// marks the component as having a problem.
+    ///
+    /// the health only ever gets worse, so a critical problem won't be
+    /// hidden by a later warning.
+    pub(crate) fn flag(&mut self, health: Health, reason: impl Into<String>) {
+        if health > self.health {
+            self.health = health;
+        }
+        self.reasons
+            .get_or_insert_with(Vec::new)
+            .push(reason.into());
+    }
+
+    /// checks the status for problems and sets its health.
+    ///
+    /// returns `None` if nothing is known about the component.
+    pub(crate) fn assess(mut self) -> Option<Self> {
+        // a driver that shares its firmware version has loaded some, even if
+        // it tried (and failed) to load newer versions first
+        if self.firmware_version.is_some() {
+            self.firmware = Some(FirmwareState::Loaded);
+        }
+
+        if let Some(FirmwareState::Missing(files)) = &self.firmware {
+            let reason = format!("Its firmware couldn't be loaded: {}", files.join(", "));
+            self.flag(Health::Warning, reason);
+        }
+
+        if self.runtime_status == Some(RuntimeStatus::Error) {
+            self.flag(Health::Critical, "Runtime power management failed.");
+        }
+
+        if self.link_degraded == Some(true) {
+            self.flag(
+                Health::Warning,
+                "The link has fewer lanes than it supports.",
+            );
+        }
+
+        for sensor in self.sensors.clone().unwrap_or_default() {
+            self.assess_sensor(&sensor);
+        }
+
+        // no problems, but only if we know anything at all
+        if self.health == Health::Unknown {
+            if self == Self::default() {
+                return None;
+            }
+            self.health = Health::Ok;
+        }
+
+        Some(self)
+    }
+
+    /// flags a sensor reading that's out of range.
+    fn assess_sensor(&mut self, sensor: &Sensor) {
+        let Some(value) = sensor.value else {
+            return;
+        };
+
+        let name = sensor
+            .label
+            .as_deref()
+            .or(sensor.chip.as_deref())
+            .unwrap_or("A sensor");
+        let unit = match sensor.kind {
+            SensorKind::Temperature => " °C",
+            SensorKind::Fan => " RPM",
+            SensorKind::Voltage => " V",
+            SensorKind::Current => " A",
+            SensorKind::Power => " W",
+        };
+
+        // fans and power are often at their max on purpose
+        let limited = matches!(
+            sensor.kind,
+            SensorKind::Temperature | SensorKind::Voltage | SensorKind::Current
+        );
+
+        if let Some(crit) = sensor.crit.filter(|crit| value >= *crit) {
+            self.flag(
+                Health::Critical,
+                format!("{name} is at {value}{unit}, past its critical limit of {crit}{unit}."),
+            );
+        } else if let Some(max) = sensor.max.filter(|max| limited && value >= *max) {
+            self.flag(
+                Health::Warning,
+                format!("{name} is at {value}{unit}, past its limit of {max}{unit}."),
+            );
+        } else if let Some(min) = sensor
+            .min
+            .filter(|min| sensor.kind == SensorKind::Voltage && value < *min)
+        {
+            self.flag(
+                Health::Warning,
+                format!("{name} is at {value}{unit}, below its limit of {min}{unit}."),
+            );
+        } else if sensor.alarm == Some(true) {
+            self.flag(Health::Warning, format!("{name} is out of range."));
+        }
+    }
+}
+
+/// An overall assessment of a component's health.
+///
+/// Healths are ordered from least to most severe, so `Unknown` comes first and
+/// `Critical` comes last.
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, TypeScript,
+)]
+pub enum Health {
+    /// Nothing seems wrong.
+    Ok,
+    /// Something's wrong, but the component still works.
+    Warning,
+    /// The component is failing, or is about to.
+    Critical,
+    /// We don't know enough about the component to say.
+    #[default]
+    Unknown,
+}
+
+impl Health {
+    /// how bad this health is, from best to worst.
+    fn severity(&self) -> u8 {
+        match self {
+            Health::Unknown => 0,
+            Health::Ok => 1,
+            Health::Warning => 2,
+            Health::Critical => 3,
+        }
+    }
+}
+
+impl PartialOrd for Health {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Health {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.severity().cmp(&other.severity())
+    }
+}
+
+/// Whether a component's firmware was loaded.
 #[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
 #[non_exhaustive]
-pub struct ComponentStatus {}
+pub enum FirmwareState {
+    /// The firmware was loaded.
+    Loaded,
+    /// The driver asked for firmware files that couldn't be loaded, like
+    /// `iwlwifi-so-a0-gf-a0-89.ucode`.
+    Missing(Vec<String>),
+}
 
 /// A general 'description' about the component
 #[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
@@ -193,6 +465,30 @@ pub enum ComponentDescription {
     /// About a network interface device.
     NicDescription(NicDescription),
 
+    /// About a PCI function.
+    PciDescription(PciDescription),
+
+    /// About a USB device.
+    UsbDescription(UsbDescription),
+
+    /// About a USB Type-C port and whatever's plugged into it.
+    TypeCDescription(TypeCDescription),
+
+    /// About a Thunderbolt (or USB4) domain, router, or device.
+    ThunderboltDescription(ThunderboltDescription),
+
+    /// About a sound card.
+    AudioDescription(AudioDescription),
+
+    /// About an input device, like a keyboard or touchpad.
+    InputDescription(InputDescription),
+
+    /// About a Bluetooth controller.
+    BluetoothDescription(BluetoothDescription),
+
+    /// About a camera or other video device.
+    CameraDescription(CameraDescription),
+
     /// No description is available for this device.
     None,
 }
@@ -207,18 +503,34 @@ pub enum Removability {
     NonRemovable,
 }
 
+/// Whether a radio (like Wi-Fi or Bluetooth) is turned off by a "kill switch".
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+pub struct Rfkill {
+    /// Blocked by software, like airplane mode.
+    pub soft_blocked: bool,
+
+    /// Blocked by hardware, like a physical switch or BIOS setting.
+    pub hard_blocked: bool,
+}
+
 // all this helps with accessing devices on linux
 #[cfg(target_os = "linux")]
-mod linux {
+pub(crate) mod linux {
     use async_fs::DirEntry;
     use futures::StreamExt as _;
 
     use crate::prelude::internal::*;
 
-    use std::path::Path;
+    use super::Context;
+
+    use std::{
+        collections::BTreeMap,
+        ffi::OsStr,
+        path::{Path, PathBuf},
+    };
 
     #[tracing::instrument(skip(path))]
-    pub(super) async fn devices(path: impl AsRef<Path>) -> GhrResult<Vec<DirEntry>> {
+    pub(crate) async fn devices(path: impl AsRef<Path>) -> GhrResult<Vec<DirEntry>> {
         let all_devices = async_fs::read_dir(path)
             .await
             .map_err(|e| GhrError::ComponentInfoInaccessible(e.to_string()))?;
@@ -228,6 +540,159 @@ mod linux {
             .collect::<Vec<_>>()
             .await)
     }
+
+    /// reads the sources every device is looked up in, under `root`.
+    #[tracing::instrument]
+    pub(crate) async fn context(root: impl AsRef<Path> + std::fmt::Debug) -> Context {
+        let root = root.as_ref();
+        let (log, sensors) = futures::join! {
+            kernel_log(root.join("dev/kmsg")),
+            super::sensors::linux::by_owner(
+                root.join("sys/class/hwmon"),
+                root.join("sys/class/thermal"),
+            ),
+        };
+        let log = log.unwrap_or_default();
+
+        let mut missing_firmware = BTreeMap::<String, Vec<String>>::new();
+        for (name, file) in firmware_failures(&log) {
+            let files = missing_firmware.entry(name.to_string()).or_default();
+            if !files.iter().any(|f| f == file) {
+                files.push(file.to_string());
+            }
+        }
+
+        Context {
+            missing_firmware,
+            sensors,
+        }
+    }
+
+    /// finds the status of the `sysfs` device at `device`, like
+    /// `/sys/class/net/eth0/device`.
+    ///
+    /// collectors can add what they know, then call
+    /// [`ComponentStatus::assess`] to get its health.
+    #[tracing::instrument(skip(context))]
+    pub(crate) async fn status(device: &Path, context: &Context) -> ComponentStatus {
+        // class devices (like `nvme0`) keep their driver on their parent
+        let parent = device.join("device");
+        let (driver, parent_driver, module, parent_module) = futures::join! {
+            driver_name(device),
+            driver_name(&parent),
+            driver_module(device),
+            driver_module(&parent),
+        };
+        let (runtime_status, canonical) = futures::join! {
+            runtime_status(device),
+            async_fs::canonicalize(device),
+        };
+
+        // a device that doesn't exist can't have a driver
+        let exists = async_fs::metadata(device).await.is_ok();
+
+        // the kernel names devices by their bus name (like `0000:00:14.3`),
+        // or by their class device's (like `hci0`)
+        let canonical = canonical.ok();
+        let mut names = Vec::new();
+        names.extend(canonical.as_deref().and_then(Path::file_name));
+        if device.file_name() == Some(OsStr::new("device")) {
+            names.extend(device.parent().and_then(Path::file_name));
+        }
+        let missing_firmware = names
+            .iter()
+            .find_map(|name| context.missing_firmware.get(&*name.to_string_lossy()));
+
+        ComponentStatus {
+            driver_bound: exists.then_some(driver.or(parent_driver).is_some()),
+            module: module.or(parent_module),
+            runtime_status,
+            sensors: canonical
+                .as_ref()
+                .and_then(|device| context.sensors.get(device))
+                .cloned(),
+            firmware: missing_firmware.cloned().map(FirmwareState::Missing),
+            ..Default::default()
+        }
+    }
+
+    /// reads the lines of the kernel log at `path` that mention firmware.
+    ///
+    /// `/dev/kmsg` gives one record per read, so this is only done once per
+    /// report.
+    #[tracing::instrument]
+    async fn kernel_log(path: PathBuf) -> Option<String> {
+        use std::{io::Read as _, os::unix::fs::OpenOptionsExt as _};
+
+        blocking::unblock(move || {
+            // `/dev/kmsg` waits for new messages at its end, unless we ask it
+            // not to
+            let mut kmsg = std::fs::OpenOptions::new()
+                .read(true)
+                .custom_flags(libc::O_NONBLOCK)
+                .open(&path)
+                .inspect_err(|e| tracing::debug!("Can't read the kernel log. (err: {e})"))
+                .ok()?;
+
+            // each read gives one record
+            let mut log = String::new();
+            let mut buf = vec![0; 8192];
+            loop {
+                match kmsg.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(len) => {
+                        let record = String::from_utf8_lossy(&buf[..len]);
+                        record
+                            .lines()
+                            .filter(|line| line.contains("firmware"))
+                            .for_each(|line| {
+                                log.push_str(line);
+                                log.push('\n');
+                            });
+                    }
+                    // old records can be overwritten while we read
+                    Err(e) if e.raw_os_error() == Some(libc::EPIPE) => continue,
+                    // and `WouldBlock` means we're at the end
+                    Err(_) => break,
+                }
+            }
+
+            Some(log)
+        })
+        .await
+    }
+
+    /// finds failed firmware loads in the kernel log, as `(device, file)`.
+    ///
+    /// records look like `4,1102,3345700,-;iwlwifi 0000:00:14.3: Direct
+    /// firmware load for iwlwifi-so-a0-gf-a0-89.ucode failed with error -2`.
+    pub(super) fn firmware_failures(log: &str) -> impl Iterator<Item = (&str, &str)> {
+        log.lines().filter_map(|line| {
+            let (_, message) = line.split_once(';')?;
+            let (prefix, message) = message.split_once(": ")?;
+
+            let file = match message.strip_prefix("Direct firmware load for ") {
+                Some(rest) => rest.split_once(" failed")?.0,
+                None => message
+                    .strip_prefix("firmware: failed to load ")?
+                    .split_whitespace()
+                    .next()?,
+            };
+
+            // the prefix is the driver (or class) and the device's name
+            Some((prefix.rsplit(' ').next()?, file))
+        })
+    }
+
+    /// finds the status of the processor in socket `index`.
+    #[tracing::instrument]
+    pub(crate) async fn cpu_status(index: usize, sockets: usize) -> Option<ComponentStatus> {
+        ComponentStatus {
+            sensors: super::sensors::linux::cpu_sensors(index, sockets).await,
+            ..Default::default()
+        }
+        .assess()
+    }
 }
 
 #[cfg(target_os = "windows")]
@@ -445,3 +910,62 @@ pub(crate) mod windows {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_health_order() {
+        assert!(Health::Unknown < Health::Ok);
+        assert!(Health::Ok < Health::Warning);
+        assert!(Health::Warning < Health::Critical);
+
+        // a critical problem isn't hidden by a later warning
+        let mut status = ComponentStatus::default();
+        status.flag(Health::Critical, "It's on fire.");
+        status.flag(Health::Warning, "It's also loud.");
+        assert_eq!(status.health, Health::Critical);
+        assert_eq!(status.reasons.unwrap().len(), 2);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn check_firmware_failures() {
+        let log = concat!(
+            "4,1102,3345700,-;iwlwifi 0000:00:14.3: Direct firmware load for ",
+            "iwlwifi-so-a0-gf-a0-89.ucode failed with error -2\n",
+            "4,1200,4345700,-;rtw89_8852be 0000:03:00.0: firmware: failed to load ",
+            "rtw89/rtw8852b_fw-1.bin (-2)\n",
+            "6,1201,4345800,-;iwlwifi 0000:00:14.3: loaded firmware version 86.fb5c9aeb.0\n",
+        );
+
+        assert_eq!(
+            linux::firmware_failures(log).collect::<Vec<_>>(),
+            [
+                ("0000:00:14.3", "iwlwifi-so-a0-gf-a0-89.ucode"),
+                ("0000:03:00.0", "rtw89/rtw8852b_fw-1.bin"),
+            ]
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn check_missing_firmware() {
+        let root = env!("CARGO_MANIFEST_DIR");
+        let bluetooth = std::path::PathBuf::from(format!(
+            "{root}/tests/assets/linux/sysfs/sys/class/bluetooth/hci0/device"
+        ));
+
+        // the kernel log says the controller's firmware is missing
+        let context = linux::context(format!("{root}/tests/assets/linux/sysfs")).await;
+        let status = linux::status(&bluetooth, &context).await.assess().unwrap();
+        assert_eq!(
+            status.firmware,
+            Some(FirmwareState::Missing(vec![
+                "intel/ibt-0040-0041.sfi".into()
+            ]))
+        );
+        assert_eq!(status.health, Health::Warning);
+    }
+}
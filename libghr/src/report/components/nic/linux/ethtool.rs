@@ -0,0 +1,327 @@
+//! a tiny wrapper over the `ethtool` ioctls.
+//!
+//! `sysfs` doesn't expose a card's firmware version or supported link modes,
+//! so we have to ask the driver directly. neither of these need root.
+
+use std::os::fd::{AsRawFd as _, FromRawFd as _, OwnedFd};
+
+/// `ETHTOOL_GDRVINFO`: get driver info
+const ETHTOOL_GDRVINFO: u32 = 0x0000_0003;
+
+/// `ETHTOOL_GLINKSETTINGS`: get link settings
+const ETHTOOL_GLINKSETTINGS: u32 = 0x0000_004c;
+
+/// the most 32-bit words the kernel can use for each link mode mask
+const MAX_LINK_MODE_NWORDS: usize = 127;
+
+/// `struct ethtool_drvinfo` from `linux/ethtool.h`
+#[repr(C)]
+#[allow(dead_code)] // the kernel fills these out
+struct DriverInfo {
+    cmd: u32,
+    driver: [u8; 32],
+    version: [u8; 32],
+    fw_version: [u8; 32],
+    bus_info: [u8; 32],
+    erom_version: [u8; 32],
+    reserved2: [u8; 12],
+    n_priv_flags: u32,
+    n_stats: u32,
+    testinfo_len: u32,
+    eedump_len: u32,
+    regdump_len: u32,
+}
+
+/// `struct ethtool_link_settings` from `linux/ethtool.h`, followed by space
+/// for its (flexible) link mode masks
+#[repr(C)]
+#[allow(dead_code)] // the kernel fills these out
+struct LinkSettings {
+    cmd: u32,
+    speed: u32,
+    duplex: u8,
+    port: u8,
+    phy_address: u8,
+    autoneg: u8,
+    mdio_support: u8,
+    eth_tp_mdix: u8,
+    eth_tp_mdix_ctrl: u8,
+    link_mode_masks_nwords: i8,
+    transceiver: u8,
+    master_slave_cfg: u8,
+    master_slave_state: u8,
+    rate_matching: u8,
+    reserved: [u32; 7],
+
+    /// `supported`, `advertising`, then `lp_advertising` masks, each
+    /// `link_mode_masks_nwords` long
+    link_mode_masks: [u32; 3 * MAX_LINK_MODE_NWORDS],
+}
+
+/// the names of link modes that describe a speed, by their bit index in
+/// `enum ethtool_link_mode_bit_indices`.
+///
+/// other bits (like `Autoneg` or `Pause`) describe features, so we skip them.
+const LINK_MODES: &[(usize, &str)] = &[
+    (0, "10baseT/Half"),
+    (1, "10baseT/Full"),
+    (2, "100baseT/Half"),
+    (3, "100baseT/Full"),
+    (4, "1000baseT/Half"),
+    (5, "1000baseT/Full"),
+    (12, "10000baseT/Full"),
+    (15, "2500baseX/Full"),
+    (17, "1000baseKX/Full"),
+    (18, "10000baseKX4/Full"),
+    (19, "10000baseKR/Full"),
+    (21, "20000baseMLD2/Full"),
+    (22, "20000baseKR2/Full"),
+    (23, "40000baseKR4/Full"),
+    (24, "40000baseCR4/Full"),
+    (25, "40000baseSR4/Full"),
+    (26, "40000baseLR4/Full"),
+    (27, "56000baseKR4/Full"),
+    (28, "56000baseCR4/Full"),
+    (29, "56000baseSR4/Full"),
+    (30, "56000baseLR4/Full"),
+    (31, "25000baseCR/Full"),
+    (32, "25000baseKR/Full"),
+    (33, "25000baseSR/Full"),
+    (34, "50000baseCR2/Full"),
+    (35, "50000baseKR2/Full"),
+    (36, "100000baseKR4/Full"),
+    (37, "100000baseSR4/Full"),
+    (38, "100000baseCR4/Full"),
+    (39, "100000baseLR4_ER4/Full"),
+    (40, "50000baseSR2/Full"),
+    (41, "1000baseX/Full"),
+    (42, "10000baseCR/Full"),
+    (43, "10000baseSR/Full"),
+    (44, "10000baseLR/Full"),
+    (45, "10000baseLRM/Full"),
+    (46, "10000baseER/Full"),
+    (47, "2500baseT/Full"),
+    (48, "5000baseT/Full"),
+];
+
+/// info about a network card's driver, as reported by `ethtool`.
+#[derive(Clone, Debug)]
+pub(crate) struct EthtoolDriverInfo {
+    pub(crate) driver: Option<String>,
+    pub(crate) firmware_version: Option<String>,
+}
+
+/// asks network drivers about their interfaces.
+///
+/// collectors take one of these so tests can answer with fixture data
+/// instead of the host's real interfaces. its ioctls block, so they're sent
+/// from another thread.
+pub(crate) trait Ethtool: std::fmt::Debug + Clone + Send + 'static {
+    /// gets the driver name and firmware version of interface `name`.
+    fn driver_info(&self, name: &str) -> Option<EthtoolDriverInfo>;
+
+    /// gets the link modes interface `name` supports.
+    fn supported_link_modes(&self, name: &str) -> Option<Vec<String>>;
+}
+
+/// asks the kernel with the `ethtool` ioctls.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Ioctl;
+
+impl Ethtool for Ioctl {
+    fn driver_info(&self, name: &str) -> Option<EthtoolDriverInfo> {
+        driver_info(name)
+    }
+
+    fn supported_link_modes(&self, name: &str) -> Option<Vec<String>> {
+        supported_link_modes(name)
+    }
+}
+
+/// answers for the interfaces in the test fixtures.
+#[cfg(test)]
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Fixture;
+
+#[cfg(test)]
+impl Ethtool for Fixture {
+    fn driver_info(&self, name: &str) -> Option<EthtoolDriverInfo> {
+        let (driver, firmware_version) = match name {
+            "enp7s0" => ("igb", "3.30, 0x800005cc"),
+            "wlo1" => ("iwlwifi", "89.202a2f7b.0 ty-a0-gf-a0-89.uc"),
+            "enx00e04c680001" => ("r8152", "rtl8153b-2 v2 04/27/23"),
+            "docker0" => ("bridge", "N/A"),
+            "veth3a1b2c4" => ("veth", ""),
+            _ => return None,
+        };
+
+        Some(EthtoolDriverInfo {
+            driver: Some(driver.into()),
+            firmware_version: Some(firmware_version.into()),
+        })
+    }
+
+    fn supported_link_modes(&self, name: &str) -> Option<Vec<String>> {
+        let modes: &[&str] = match name {
+            "enp7s0" => &["10baseT/Full", "100baseT/Full", "1000baseT/Full"],
+            "enx00e04c680001" => &["100baseT/Full", "1000baseT/Full"],
+            _ => return None,
+        };
+
+        Some(modes.iter().map(|mode| mode.to_string()).collect())
+    }
+}
+
+/// asks the driver of interface `name` for its driver name and firmware
+/// version.
+#[tracing::instrument]
+fn driver_info(name: &str) -> Option<EthtoolDriverInfo> {
+    // SAFETY: `DriverInfo` is plain old data, so all zeroes is valid
+    let mut info: DriverInfo = unsafe { core::mem::zeroed() };
+    info.cmd = ETHTOOL_GDRVINFO;
+
+    // SAFETY: `info` is a `struct ethtool_drvinfo` with its command set
+    unsafe { ethtool(name, core::ptr::addr_of_mut!(info).cast()) }?;
+
+    Some(EthtoolDriverInfo {
+        driver: c_string(&info.driver),
+        firmware_version: c_string(&info.fw_version),
+    })
+}
+
+/// asks the driver of interface `name` which link modes it supports.
+#[tracing::instrument]
+fn supported_link_modes(name: &str) -> Option<Vec<String>> {
+    // SAFETY: `LinkSettings` is plain old data, so all zeroes is valid
+    let mut settings: LinkSettings = unsafe { core::mem::zeroed() };
+    settings.cmd = ETHTOOL_GLINKSETTINGS;
+
+    // first, we do a "handshake" with `nwords` set to zero. the kernel
+    // responds with the negative number of words it wants us to use
+    //
+    // SAFETY: `settings` is a `struct ethtool_link_settings` with its command
+    //         set, and has room for the largest possible masks
+    unsafe { ethtool(name, core::ptr::addr_of_mut!(settings).cast()) }?;
+
+    let nwords = settings.link_mode_masks_nwords;
+    if nwords >= 0 {
+        tracing::debug!("Link settings handshake failed for `{name}`. (nwords: {nwords})");
+        return None;
+    }
+
+    // now, ask again with the right size
+    settings.cmd = ETHTOOL_GLINKSETTINGS;
+    settings.link_mode_masks_nwords = -nwords;
+
+    // SAFETY: same as above
+    unsafe { ethtool(name, core::ptr::addr_of_mut!(settings).cast()) }?;
+
+    // the `supported` mask comes first
+    let nwords = usize::from(nwords.unsigned_abs());
+    let supported = settings.link_mode_masks.get(..nwords)?;
+
+    Some(link_mode_names(supported))
+}
+
+/// converts a link mode bitmask into a list of link mode names.
+#[tracing::instrument]
+pub(crate) fn link_mode_names(mask: &[u32]) -> Vec<String> {
+    LINK_MODES
+        .iter()
+        .filter(|(bit, _)| {
+            mask.get(bit / 32)
+                .is_some_and(|word| word & (1 << (bit % 32)) != 0)
+        })
+        .map(|(_, name)| name.to_string())
+        .collect()
+}
+
+/// runs the `SIOCETHTOOL` ioctl on the interface `name` with the given
+/// command struct.
+///
+/// # Safety
+///
+/// `data` must point to a valid `ethtool` command struct that matches the
+/// `cmd` it contains.
+#[tracing::instrument(skip(data))]
+unsafe fn ethtool(name: &str, data: *mut libc::c_char) -> Option<()> {
+    // the name must fit in `ifr_name` with a nul terminator
+    if name.len() >= libc::IFNAMSIZ || name.contains('\0') {
+        tracing::warn!("Interface name `{name}` can't be used with `ethtool`.");
+        return None;
+    }
+
+    // SAFETY: `ifreq` is plain old data, so all zeroes is valid
+    let mut ifreq: libc::ifreq = unsafe { core::mem::zeroed() };
+    for (dst, src) in ifreq.ifr_name.iter_mut().zip(name.bytes()) {
+        *dst = src as libc::c_char;
+    }
+    ifreq.ifr_ifru.ifru_data = data;
+
+    // any socket will do
+    //
+    // SAFETY: `socket` has no preconditions
+    let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0) };
+    if fd < 0 {
+        tracing::warn!("Failed to open a socket for `ethtool`.");
+        return None;
+    }
+
+    // SAFETY: we just created this fd, and nothing else owns it
+    let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    // SAFETY: `ifreq` is valid, and the caller ensures `data` is too
+    let result = unsafe { libc::ioctl(socket.as_raw_fd(), libc::SIOCETHTOOL, &mut ifreq) };
+    if result < 0 {
+        tracing::debug!(
+            "`ethtool` command failed for `{name}`. (err: {})",
+            std::io::Error::last_os_error()
+        );
+        return None;
+    }
+
+    Some(())
+}
+
+/// reads a nul-terminated string from a fixed-size buffer.
+///
+/// empty strings become `None`.
+#[tracing::instrument]
+fn c_string(buf: &[u8]) -> Option<String> {
+    let len = buf.iter().position(|b| *b == 0).unwrap_or(buf.len());
+    let s = String::from_utf8_lossy(&buf[..len]).trim().to_string();
+
+    if s.is_empty() {
+        None
+    } else {
+        Some(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_link_mode_names() {
+        // 100baseT/Full, 1000baseT/Full, autoneg, and 2500baseT/Full
+        let mask = [(1 << 3) | (1 << 5) | (1 << 6), 1 << (47 - 32)];
+
+        assert_eq!(
+            link_mode_names(&mask),
+            vec!["100baseT/Full", "1000baseT/Full", "2500baseT/Full"]
+        );
+    }
+
+    #[test]
+    fn check_c_string() {
+        assert_eq!(c_string(b"igc\0\0\0").unwrap(), "igc");
+        assert!(c_string(b"\0\0\0").is_none());
+    }
+
+    #[test]
+    fn missing_interface() {
+        assert!(driver_info("ghr-not-real0").is_none());
+        assert!(supported_link_modes("ghr-not-real0").is_none());
+    }
+}
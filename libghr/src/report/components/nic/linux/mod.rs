@@ -0,0 +1,554 @@
+use std::path::{Path, PathBuf};
+
+use futures::StreamExt;
+
+use crate::{
+    prelude::internal::*,
+    report::components::{self, Context},
+};
+
+pub(crate) mod ethtool;
+mod nl80211;
+
+use ethtool::Ethtool;
+use nl80211::Nl80211;
+
+/// the `ARPHRD_LOOPBACK` interface type from `linux/if_arp.h`
+const ARPHRD_LOOPBACK: u32 = 772;
+
+/// interface types (`ARPHRD_*`) used by tunnels, like `ipip`, `sit`, and `gre`.
+///
+/// `ARPHRD_NONE` (65534) is used by `tun` and WireGuard devices.
+const ARPHRD_TUNNELS: &[u32] = &[768, 769, 776, 778, 823, 65534];
+
+/// find and returns info about network devices on the system.
+#[tracing::instrument(skip(context))]
+pub(crate) async fn get(context: &Context) -> GhrResult<Vec<ComponentInfo>> {
+    let net_sysfs = PathBuf::from("/sys/class/net");
+
+    // run for the sysfs
+    all(net_sysfs, ethtool::Ioctl, nl80211::Sockets, context).await
+}
+
+/// gets info about all devices at the given path.
+#[tracing::instrument(skip(context))]
+async fn all<P: AsRef<Path> + std::fmt::Debug>(
+    path: P,
+    ethtool: impl Ethtool,
+    nl80211: impl Nl80211,
+    context: &Context,
+) -> GhrResult<Vec<ComponentInfo>> {
+    let entries = async_fs::read_dir(path).await.map_err(|e| {
+        GhrError::ComponentInfoInaccessible(format!(
+            "Couldn't read network interface devices from `sysfs`. (err: {e})"
+        ))
+    })?;
+
+    // iterate over entries, only use good paths, then run them through `one`
+    Ok(entries
+        .map(|res| res.map(|entry| entry.path()))
+        .filter_map(|res| async { res.ok() })
+        .filter_map(|path| one(path, ethtool.clone(), nl80211.clone(), context))
+        .collect()
+        .await)
+}
+
+/// fetches info about the device at the given path.
+///
+/// path is to a `sysfs` networking device representation, generally
+/// at `/sys/class/net/<device>`.
+///
+/// loopback interfaces are skipped.
+#[tracing::instrument(skip(context))]
+async fn one<P: AsRef<Path> + std::fmt::Debug>(
+    path: P,
+    ethtool: impl Ethtool,
+    nl80211: impl Nl80211,
+    context: &Context,
+) -> Option<ComponentInfo> {
+    let path = path.as_ref();
+    let name = path.file_name()?.to_string_lossy().to_string();
+
+    // skip the loopback interface. it's not hardware!
+    let ty = sysfs_value_opt::<u32>(path.join("type")).await;
+    if ty == Some(ARPHRD_LOOPBACK) {
+        tracing::debug!("Skipping loopback interface `{name}`.");
+        return None;
+    }
+
+    // figure out what we're working with
+    let (kind, bus) = futures::join! {
+        kind(path, ty),
+        bus(path),
+    };
+
+    // usb cards keep their ids on the usb device, while most others use pci
+    let (class, id, vendor_id) = if bus == ComponentBus::Usb {
+        usb_names(path).await
+    } else {
+        let civ = Civ::new(path).await;
+        (civ.class, civ.id, civ.vendor)
+    };
+
+    // also grab some info about the link from `sysfs`
+    let (speed, mtu, state, carrier, duplex, mac_address, sysfs_driver) = futures::join! {
+        sysfs_value_opt::<u32>(path.join("speed")),
+        sysfs_value_opt::<u32>(path.join("mtu")),
+        state(path),
+        carrier(path),
+        duplex(path),
+        mac_address(path),
+        driver_name(path.join("device")),
+    };
+    let device = path.join("device");
+    let (mut status, errors) = futures::join! {
+        components::linux::status(&device, context),
+        errors(path),
+    };
+
+    // and ask the driver for anything `sysfs` doesn't have. its ioctls
+    // block, so keep them off the executor
+    let (ethtool_info, link_modes) = {
+        let name = name.clone();
+        blocking::unblock(move || {
+            (
+                ethtool.driver_info(&name),
+                ethtool.supported_link_modes(&name),
+            )
+        })
+        .await
+    };
+    let link_modes = link_modes.filter(|modes| !modes.is_empty());
+
+    let (ethtool_driver, firmware_version) = match ethtool_info {
+        Some(info) => (info.driver, info.firmware_version),
+        None => (None, None),
+    };
+
+    // virtual interfaces have no `device`, but `ethtool` still knows their
+    // driver (like `bridge` or `veth`)
+    let driver = sysfs_driver.or(ethtool_driver);
+
+    // the card's health depends on its driver and errors. a link slower than
+    // the card's fastest mode is normal (like on a gigabit switch), so
+    // that's only reported in `link_modes`
+    status.firmware_version = firmware_version.clone();
+    status.errors = errors;
+
+    // wi-fi cards also get a description of their radio
+    let wireless = if kind == NicKind::Wireless {
+        Some(wireless(path, nl80211).await)
+    } else {
+        None
+    };
+
+    Some(ComponentInfo {
+        bus,
+        id,
+        class,
+        vendor_id,
+        status: status.assess(),
+        desc: ComponentDescription::NicDescription(NicDescription {
+            // a wi-fi card's speed is just its current bitrate, which changes
+            // with the signal
+            max_speed: speed.filter(|_| kind != NicKind::Wireless),
+            mtu,
+            name: Some(name),
+            kind: Some(kind),
+            driver,
+            firmware_version,
+            state,
+            carrier,
+            duplex,
+            link_modes,
+            mac_address,
+            wireless,
+        }),
+    })
+}
+
+/// classifies the interface at `path`.
+///
+/// `ty` is the interface's `ARPHRD_*` type, if known.
+#[tracing::instrument]
+async fn kind(path: &Path, ty: Option<u32>) -> NicKind {
+    let (uevent, wireless, phy80211, bridge, bonding, tun_flags, device) = futures::join! {
+        sysfs_value_opt::<String>(path.join("uevent")),
+        exists(path.join("wireless")),
+        exists(path.join("phy80211")),
+        exists(path.join("bridge")),
+        exists(path.join("bonding")),
+        exists(path.join("tun_flags")),
+        exists(path.join("device")),
+    };
+
+    // the uevent file has a `DEVTYPE` for many virtual interfaces
+    let devtype = uevent.as_deref().and_then(|uevent| {
+        uevent
+            .lines()
+            .find_map(|line| line.strip_prefix("DEVTYPE="))
+            .map(|devtype| devtype.trim().to_string())
+    });
+
+    if wireless || phy80211 || devtype.as_deref() == Some("wlan") {
+        return NicKind::Wireless;
+    }
+
+    if bridge || devtype.as_deref() == Some("bridge") {
+        return NicKind::Bridge;
+    }
+
+    if bonding || devtype.as_deref() == Some("bond") {
+        return NicKind::Bond;
+    }
+
+    match devtype.as_deref() {
+        Some("vlan") => return NicKind::Vlan,
+        Some("wireguard" | "vxlan" | "geneve" | "gretap" | "ip6gretap" | "ipip" | "sit") => {
+            return NicKind::Tunnel
+        }
+        _ => (),
+    }
+
+    if tun_flags || ty.is_some_and(|ty| ARPHRD_TUNNELS.contains(&ty)) {
+        return NicKind::Tunnel;
+    }
+
+    // only real hardware has a `device`
+    if device {
+        NicKind::Physical
+    } else {
+        NicKind::Virtual
+    }
+}
+
+/// finds the bus that the interface's device is attached to.
+///
+/// virtual interfaces don't have one, so they'll be `Unknown`.
+#[tracing::instrument]
+async fn bus(path: &Path) -> ComponentBus {
+    let Ok(subsystem) = async_fs::read_link(path.join("device/subsystem")).await else {
+        return ComponentBus::Unknown;
+    };
+
+    match subsystem
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .as_deref()
+    {
+        Some("pci") => ComponentBus::Pci,
+        Some("usb") => ComponentBus::Usb,
+        Some(other) => ComponentBus::Other(other.to_string()),
+        None => ComponentBus::Unknown,
+    }
+}
+
+/// finds the (class, id, vendor) of a usb network card.
+///
+/// the interface's `device` is a usb interface, like `2-2:1.0`. its parent is
+/// the usb device itself.
+#[tracing::instrument]
+async fn usb_names(path: &Path) -> (Option<String>, Option<String>, Option<String>) {
+    let Ok(interface) = async_fs::canonicalize(path.join("device")).await else {
+        return (None, None, None);
+    };
+
+    let Some(usb_device) = interface.parent() else {
+        return (None, None, None);
+    };
+
+    let (vendor_id, id) =
+        crate::report::components::usb::linux::usb_vendor_and_id(usb_device).await;
+    (None, id, vendor_id)
+}
+
+/// describes the radio of a wireless interface.
+///
+/// `sysfs` links each wireless interface to its "phy" (at
+/// `/sys/class/ieee80211/phyN`), but the phy's capabilities are only
+/// available over `nl80211`.
+#[tracing::instrument]
+async fn wireless(path: &Path, nl80211: impl Nl80211) -> WirelessDescription {
+    let phy_path = path.join("phy80211");
+
+    let (phy, index, rfkill) = futures::join! {
+        sysfs_value_opt::<String>(phy_path.join("name")),
+        sysfs_value_opt::<u32>(phy_path.join("index")),
+        rfkill(&phy_path),
+    };
+
+    // netlink sockets block, so keep them off the executor
+    let (caps, regulatory_domain) = match index {
+        Some(index) => {
+            blocking::unblock(move || (nl80211.wiphy(index), nl80211.regulatory_domain(index)))
+                .await
+        }
+        None => (None, None),
+    };
+
+    // don't report empty lists when `nl80211` isn't around
+    let caps = caps.unwrap_or_default();
+    fn non_empty<T>(set: std::collections::BTreeSet<T>) -> Option<Vec<T>> {
+        (!set.is_empty()).then(|| set.into_iter().collect())
+    }
+
+    WirelessDescription {
+        phy,
+        standards: non_empty(caps.standards),
+        bands: non_empty(caps.bands),
+        max_spatial_streams: caps.max_spatial_streams,
+        channel_widths: non_empty(caps.channel_widths),
+        regulatory_domain,
+        rfkill,
+    }
+}
+
+/// reads the interface's operational state.
+#[tracing::instrument]
+async fn state(path: &Path) -> Option<NicState> {
+    let operstate = sysfs_value_opt::<String>(path.join("operstate")).await?;
+
+    match operstate.as_str() {
+        "up" => Some(NicState::Up),
+        "down" => Some(NicState::Down),
+        "dormant" => Some(NicState::Dormant),
+        "lowerlayerdown" => Some(NicState::LowerLayerDown),
+        "notpresent" => Some(NicState::NotPresent),
+        "testing" => Some(NicState::Testing),
+        other => {
+            tracing::debug!("Interface has an unknown operstate: `{other}`");
+            None
+        }
+    }
+}
+
+/// checks whether the interface has a link.
+///
+/// note that the kernel fails to read this file when the interface is down.
+#[tracing::instrument]
+async fn carrier(path: &Path) -> Option<bool> {
+    match sysfs_value_opt::<u8>(path.join("carrier")).await? {
+        1 => Some(true),
+        0 => Some(false),
+        other => {
+            tracing::debug!("Interface has a weird carrier value: `{other}`");
+            None
+        }
+    }
+}
+
+/// adds up the receive and transmit errors on the interface.
+#[tracing::instrument]
+async fn errors(path: &Path) -> Option<u64> {
+    let (rx, tx) = futures::join! {
+        sysfs_value_opt::<u64>(path.join("statistics/rx_errors")),
+        sysfs_value_opt::<u64>(path.join("statistics/tx_errors")),
+    };
+
+    match (rx, tx) {
+        (None, None) => None,
+        (rx, tx) => Some(rx.unwrap_or_default() + tx.unwrap_or_default()),
+    }
+}
+
+/// reads the duplex mode of the interface's link.
+#[tracing::instrument]
+async fn duplex(path: &Path) -> Option<Duplex> {
+    match sysfs_value_opt::<String>(path.join("duplex"))
+        .await?
+        .as_str()
+    {
+        "full" => Some(Duplex::Full),
+        "half" => Some(Duplex::Half),
+        _ => None,
+    }
+}
+
+/// reads the interface's mac address.
+#[tracing::instrument]
+async fn mac_address(path: &Path) -> Option<Sensitive> {
+    let address = sysfs_value_opt::<String>(path.join("address")).await?;
+
+    // some virtual interfaces have an all-zero address
+    if address.is_empty() || address.chars().all(|c| c == '0' || c == ':') {
+        return None;
+    }
+
+    Some(Sensitive::new(address))
+}
+
+/// checks if something exists at `path`, without following symlinks.
+#[tracing::instrument]
+async fn exists(path: PathBuf) -> bool {
+    async_fs::symlink_metadata(path).await.is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn check_nic_linux() {
+        // get the path of the card
+        let wifi_path = net_path().join("wlo1");
+
+        // give it to the func
+        let info = one(
+            wifi_path,
+            ethtool::Fixture,
+            nl80211::Fixture,
+            &Context::default(),
+        )
+        .await
+        .unwrap();
+
+        // check name + vendor
+        assert_eq!(info.vendor_id().unwrap(), "Intel Corporation");
+        assert_eq!(info.id().unwrap(), "Wi-Fi 6 AX210/AX211/AX411 160MHz");
+        assert_eq!(info.bus(), ComponentBus::Pci);
+
+        // grab adapter details
+        let ComponentDescription::NicDescription(desc) = info.desc else {
+            panic!("wrong desc");
+        };
+
+        // wi-fi has no fixed speed, but we can check the mtu
+        assert_eq!(desc.max_speed, None);
+        assert_eq!(desc.mtu, Some(2_800));
+
+        // and the rest
+        assert_eq!(desc.name.unwrap(), "wlo1");
+        assert_eq!(desc.kind.unwrap(), NicKind::Wireless);
+        assert_eq!(desc.driver.unwrap(), "iwlwifi");
+        assert_eq!(desc.state.unwrap(), NicState::Up);
+        assert_eq!(desc.carrier, Some(true));
+        assert!(desc.duplex.is_none());
+
+        // it's wireless, so we know about its radio, too
+        let wireless = desc.wireless.unwrap();
+        assert_eq!(wireless.phy.unwrap(), "phy0");
+        assert_eq!(
+            wireless.rfkill.unwrap(),
+            Rfkill {
+                soft_blocked: false,
+                hard_blocked: false,
+            }
+        );
+
+        // ...and what `nl80211` says it can do
+        assert_eq!(
+            wireless.standards.unwrap(),
+            [WifiStandard::N, WifiStandard::Ac, WifiStandard::Ax]
+        );
+        assert_eq!(
+            wireless.bands.unwrap(),
+            [WifiBand::Ghz2_4, WifiBand::Ghz5, WifiBand::Ghz6]
+        );
+        assert_eq!(wireless.max_spatial_streams, Some(2));
+        assert_eq!(wireless.channel_widths.unwrap(), [20, 40, 80, 160]);
+        assert_eq!(wireless.regulatory_domain.unwrap(), "US");
+    }
+
+    #[tokio::test]
+    async fn check_nic_linux_ethernet() {
+        let info = one(
+            net_path().join("enp7s0"),
+            ethtool::Fixture,
+            nl80211::Fixture,
+            &Context::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(info.bus(), ComponentBus::Pci);
+
+        let ComponentDescription::NicDescription(desc) = info.desc else {
+            panic!("wrong desc");
+        };
+
+        let status = info.status.unwrap();
+        assert_eq!(status.health, Health::Ok);
+        assert_eq!(status.driver_bound, Some(true));
+        assert_eq!(status.errors, Some(12));
+
+        assert_eq!(desc.kind.unwrap(), NicKind::Physical);
+        assert_eq!(desc.driver.unwrap(), "igb");
+        assert_eq!(desc.firmware_version.unwrap(), "3.30, 0x800005cc");
+        assert_eq!(
+            desc.link_modes.unwrap(),
+            ["10baseT/Full", "100baseT/Full", "1000baseT/Full"]
+        );
+        assert_eq!(desc.state.unwrap(), NicState::Down);
+        assert_eq!(desc.carrier, None, "carrier can't be read when down");
+        assert_eq!(desc.duplex.unwrap(), Duplex::Full);
+
+        // mac addresses are kept until the report redacts them
+        assert_eq!(
+            desc.mac_address.unwrap(),
+            Sensitive::new("10:7b:44:aa:bb:cc")
+        );
+
+        // and wired cards have no radio
+        assert!(desc.wireless.is_none());
+    }
+
+    #[tokio::test]
+    async fn check_nic_linux_usb() {
+        let info = one(
+            net_path().join("enx00e04c680001"),
+            ethtool::Fixture,
+            nl80211::Fixture,
+            &Context::default(),
+        )
+        .await
+        .unwrap();
+
+        // usb nics should say so
+        assert_eq!(info.bus(), ComponentBus::Usb);
+        assert_eq!(info.vendor_id().unwrap(), "Realtek Semiconductor Corp.");
+        assert_eq!(info.id().unwrap(), "RTL8153 Gigabit Ethernet Adapter");
+
+        let ComponentDescription::NicDescription(desc) = info.desc else {
+            panic!("wrong desc");
+        };
+
+        assert_eq!(desc.kind.unwrap(), NicKind::Physical);
+        assert_eq!(desc.driver.unwrap(), "r8152");
+    }
+
+    #[tokio::test]
+    async fn check_nic_linux_virtual() {
+        let all = all(
+            net_path(),
+            ethtool::Fixture,
+            nl80211::Fixture,
+            &Context::default(),
+        )
+        .await
+        .unwrap();
+
+        // find each interface's kind by name
+        let kinds = all
+            .into_iter()
+            .filter_map(|info| match info.desc {
+                ComponentDescription::NicDescription(desc) => Some((desc.name?, desc.kind?)),
+                _ => None,
+            })
+            .collect::<std::collections::HashMap<_, _>>();
+
+        // loopback isn't hardware, so it's skipped
+        assert!(!kinds.contains_key("lo"));
+
+        assert_eq!(kinds["docker0"], NicKind::Bridge);
+        assert_eq!(kinds["veth3a1b2c4"], NicKind::Virtual);
+        assert_eq!(kinds["wg0"], NicKind::Tunnel);
+        assert_eq!(kinds["enp7s0.10"], NicKind::Vlan);
+    }
+
+    #[tracing::instrument]
+    fn net_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs/sys/class/net"))
+    }
+}
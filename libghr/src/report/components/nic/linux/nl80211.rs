@@ -0,0 +1,657 @@
+//! a tiny `nl80211` client for reading wireless card capabilities.
+//!
+//! `sysfs` only tells us that a wireless "phy" exists. everything else (bands,
+//! standards, streams, ...) lives behind generic netlink, so we speak just
+//! enough of it here. none of these requests need root.
+
+use std::{
+    collections::BTreeSet,
+    os::fd::{AsRawFd as _, FromRawFd as _, OwnedFd},
+};
+
+use crate::prelude::internal::*;
+
+// netlink message types and flags from `linux/netlink.h`
+const NLMSG_ERROR: u16 = 2;
+const NLMSG_DONE: u16 = 3;
+const NLM_F_REQUEST: u16 = 0x1;
+const NLM_F_DUMP: u16 = 0x300;
+
+/// the size of a `struct nlmsghdr`
+const NLMSG_HDRLEN: usize = 16;
+/// the size of a `struct genlmsghdr`
+const GENL_HDRLEN: usize = 4;
+/// the size of a `struct nlattr`
+const NLA_HDRLEN: usize = 4;
+/// masks off the `NLA_F_NESTED` and `NLA_F_NET_BYTEORDER` flags
+const NLA_TYPE_MASK: u16 = 0x3fff;
+
+// generic netlink controller, from `linux/genetlink.h`
+const GENL_ID_CTRL: u16 = 0x10;
+const CTRL_CMD_GETFAMILY: u8 = 3;
+const CTRL_ATTR_FAMILY_ID: u16 = 1;
+const CTRL_ATTR_FAMILY_NAME: u16 = 2;
+
+// commands and attributes from `linux/nl80211.h`
+const NL80211_CMD_GET_WIPHY: u8 = 1;
+const NL80211_CMD_GET_REG: u8 = 31;
+const NL80211_ATTR_WIPHY: u16 = 1;
+const NL80211_ATTR_WIPHY_BANDS: u16 = 22;
+const NL80211_ATTR_REG_ALPHA2: u16 = 33;
+const NL80211_ATTR_SPLIT_WIPHY_DUMP: u16 = 174;
+
+// `enum nl80211_band_attr`
+const NL80211_BAND_ATTR_HT_MCS_SET: u16 = 3;
+const NL80211_BAND_ATTR_HT_CAPA: u16 = 4;
+const NL80211_BAND_ATTR_VHT_MCS_SET: u16 = 7;
+const NL80211_BAND_ATTR_VHT_CAPA: u16 = 8;
+const NL80211_BAND_ATTR_IFTYPE_DATA: u16 = 9;
+
+// `enum nl80211_band_iftype_attr`
+const NL80211_BAND_IFTYPE_ATTR_HE_CAP_PHY: u16 = 3;
+const NL80211_BAND_IFTYPE_ATTR_HE_CAP_MCS_SET: u16 = 4;
+const NL80211_BAND_IFTYPE_ATTR_EHT_CAP_PHY: u16 = 9;
+
+// `enum nl80211_band`
+const NL80211_BAND_2GHZ: u16 = 0;
+const NL80211_BAND_5GHZ: u16 = 1;
+const NL80211_BAND_60GHZ: u16 = 2;
+const NL80211_BAND_6GHZ: u16 = 3;
+
+/// capabilities of one wireless phy, collected from its `nl80211` messages.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct WiphyCaps {
+    pub(crate) bands: BTreeSet<WifiBand>,
+    pub(crate) standards: BTreeSet<WifiStandard>,
+    pub(crate) channel_widths: BTreeSet<u16>,
+    pub(crate) max_spatial_streams: Option<u8>,
+}
+
+impl WiphyCaps {
+    /// adds the info in one `NL80211_CMD_GET_WIPHY` message's attributes.
+    ///
+    /// split dumps spread a phy across many messages, so call this on each.
+    #[tracing::instrument(skip(self, payload))]
+    pub(crate) fn add_message(&mut self, payload: &[u8]) {
+        for (ty, bands) in attrs(payload) {
+            if ty != NL80211_ATTR_WIPHY_BANDS {
+                continue;
+            }
+
+            // each band is nested under its `enum nl80211_band` index
+            for (band, band_attrs) in attrs(bands) {
+                match band {
+                    NL80211_BAND_2GHZ => self.bands.insert(WifiBand::Ghz2_4),
+                    NL80211_BAND_5GHZ => self.bands.insert(WifiBand::Ghz5),
+                    NL80211_BAND_6GHZ => self.bands.insert(WifiBand::Ghz6),
+                    NL80211_BAND_60GHZ => self.bands.insert(WifiBand::Ghz60),
+                    other => {
+                        tracing::debug!("Skipping unknown band `{other}`.");
+                        continue;
+                    }
+                };
+
+                self.add_band(band_attrs);
+            }
+        }
+    }
+
+    /// adds the capabilities found in one band's attributes.
+    #[tracing::instrument(skip(self, band_attrs))]
+    fn add_band(&mut self, band_attrs: &[u8]) {
+        for (ty, data) in attrs(band_attrs) {
+            match ty {
+                // 802.11n. bit 1 is `SUP_WIDTH_20_40`
+                NL80211_BAND_ATTR_HT_CAPA => {
+                    self.standards.insert(WifiStandard::N);
+                    self.channel_widths.insert(20);
+
+                    if read_u16(data).is_some_and(|capa| capa & (1 << 1) != 0) {
+                        self.channel_widths.insert(40);
+                    }
+                }
+
+                // the first four bytes are the rx mcs bitmask, one per stream
+                NL80211_BAND_ATTR_HT_MCS_SET => {
+                    let streams = data.iter().take(4).filter(|mask| **mask != 0).count();
+                    self.add_streams(streams);
+                }
+
+                // 802.11ac always supports 80 MHz. bits 2-3 are the
+                // "supported channel width set"
+                NL80211_BAND_ATTR_VHT_CAPA => {
+                    self.standards.insert(WifiStandard::Ac);
+                    self.channel_widths.extend([20, 40, 80]);
+
+                    if read_u32(data).is_some_and(|capa| (capa >> 2) & 0b11 != 0) {
+                        self.channel_widths.insert(160);
+                    }
+                }
+
+                NL80211_BAND_ATTR_VHT_MCS_SET => {
+                    if let Some(map) = read_u16(data) {
+                        self.add_streams(streams_from_mcs_map(map));
+                    }
+                }
+
+                // 802.11ax and 802.11be are described per interface type
+                NL80211_BAND_ATTR_IFTYPE_DATA => {
+                    for (_, iftype_data) in attrs(data) {
+                        self.add_iftype_data(iftype_data);
+                    }
+                }
+
+                _ => (),
+            }
+        }
+    }
+
+    /// adds the he (802.11ax) and eht (802.11be) capabilities for one
+    /// interface type.
+    #[tracing::instrument(skip(self, iftype_data))]
+    fn add_iftype_data(&mut self, iftype_data: &[u8]) {
+        for (ty, data) in attrs(iftype_data) {
+            match ty {
+                // the first byte is the "channel width set"
+                NL80211_BAND_IFTYPE_ATTR_HE_CAP_PHY => {
+                    self.standards.insert(WifiStandard::Ax);
+                    self.channel_widths.insert(20);
+
+                    let widths = data.first().copied().unwrap_or_default();
+                    if widths & 0b0000_0110 != 0 {
+                        self.channel_widths.insert(40);
+                    }
+                    if widths & 0b0000_0100 != 0 {
+                        self.channel_widths.insert(80);
+                    }
+                    if widths & 0b0001_1000 != 0 {
+                        self.channel_widths.insert(160);
+                    }
+                }
+
+                // the first two bytes are the rx mcs map for <= 80 MHz
+                NL80211_BAND_IFTYPE_ATTR_HE_CAP_MCS_SET => {
+                    if let Some(map) = read_u16(data) {
+                        self.add_streams(streams_from_mcs_map(map));
+                    }
+                }
+
+                // bit 1 is "support for 320 MHz in 6 GHz"
+                NL80211_BAND_IFTYPE_ATTR_EHT_CAP_PHY => {
+                    self.standards.insert(WifiStandard::Be);
+
+                    if data.first().is_some_and(|b| b & (1 << 1) != 0) {
+                        self.channel_widths.insert(320);
+                    }
+                }
+
+                _ => (),
+            }
+        }
+    }
+
+    /// keeps the highest number of spatial streams seen.
+    #[tracing::instrument(skip(self))]
+    fn add_streams(&mut self, streams: usize) {
+        let Ok(streams) = u8::try_from(streams) else {
+            return;
+        };
+
+        if streams > 0 && self.max_spatial_streams.map_or(true, |max| streams > max) {
+            self.max_spatial_streams = Some(streams);
+        }
+    }
+}
+
+/// counts the streams in a vht/he mcs map.
+///
+/// each stream gets two bits, where `0b11` means "not supported".
+#[tracing::instrument]
+fn streams_from_mcs_map(map: u16) -> usize {
+    (0..8).filter(|n| (map >> (n * 2)) & 0b11 != 0b11).count()
+}
+
+/// asks the kernel's wireless stack about its phys.
+///
+/// collectors take one of these so tests can answer with fixture data
+/// instead of the host's real cards. its requests block, so they're sent
+/// from another thread.
+pub(crate) trait Nl80211: std::fmt::Debug + Clone + Send + 'static {
+    /// gets the capabilities of the phy with the given index.
+    fn wiphy(&self, index: u32) -> Option<WiphyCaps>;
+
+    /// gets the regulatory domain used by the phy with the given index.
+    fn regulatory_domain(&self, index: u32) -> Option<String>;
+}
+
+/// asks the kernel over a generic netlink socket.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Sockets;
+
+impl Nl80211 for Sockets {
+    fn wiphy(&self, index: u32) -> Option<WiphyCaps> {
+        wiphy(index)
+    }
+
+    fn regulatory_domain(&self, index: u32) -> Option<String> {
+        regulatory_domain(index)
+    }
+}
+
+/// answers for the phys in the test fixtures.
+#[cfg(test)]
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Fixture;
+
+#[cfg(test)]
+impl Nl80211 for Fixture {
+    fn wiphy(&self, index: u32) -> Option<WiphyCaps> {
+        // `phy0` is an ax210
+        (index == 0).then(|| {
+            let mut caps = WiphyCaps::default();
+            caps.add_message(&ax210_message());
+            caps
+        })
+    }
+
+    fn regulatory_domain(&self, index: u32) -> Option<String> {
+        (index == 0).then(|| alpha2(&regulatory_message()).unwrap())
+    }
+}
+
+/// makes an attribute
+#[cfg(test)]
+fn attr(ty: u16, data: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    put_attr(&mut buf, ty, data);
+    buf
+}
+
+/// builds a wiphy message for a Wi-Fi 6E card like the AX210: 2x2, with
+/// 802.11ax on 2.4, 5, and 6 GHz
+#[cfg(test)]
+fn ax210_message() -> Vec<u8> {
+    // 40 MHz, and two rx streams
+    let ht = [
+        attr(NL80211_BAND_ATTR_HT_CAPA, &0x09ef_u16.to_ne_bytes()),
+        attr(
+            NL80211_BAND_ATTR_HT_MCS_SET,
+            &[0xff, 0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+        ),
+    ]
+    .concat();
+
+    // 160 MHz, and a 2-stream mcs map (`0xfffa`)
+    let vht = [
+        attr(NL80211_BAND_ATTR_VHT_CAPA, &0x0399_79f6_u32.to_ne_bytes()),
+        attr(
+            NL80211_BAND_ATTR_VHT_MCS_SET,
+            &[0xfa, 0xff, 0, 0, 0, 0, 0, 0],
+        ),
+    ]
+    .concat();
+
+    // 40/80/160 MHz, with two streams
+    let he = attr(
+        NL80211_BAND_ATTR_IFTYPE_DATA,
+        &attr(
+            1,
+            &[
+                attr(NL80211_BAND_IFTYPE_ATTR_HE_CAP_PHY, &[0b0000_1110, 0, 0]),
+                attr(NL80211_BAND_IFTYPE_ATTR_HE_CAP_MCS_SET, &[0xfa, 0xff, 0, 0]),
+            ]
+            .concat(),
+        ),
+    );
+
+    let bands = [
+        attr(NL80211_BAND_2GHZ, &[ht.clone(), he.clone()].concat()),
+        attr(NL80211_BAND_5GHZ, &[ht, vht, he.clone()].concat()),
+        attr(NL80211_BAND_6GHZ, &he),
+    ]
+    .concat();
+
+    [
+        attr(NL80211_ATTR_WIPHY, &0_u32.to_ne_bytes()),
+        attr(NL80211_ATTR_WIPHY_BANDS, &bands),
+    ]
+    .concat()
+}
+
+/// builds a regulatory message for the united states
+#[cfg(test)]
+fn regulatory_message() -> Vec<u8> {
+    [
+        attr(NL80211_ATTR_WIPHY, &0_u32.to_ne_bytes()),
+        attr(NL80211_ATTR_REG_ALPHA2, b"US\0"),
+    ]
+    .concat()
+}
+
+/// grabs the capabilities of the wireless phy with the given index.
+#[tracing::instrument]
+fn wiphy(index: u32) -> Option<WiphyCaps> {
+    let mut netlink = Netlink::new()?;
+    let family = netlink.nl80211_family()?;
+
+    // ask for a split dump of just this phy. newer info (like 802.11ax) is
+    // only sent in split dumps
+    let mut request = Vec::new();
+    put_attr(&mut request, NL80211_ATTR_WIPHY, &index.to_ne_bytes());
+    put_attr(&mut request, NL80211_ATTR_SPLIT_WIPHY_DUMP, &[]);
+
+    let messages = netlink.request(
+        family,
+        NLM_F_REQUEST | NLM_F_DUMP,
+        NL80211_CMD_GET_WIPHY,
+        &request,
+    )?;
+
+    let mut caps = WiphyCaps::default();
+    for message in messages {
+        caps.add_message(&message);
+    }
+
+    Some(caps)
+}
+
+/// grabs the regulatory domain (country code) used by the phy with the given
+/// index.
+///
+/// cards that manage their own regulatory domain report it directly. for
+/// others, we use the system-wide one.
+#[tracing::instrument]
+fn regulatory_domain(index: u32) -> Option<String> {
+    let mut netlink = Netlink::new()?;
+    let family = netlink.nl80211_family()?;
+
+    let mut request = Vec::new();
+    put_attr(&mut request, NL80211_ATTR_WIPHY, &index.to_ne_bytes());
+
+    let messages = netlink
+        .request(family, NLM_F_REQUEST, NL80211_CMD_GET_REG, &request)
+        .or_else(|| netlink.request(family, NLM_F_REQUEST, NL80211_CMD_GET_REG, &[]))?;
+
+    messages.iter().find_map(|message| alpha2(message))
+}
+
+/// finds the regulatory domain in a `NL80211_CMD_GET_REG` message.
+#[tracing::instrument(skip(payload))]
+fn alpha2(payload: &[u8]) -> Option<String> {
+    attrs(payload)
+        .find(|(ty, _)| *ty == NL80211_ATTR_REG_ALPHA2)
+        .and_then(|(_, data)| c_str(data))
+}
+
+/// a generic netlink socket.
+struct Netlink {
+    socket: OwnedFd,
+    seq: u32,
+}
+
+impl Netlink {
+    /// opens a new generic netlink socket.
+    #[tracing::instrument]
+    fn new() -> Option<Self> {
+        // SAFETY: `socket` has no preconditions
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+                libc::NETLINK_GENERIC,
+            )
+        };
+
+        if fd < 0 {
+            tracing::debug!(
+                "Failed to open a generic netlink socket. (err: {})",
+                std::io::Error::last_os_error()
+            );
+            return None;
+        }
+
+        // SAFETY: we just created this fd, and nothing else owns it
+        let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        // don't wait forever on a reply that never comes
+        let timeout = libc::timeval {
+            tv_sec: 1,
+            tv_usec: 0,
+        };
+        // SAFETY: `timeout` is a valid `struct timeval` for its size
+        unsafe {
+            libc::setsockopt(
+                socket.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_RCVTIMEO,
+                core::ptr::addr_of!(timeout).cast(),
+                core::mem::size_of::<libc::timeval>() as libc::socklen_t,
+            )
+        };
+
+        Some(Self { socket, seq: 0 })
+    }
+
+    /// asks the generic netlink controller for the `nl80211` family id.
+    #[tracing::instrument(skip(self))]
+    fn nl80211_family(&mut self) -> Option<u16> {
+        let mut request = Vec::new();
+        put_attr(&mut request, CTRL_ATTR_FAMILY_NAME, b"nl80211\0");
+
+        let messages = self.request(GENL_ID_CTRL, NLM_F_REQUEST, CTRL_CMD_GETFAMILY, &request)?;
+
+        messages.iter().find_map(|message| {
+            attrs(message)
+                .find(|(ty, _)| *ty == CTRL_ATTR_FAMILY_ID)
+                .and_then(|(_, data)| read_u16(data))
+        })
+    }
+
+    /// sends a generic netlink request, then returns the payload of each
+    /// response (without its headers).
+    #[tracing::instrument(skip(self, attributes))]
+    fn request(
+        &mut self,
+        family: u16,
+        flags: u16,
+        cmd: u8,
+        attributes: &[u8],
+    ) -> Option<Vec<Vec<u8>>> {
+        self.seq += 1;
+        let seq = self.seq;
+
+        // build the message: `nlmsghdr`, `genlmsghdr`, then attributes
+        let len = NLMSG_HDRLEN + GENL_HDRLEN + attributes.len();
+        let mut message = Vec::with_capacity(len);
+        message.extend((len as u32).to_ne_bytes());
+        message.extend(family.to_ne_bytes());
+        message.extend(flags.to_ne_bytes());
+        message.extend(seq.to_ne_bytes());
+        message.extend(0_u32.to_ne_bytes()); // port id; the kernel fills it
+        message.extend([cmd, 1, 0, 0]); // cmd, version, reserved
+        message.extend(attributes);
+
+        // SAFETY: `message` is valid for its length
+        let sent = unsafe {
+            libc::send(
+                self.socket.as_raw_fd(),
+                message.as_ptr().cast(),
+                message.len(),
+                0,
+            )
+        };
+        if sent < 0 {
+            tracing::debug!(
+                "Failed to send a netlink request. (err: {})",
+                std::io::Error::last_os_error()
+            );
+            return None;
+        }
+
+        // dumps end with `NLMSG_DONE`, while others just send one message
+        let is_dump = flags & NLM_F_DUMP == NLM_F_DUMP;
+        let mut payloads = Vec::new();
+        let mut buf = vec![0_u8; 65536];
+
+        loop {
+            // SAFETY: `buf` is valid for its length
+            let received = unsafe {
+                libc::recv(
+                    self.socket.as_raw_fd(),
+                    buf.as_mut_ptr().cast(),
+                    buf.len(),
+                    0,
+                )
+            };
+            let Ok(received) = usize::try_from(received) else {
+                tracing::debug!(
+                    "Failed to receive a netlink response. (err: {})",
+                    std::io::Error::last_os_error()
+                );
+                return None;
+            };
+
+            let mut rest = &buf[..received];
+            while rest.len() >= NLMSG_HDRLEN {
+                let msg_len = read_u32(&rest[0..4])? as usize;
+                let msg_type = read_u16(&rest[4..6])?;
+                let msg_seq = read_u32(&rest[8..12])?;
+
+                if msg_len < NLMSG_HDRLEN || msg_len > rest.len() {
+                    tracing::warn!("Got a malformed netlink message.");
+                    return None;
+                }
+
+                let body = &rest[NLMSG_HDRLEN..msg_len];
+                rest = &rest[align(msg_len).min(rest.len())..];
+
+                // skip anything that isn't for us
+                if msg_seq != seq {
+                    continue;
+                }
+
+                match msg_type {
+                    NLMSG_DONE => return Some(payloads),
+                    NLMSG_ERROR => {
+                        // an error code of zero is just an acknowledgement
+                        let errno = body.get(0..4).and_then(read_u32).map(|e| e as i32);
+                        if errno != Some(0) {
+                            tracing::debug!("Netlink request failed. (errno: {errno:?})");
+                            return None;
+                        }
+                        return Some(payloads);
+                    }
+                    _ => payloads.push(body.get(GENL_HDRLEN..)?.to_vec()),
+                }
+            }
+
+            if !is_dump {
+                return Some(payloads);
+            }
+        }
+    }
+}
+
+/// iterates over the netlink attributes in `buf`, giving each one's type and
+/// payload.
+#[tracing::instrument(skip(buf))]
+fn attrs(mut buf: &[u8]) -> impl Iterator<Item = (u16, &[u8])> {
+    core::iter::from_fn(move || {
+        let len = usize::from(read_u16(buf.get(0..2)?)?);
+        let ty = read_u16(buf.get(2..4)?)? & NLA_TYPE_MASK;
+
+        if len < NLA_HDRLEN || len > buf.len() {
+            return None;
+        }
+
+        let data = &buf[NLA_HDRLEN..len];
+        buf = &buf[align(len).min(buf.len())..];
+
+        Some((ty, data))
+    })
+}
+
+/// appends an attribute to a netlink message.
+#[tracing::instrument(skip(buf, data))]
+fn put_attr(buf: &mut Vec<u8>, ty: u16, data: &[u8]) {
+    let len = NLA_HDRLEN + data.len();
+    buf.extend((len as u16).to_ne_bytes());
+    buf.extend(ty.to_ne_bytes());
+    buf.extend(data);
+    buf.resize(buf.len() + (align(len) - len), 0);
+}
+
+/// netlink aligns everything to four bytes.
+fn align(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+fn read_u16(data: &[u8]) -> Option<u16> {
+    Some(u16::from_ne_bytes(data.get(0..2)?.try_into().ok()?))
+}
+
+fn read_u32(data: &[u8]) -> Option<u32> {
+    Some(u32::from_ne_bytes(data.get(0..4)?.try_into().ok()?))
+}
+
+/// reads a nul-terminated string.
+fn c_str(data: &[u8]) -> Option<String> {
+    let len = data.iter().position(|b| *b == 0).unwrap_or(data.len());
+    let s = String::from_utf8_lossy(&data[..len]).trim().to_string();
+
+    if s.is_empty() {
+        None
+    } else {
+        Some(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_ax210_caps() {
+        let mut caps = WiphyCaps::default();
+        caps.add_message(&ax210_message());
+
+        assert_eq!(
+            caps.bands,
+            BTreeSet::from([WifiBand::Ghz2_4, WifiBand::Ghz5, WifiBand::Ghz6])
+        );
+        assert_eq!(
+            caps.standards,
+            BTreeSet::from([WifiStandard::N, WifiStandard::Ac, WifiStandard::Ax])
+        );
+        assert_eq!(caps.channel_widths, BTreeSet::from([20, 40, 80, 160]));
+        assert_eq!(caps.max_spatial_streams, Some(2));
+    }
+
+    #[test]
+    fn check_eht_caps() {
+        let eht = attr(
+            NL80211_BAND_ATTR_IFTYPE_DATA,
+            &attr(1, &attr(NL80211_BAND_IFTYPE_ATTR_EHT_CAP_PHY, &[0b10, 0])),
+        );
+        let message = attr(NL80211_ATTR_WIPHY_BANDS, &attr(NL80211_BAND_6GHZ, &eht));
+
+        let mut caps = WiphyCaps::default();
+        caps.add_message(&message);
+
+        assert!(caps.standards.contains(&WifiStandard::Be));
+        assert!(caps.channel_widths.contains(&320));
+    }
+
+    #[test]
+    fn check_mcs_map() {
+        assert_eq!(streams_from_mcs_map(0xfffe), 1);
+        assert_eq!(streams_from_mcs_map(0xfffa), 2);
+        assert_eq!(streams_from_mcs_map(0xffaa), 4);
+        assert_eq!(streams_from_mcs_map(0xffff), 0);
+    }
+
+    #[test]
+    fn check_alpha2() {
+        assert_eq!(alpha2(&regulatory_message()).unwrap(), "US");
+    }
+}
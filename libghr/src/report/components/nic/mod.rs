@@ -1,7 +1,7 @@
 use crate::prelude::internal::*;
 
 #[cfg(target_os = "linux")]
-mod linux;
+pub(crate) mod linux;
 
 #[cfg(target_os = "windows")]
 mod windows;
@@ -11,18 +11,188 @@ mod windows;
 #[non_exhaustive]
 pub struct NicDescription {
     /// The known maximum speed of the card, in Mbps.
+    ///
+    /// Wireless cards don't have one, since their speed changes with the
+    /// signal.
     pub max_speed: Option<u32>,
 
     /// The "maximum transfer unit" of a network interface card.
     pub mtu: Option<u32>,
+
+    /// The name of the interface, like `enp7s0` or `Wi-Fi`.
+    pub name: Option<String>,
+
+    /// What kind of interface this is.
+    pub kind: Option<NicKind>,
+
+    /// The driver in use for this interface.
+    pub driver: Option<String>,
+
+    /// The version of the firmware running on the card.
+    pub firmware_version: Option<String>,
+
+    /// The interface's operational state.
+    pub state: Option<NicState>,
+
+    /// Whether the interface has a physical link (e.g., a plugged-in cable).
+    pub carrier: Option<bool>,
+
+    /// The negotiated duplex mode of the link.
+    pub duplex: Option<Duplex>,
+
+    /// The link modes the card supports, like `1000baseT/Full`.
+    pub link_modes: Option<Vec<String>>,
+
+    /// The hardware (MAC) address of the interface.
+    ///
+    /// This is redacted by default. See [`crate::report::privacy`].
+    pub mac_address: Option<Sensitive>,
+
+    /// Extra info for wireless (Wi-Fi) cards.
+    pub wireless: Option<WirelessDescription>,
+}
+
+/// Capabilities and state of a wireless (Wi-Fi) card.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct WirelessDescription {
+    /// The name of the card's wireless "phy", like `phy0`.
+    pub phy: Option<String>,
+
+    /// The Wi-Fi standards the card supports.
+    pub standards: Option<Vec<WifiStandard>>,
+
+    /// The frequency bands the card can use.
+    pub bands: Option<Vec<WifiBand>>,
+
+    /// The most spatial streams (antenna chains) the card can receive with.
+    pub max_spatial_streams: Option<u8>,
+
+    /// The channel widths the card supports, in MHz.
+    pub channel_widths: Option<Vec<u16>>,
+
+    /// The regulatory domain in use, as a two-letter country code.
+    ///
+    /// `00` means the "world" domain, which is used when the country is
+    /// unknown.
+    pub regulatory_domain: Option<String>,
+
+    /// Whether the card's radio is blocked.
+    pub rfkill: Option<Rfkill>,
+}
+
+/// A Wi-Fi standard.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    TypeScript,
+)]
+#[non_exhaustive]
+pub enum WifiStandard {
+    /// 802.11n, or Wi-Fi 4.
+    N,
+    /// 802.11ac, or Wi-Fi 5.
+    Ac,
+    /// 802.11ax, or Wi-Fi 6 (and 6E on 6 GHz).
+    Ax,
+    /// 802.11be, or Wi-Fi 7.
+    Be,
+}
+
+/// A Wi-Fi frequency band.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    TypeScript,
+)]
+#[non_exhaustive]
+pub enum WifiBand {
+    /// 2.4 GHz.
+    Ghz2_4,
+    /// 5 GHz.
+    Ghz5,
+    /// 6 GHz.
+    Ghz6,
+    /// 60 GHz, used by 802.11ad/ay.
+    Ghz60,
+}
+
+/// The kind of a network interface.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum NicKind {
+    /// A wired interface backed by real hardware.
+    Physical,
+    /// A wireless (Wi-Fi) interface.
+    Wireless,
+    /// A software bridge connecting other interfaces.
+    Bridge,
+    /// Several interfaces bonded together into one.
+    Bond,
+    /// A VLAN on top of another interface.
+    Vlan,
+    /// A tunnel, like `tun`, WireGuard, or GRE.
+    Tunnel,
+    /// Any other software-only interface, like `veth` pairs.
+    Virtual,
+}
+
+/// The operational state of a network interface.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum NicState {
+    /// The interface is up and usable.
+    Up,
+    /// The interface is down.
+    Down,
+    /// The interface is up, but waiting on something, like Wi-Fi
+    /// authentication.
+    Dormant,
+    /// The interface it's stacked on is down.
+    LowerLayerDown,
+    /// The interface's hardware is missing.
+    NotPresent,
+    /// The interface is running self-tests.
+    Testing,
+}
+
+/// The duplex mode of a network link.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+pub enum Duplex {
+    /// Both sides can send at once.
+    Full,
+    /// Only one side can send at a time.
+    Half,
 }
 
 /// Finds and returns info about network devices on the system.
 #[tracing::instrument]
 pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
+    get_with(&super::Context::load().await).await
+}
+
+/// does the same as [`get`], looking devices up in the report's shared
+/// `context`.
+#[tracing::instrument(skip(context))]
+#[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+pub(crate) async fn get_with(context: &super::Context) -> GhrResult<Vec<ComponentInfo>> {
     #[cfg(target_os = "windows")]
     return windows::get().await;
 
     #[cfg(target_os = "linux")]
-    return linux::get().await;
+    return linux::get(context).await;
 }
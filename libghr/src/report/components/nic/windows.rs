@@ -62,6 +62,32 @@ async fn one(fields: HashMap<String, Variant>) -> Option<ComponentInfo> {
         })
         .and_then(|megs| u32::try_from(megs).ok());
 
+    // the connection name (like "Wi-Fi") and driver service
+    let connection_name = fields
+        .get("NetConnectionID")
+        .and_then(|v| v.string_from_variant());
+    let driver = fields
+        .get("ServiceName")
+        .and_then(|v| v.string_from_variant());
+
+    // the connection status tells us whether the link is up.
+    //
+    // see: https://learn.microsoft.com/en-us/windows/win32/cimwin32prov/win32-networkadapter
+    let (state, carrier) = match fields
+        .get("NetConnectionStatus")
+        .and_then(|v| v.u32_from_variant())
+    {
+        Some(2) => (Some(NicState::Up), Some(true)),
+        Some(0) => (Some(NicState::Down), None),
+        Some(7) => (Some(NicState::Down), Some(false)),
+        _ => (None, None),
+    };
+
+    let mac_address = fields
+        .get("MACAddress")
+        .and_then(|v| v.string_from_variant())
+        .map(Sensitive::new);
+
     Some(ComponentInfo {
         bus: ComponentBus::Unknown,
         id: name,
@@ -71,6 +97,16 @@ async fn one(fields: HashMap<String, Variant>) -> Option<ComponentInfo> {
         desc: ComponentDescription::NicDescription(NicDescription {
             max_speed,
             mtu: None,
+            name: connection_name,
+            kind: None,
+            driver,
+            firmware_version: None,
+            state,
+            carrier,
+            duplex: None,
+            link_modes: None,
+            mac_address,
+            wireless: None,
         }),
     })
 }
@@ -99,6 +135,11 @@ mod tests {
 
         // speed is null for this one. it's correct to be none.
         assert!(desc.max_speed.is_none());
+
+        // connection info
+        assert_eq!(desc.name.unwrap(), "Wi-Fi");
+        assert_eq!(desc.driver.unwrap(), "Netwtw10");
+        assert_eq!(desc.state.unwrap(), NicState::Up);
     }
 
     #[tracing::instrument]
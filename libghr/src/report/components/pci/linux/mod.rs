@@ -0,0 +1,560 @@
+//! pci
+
+use std::path::Path;
+
+use crate::{
+    prelude::internal::*,
+    report::components::{self, Context},
+};
+
+mod modalias;
+
+use modalias::ModAliases;
+
+/// pci classes (the first byte of `class`) that are useless without a
+/// driver: storage, network, display, and multimedia.
+const DRIVER_CLASSES: &[&str] = &["0x01", "0x02", "0x03", "0x04"];
+
+/// `IORESOURCE_IO` from `linux/ioport.h`
+const IORESOURCE_IO: u64 = 0x0000_0100;
+/// `IORESOURCE_MEM`
+const IORESOURCE_MEM: u64 = 0x0000_0200;
+/// `IORESOURCE_PREFETCH`
+const IORESOURCE_PREFETCH: u64 = 0x0000_2000;
+/// `IORESOURCE_MEM_64`
+const IORESOURCE_MEM_64: u64 = 0x0010_0000;
+
+#[tracing::instrument(skip(context))]
+/// Gets a list of PCI devices on the system.
+pub async fn get(context: &Context) -> GhrResult<Vec<ComponentInfo>> {
+    // every device shares the same alias list, so only load it once
+    let aliases = ModAliases::load().await;
+
+    all("/sys/bus/pci/devices", &aliases, context).await
+}
+
+/// gets info about all pci functions at the given path.
+#[tracing::instrument(skip(aliases, context))]
+async fn all(
+    path: impl AsRef<Path> + std::fmt::Debug,
+    aliases: &ModAliases,
+    context: &Context,
+) -> GhrResult<Vec<ComponentInfo>> {
+    // grab info about pci devices and construct reprs
+    let mut pci = Vec::new();
+    for dev in crate::report::components::linux::devices(path).await? {
+        pci.push(one(&dev.path(), aliases, context).await);
+    }
+
+    Ok(pci)
+}
+
+/// grabs info about the pci function at `path`, like
+/// `/sys/bus/pci/devices/0000:00:14.0`.
+#[tracing::instrument(skip(aliases, context))]
+async fn one(path: &Path, aliases: &ModAliases, context: &Context) -> ComponentInfo {
+    // load its vendor id and product name
+    let (vendor, product, class) = futures::join!(
+        sysfs_value_opt::<String>(path.join("vendor")),
+        sysfs_value_opt::<String>(path.join("device")),
+        sysfs_value_opt::<String>(path.join("class")),
+    );
+
+    // read a few files to get important info about this thang
+    let raw_class = class.clone();
+    let ((id, vendor_id), class) = (
+        convert_to_pci_names(product, vendor),
+        convert_to_pci_class(class),
+    );
+
+    // and everything else
+    let (
+        driver,
+        modalias,
+        link,
+        iommu_group,
+        power_state,
+        runtime_status,
+        sriov,
+        bars,
+        aer,
+        status,
+    ) = futures::join!(
+        driver_name(path),
+        sysfs_value_opt::<String>(path.join("modalias")),
+        link(path),
+        iommu_group(path),
+        power_state(path),
+        runtime_status(path),
+        sriov(path),
+        bars(path),
+        aer(path),
+        components::linux::status(path, context),
+    );
+
+    let modules = modalias
+        .map(|modalias| aliases.modules(&modalias))
+        .filter(|modules| !modules.is_empty());
+
+    // judge the function's health from what we found
+    let mut status = status;
+    if status.driver_bound == Some(false)
+        && expects_driver(raw_class.as_deref(), modules.as_deref())
+    {
+        status.flag(Health::Warning, "No driver is bound.");
+    }
+    status.link_degraded = link.as_ref().and_then(link_degraded);
+    if let Some(ref aer) = aer {
+        status.errors = Some(aer.correctable + aer.nonfatal + aer.fatal);
+
+        if aer.fatal > 0 {
+            status.flag(
+                Health::Critical,
+                format!("The device reported {} fatal PCIe errors.", aer.fatal),
+            );
+        } else if aer.nonfatal > 0 {
+            status.flag(
+                Health::Warning,
+                format!(
+                    "The device reported {} uncorrectable PCIe errors.",
+                    aer.nonfatal
+                ),
+            );
+        }
+    }
+
+    ComponentInfo {
+        bus: ComponentBus::Pci,
+        id,
+        class,
+        vendor_id,
+        status: status.assess(),
+        desc: ComponentDescription::PciDescription(PciDescription {
+            address: path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string()),
+            driver,
+            modules,
+            link,
+            iommu_group,
+            power_state,
+            runtime_status,
+            sriov,
+            bars,
+            aer,
+        }),
+    }
+}
+
+/// reads the function's pcie link info.
+///
+/// conventional pci devices don't have these files.
+#[tracing::instrument]
+async fn link(path: &Path) -> Option<PciLink> {
+    let (speed, width, max_speed, max_width) = futures::join!(
+        sysfs_value_opt::<String>(path.join("current_link_speed")),
+        sysfs_value_opt::<u32>(path.join("current_link_width")),
+        sysfs_value_opt::<String>(path.join("max_link_speed")),
+        sysfs_value_opt::<u32>(path.join("max_link_width")),
+    );
+
+    // the kernel says "Unknown" for links it can't read
+    let known = |speed: Option<String>| speed.filter(|s| !s.starts_with("Unknown"));
+    let (speed, max_speed) = (known(speed), known(max_speed));
+
+    if speed.is_none() && width.is_none() && max_speed.is_none() && max_width.is_none() {
+        return None;
+    }
+
+    Some(PciLink {
+        speed,
+        width,
+        max_speed,
+        max_width,
+    })
+}
+
+/// checks if there's any reason to think the function should have a driver.
+///
+/// lots of functions (like bridges) work fine without one, so we only expect
+/// it when a module says it can drive the function, or when the function is
+/// useless without one.
+fn expects_driver(class: Option<&str>, modules: Option<&[String]>) -> bool {
+    modules.is_some_and(|modules| !modules.is_empty())
+        || class.is_some_and(|class| DRIVER_CLASSES.iter().any(|c| class.starts_with(c)))
+}
+
+/// checks if the link is running narrower than it can.
+///
+/// the speed isn't checked, since many devices (like gpus) slow their links
+/// down when idle. it's still in the link info, though.
+fn link_degraded(link: &PciLink) -> Option<bool> {
+    Some(link.width? < link.max_width?)
+}
+
+/// finds the iommu group of the function.
+///
+/// `iommu_group` links to `/sys/kernel/iommu_groups/<n>`.
+#[tracing::instrument]
+async fn iommu_group(path: &Path) -> Option<u32> {
+    let group = async_fs::read_link(path.join("iommu_group")).await.ok()?;
+    group.file_name()?.to_string_lossy().parse().ok()
+}
+
+/// reads the function's current device power state.
+#[tracing::instrument]
+async fn power_state(path: &Path) -> Option<PciPowerState> {
+    match sysfs_value_opt::<String>(path.join("power_state"))
+        .await?
+        .as_str()
+    {
+        "D0" => Some(PciPowerState::D0),
+        "D1" => Some(PciPowerState::D1),
+        "D2" => Some(PciPowerState::D2),
+        "D3hot" => Some(PciPowerState::D3Hot),
+        "D3cold" => Some(PciPowerState::D3Cold),
+        other => {
+            tracing::debug!("Unknown PCI power state: `{other}`");
+            None
+        }
+    }
+}
+
+/// reads the function's sr-iov virtual function counts.
+///
+/// only physical functions that support sr-iov have these files.
+#[tracing::instrument]
+async fn sriov(path: &Path) -> Option<SrIov> {
+    let (total_vfs, enabled_vfs) = futures::join!(
+        sysfs_value_opt::<u32>(path.join("sriov_totalvfs")),
+        sysfs_value_opt::<u32>(path.join("sriov_numvfs")),
+    );
+
+    Some(SrIov {
+        total_vfs: total_vfs?,
+        enabled_vfs: enabled_vfs.unwrap_or_default(),
+    })
+}
+
+/// reads the function's base address registers.
+///
+/// each line of the `resource` file is a `start end flags` triple. the first
+/// six lines are the bars, followed by the expansion rom and bridge windows.
+#[tracing::instrument]
+async fn bars(path: &Path) -> Option<Vec<PciBar>> {
+    let resource = sysfs_value_opt::<String>(path.join("resource")).await?;
+    let bars = parse_bars(&resource);
+
+    (!bars.is_empty()).then_some(bars)
+}
+
+/// parses the contents of a `resource` file into a list of bars.
+#[tracing::instrument(skip(resource))]
+fn parse_bars(resource: &str) -> Vec<PciBar> {
+    let hex = |s: &str| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok();
+
+    resource
+        .lines()
+        .take(6)
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let mut parts = line.split_whitespace();
+            let (start, end, flags) = (
+                hex(parts.next()?)?,
+                hex(parts.next()?)?,
+                hex(parts.next()?)?,
+            );
+
+            // unused bars are all zeroes
+            if end == 0 || end < start {
+                return None;
+            }
+
+            let kind = if flags & IORESOURCE_IO != 0 {
+                PciBarKind::Io
+            } else if flags & IORESOURCE_MEM != 0 {
+                PciBarKind::Memory
+            } else {
+                return None;
+            };
+
+            Some(PciBar {
+                index: index as u8,
+                size: end - start + 1,
+                kind,
+                prefetchable: flags & IORESOURCE_PREFETCH != 0,
+                is_64_bit: flags & IORESOURCE_MEM_64 != 0,
+            })
+        })
+        .collect()
+}
+
+/// reads the function's aer error counters.
+///
+/// each file lists counts for specific errors, followed by a total, like
+/// `TOTAL_ERR_COR 2`.
+#[tracing::instrument]
+async fn aer(path: &Path) -> Option<AerCounters> {
+    let (correctable, nonfatal, fatal) = futures::join!(
+        aer_total(path.join("aer_dev_correctable"), "TOTAL_ERR_COR"),
+        aer_total(path.join("aer_dev_nonfatal"), "TOTAL_ERR_NONFATAL"),
+        aer_total(path.join("aer_dev_fatal"), "TOTAL_ERR_FATAL"),
+    );
+
+    Some(AerCounters {
+        correctable: correctable?,
+        nonfatal: nonfatal?,
+        fatal: fatal?,
+    })
+}
+
+/// reads the total from one aer counter file.
+#[tracing::instrument]
+async fn aer_total(path: std::path::PathBuf, key: &str) -> Option<u64> {
+    let contents = sysfs_value_opt::<String>(path).await?;
+
+    contents.lines().find_map(|line| {
+        let (name, count) = line.split_once(' ')?;
+        (name == key).then(|| count.trim().parse().ok())?
+    })
+}
+
+/// converts the device + vendor id to strings via pci lookup
+///
+/// NOTE: this returns the idents in `(id, vendor_id)` order.
+#[tracing::instrument]
+pub fn convert_to_pci_names(
+    id: Option<String>,
+    vendor_id: Option<String>,
+) -> (Option<String>, Option<String>) {
+    // make sure we've got both
+    if let (Some(id), Some(vendor_id)) = (&id, &vendor_id) {
+        // try converting them to hex
+        if let (Ok(pid), Ok(vid)) = (
+            u16::from_str_radix(&id.replace("0x", ""), 16),
+            u16::from_str_radix(&vendor_id.replace("0x", ""), 16),
+        ) {
+            // try finding them a pci device
+            if let Some(d) = pci_ids::Device::from_vid_pid(vid, pid) {
+                // return a tuple of the id, vendor_id
+                return (
+                    Some(d.name().to_string()),
+                    Some(d.vendor().name().to_string()),
+                );
+            }
+        }
+    }
+
+    // otherwise, it didn't work. return the inputs
+    tracing::warn!("Failed to get PCI identifiers.");
+    (id, vendor_id)
+}
+
+#[tracing::instrument(skip(class))]
+/// converts a given pci class identifier to a string.
+///
+/// if it's not able to do so, returns the given value.
+pub fn convert_to_pci_class(class: Option<String>) -> Option<String> {
+    // make sure we've got a class
+    let Some((class, subclass)) = class.map(|c| {
+        let string = c.trim().to_string().replace("0x", "");
+        let split = string.split_at(2);
+
+        let (c, mut subc) = (split.0.to_string(), split.1.to_string());
+        subc.truncate(2);
+        (c, subc)
+    }) else {
+        tracing::warn!("Failed to find the class of this PCI device.");
+        return None;
+    };
+
+    // make sure we can convert it into a number
+    let (Ok(class_num), Ok(subclass_num)) = (
+        u8::from_str_radix(&class, 16),
+        u8::from_str_radix(&subclass, 16),
+    ) else {
+        tracing::warn!("Class was not a number: {}", &class);
+        return None;
+    };
+
+    // we do! let's parse it for an ID we understand
+    if let Some(parsed) = pci_ids::Subclass::from_cid_sid(class_num, subclass_num) {
+        return Some(format!("{} ({})", parsed.class().name(), parsed.name()));
+    }
+
+    Some(class)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn check_pci_nvme() {
+        let aliases = ModAliases::from_dir(sysfs_root().join("lib/modules/6.11.5")).await;
+        let info = one(
+            &pci_path().join("0000:01:00.0"),
+            &aliases,
+            &Context::default(),
+        )
+        .await;
+
+        assert_eq!(info.vendor_id().unwrap(), "Samsung Electronics Co Ltd");
+        assert_eq!(info.id().unwrap(), "NVMe SSD Controller PM9A1/PM9A3/980PRO");
+
+        // the uncorrectable aer error is worth a warning
+        let status = info.status().unwrap();
+        assert_eq!(status.health, Health::Warning);
+        assert_eq!(status.driver_bound, Some(true));
+        assert_eq!(status.link_degraded, Some(false));
+        assert_eq!(status.errors, Some(4));
+        assert_eq!(status.reasons.unwrap().len(), 1);
+
+        let ComponentDescription::PciDescription(desc) = info.desc else {
+            panic!("wrong desc");
+        };
+
+        assert_eq!(desc.address.unwrap(), "0000:01:00.0");
+        assert_eq!(desc.driver.unwrap(), "nvme");
+        assert_eq!(desc.modules.unwrap(), vec!["nvme"]);
+        assert_eq!(desc.iommu_group, Some(14));
+        assert_eq!(desc.power_state.unwrap(), PciPowerState::D0);
+        assert_eq!(desc.runtime_status.unwrap(), RuntimeStatus::Active);
+        assert!(desc.sriov.is_none());
+
+        let link = desc.link.unwrap();
+        assert_eq!(link.speed.unwrap(), "16.0 GT/s PCIe");
+        assert_eq!(link.width, Some(4));
+        assert_eq!(link.max_width, Some(4));
+
+        // one 16 KiB, 64-bit bar
+        assert_eq!(
+            desc.bars.unwrap(),
+            vec![PciBar {
+                index: 0,
+                size: 16 * 1024,
+                kind: PciBarKind::Memory,
+                prefetchable: false,
+                is_64_bit: true,
+            }]
+        );
+
+        assert_eq!(
+            desc.aer.unwrap(),
+            AerCounters {
+                correctable: 3,
+                nonfatal: 1,
+                fatal: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn check_pci_sriov() {
+        let aliases = ModAliases::from_dir(sysfs_root().join("lib/modules/6.11.5")).await;
+        let info = one(
+            &pci_path().join("0000:02:00.0"),
+            &aliases,
+            &Context::default(),
+        )
+        .await;
+
+        assert_eq!(info.vendor_id().unwrap(), "Intel Corporation");
+
+        // it's in a narrower slot than it supports
+        let status = info.status().unwrap();
+        assert_eq!(status.health, Health::Warning);
+        assert_eq!(status.link_degraded, Some(true));
+        assert!(status.errors.is_none());
+
+        let ComponentDescription::PciDescription(desc) = info.desc else {
+            panic!("wrong desc");
+        };
+
+        assert_eq!(desc.driver.unwrap(), "igb");
+        assert_eq!(desc.modules.unwrap(), vec!["igb"]);
+        assert_eq!(desc.power_state.unwrap(), PciPowerState::D3Hot);
+        assert_eq!(desc.runtime_status.unwrap(), RuntimeStatus::Suspended);
+        assert_eq!(
+            desc.sriov.unwrap(),
+            SrIov {
+                total_vfs: 7,
+                enabled_vfs: 2,
+            }
+        );
+
+        // the link is also running slower than it could, but that's only
+        // reported
+        let link = desc.link.unwrap();
+        assert_eq!(link.speed.unwrap(), "2.5 GT/s PCIe");
+        assert_eq!(link.max_speed.unwrap(), "5.0 GT/s PCIe");
+        assert_eq!(link.width, Some(1));
+        assert_eq!(link.max_width, Some(4));
+
+        // two memory bars and an i/o one. the expansion rom isn't a bar
+        let bars = desc.bars.unwrap();
+        assert_eq!(
+            bars.iter().map(|bar| bar.index).collect::<Vec<_>>(),
+            vec![0, 2, 3]
+        );
+        assert_eq!(bars[1].kind, PciBarKind::Io);
+        assert_eq!(bars[1].size, 32);
+
+        // no aer files here
+        assert!(desc.aer.is_none());
+    }
+
+    #[test]
+    fn check_link_degraded() {
+        let link = |speed: &str, width| PciLink {
+            speed: Some(speed.into()),
+            width: Some(width),
+            max_speed: Some("16.0 GT/s PCIe".into()),
+            max_width: Some(16),
+        };
+
+        // idle gpus slow their links down, and that's fine
+        assert_eq!(link_degraded(&link("2.5 GT/s PCIe", 16)), Some(false));
+        assert_eq!(link_degraded(&link("16.0 GT/s PCIe", 8)), Some(true));
+    }
+
+    #[test]
+    fn check_expects_driver() {
+        let modules = ["snd_hda_intel".to_string()];
+
+        // a host bridge is fine on its own
+        assert!(!expects_driver(Some("0x060000"), None));
+        assert!(!expects_driver(Some("0x088000"), None));
+
+        // ...but not when a module claims it, or it's a network card
+        assert!(expects_driver(Some("0x060000"), Some(&modules)));
+        assert!(expects_driver(Some("0x020000"), None));
+        assert!(expects_driver(Some("0x030000"), None));
+    }
+
+    #[tokio::test]
+    async fn check_pci_all() {
+        let all = all(pci_path(), &ModAliases::default(), &Context::default())
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 2);
+
+        // without aliases, we just don't know the modules
+        assert!(all.iter().all(|info| match &info.desc {
+            ComponentDescription::PciDescription(desc) => desc.modules.is_none(),
+            _ => false,
+        }));
+    }
+
+    #[tracing::instrument]
+    fn sysfs_root() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs"))
+    }
+
+    #[tracing::instrument]
+    fn pci_path() -> PathBuf {
+        sysfs_root().join("sys/bus/pci/devices")
+    }
+}
@@ -0,0 +1,231 @@
+//! matches device `modalias` strings to the kernel modules that handle them.
+//!
+//! `depmod` writes a list of glob patterns for each module into
+//! `/lib/modules/<release>/modules.alias`, like:
+//!
+//! ```text
+//! alias pci:v00008086d00002725sv*sd*bc*sc*i* iwlwifi
+//! ```
+//!
+//! so finding a device's modules just means matching its `modalias` against
+//! every pattern.
+
+use std::path::Path;
+
+use crate::prelude::internal::*;
+
+/// the alias files written by `depmod`. built-in modules are listed
+/// separately.
+const ALIAS_FILES: &[&str] = &["modules.alias", "modules.builtin.alias"];
+
+/// a list of module aliases.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ModAliases {
+    /// (pattern, module) pairs, in file order
+    aliases: Vec<(String, String)>,
+}
+
+impl ModAliases {
+    /// loads the aliases for the running kernel.
+    ///
+    /// this is empty if the kernel's module directory doesn't exist, like in
+    /// many containers.
+    #[tracing::instrument]
+    pub(crate) async fn load() -> Self {
+        let Some(release) = sysfs_value_opt::<String>("/proc/sys/kernel/osrelease").await else {
+            tracing::warn!("Couldn't find the kernel release. Module aliases are unavailable.");
+            return Self::default();
+        };
+
+        Self::from_dir(format!("/lib/modules/{release}")).await
+    }
+
+    /// loads the aliases in the given module directory, like
+    /// `/lib/modules/6.11.5`.
+    #[tracing::instrument]
+    pub(crate) async fn from_dir(dir: impl AsRef<Path> + std::fmt::Debug) -> Self {
+        let mut aliases = Vec::new();
+
+        for file in ALIAS_FILES {
+            let Ok(contents) = async_fs::read_to_string(dir.as_ref().join(file)).await else {
+                tracing::debug!("No `{file}` in the module directory.");
+                continue;
+            };
+
+            aliases.extend(contents.lines().filter_map(|line| {
+                let mut parts = line.split_whitespace();
+
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some("alias"), Some(pattern), Some(module)) => {
+                        Some((pattern.to_string(), module.to_string()))
+                    }
+                    _ => None,
+                }
+            }));
+        }
+
+        Self { aliases }
+    }
+
+    /// finds all modules with an alias matching `modalias`.
+    #[tracing::instrument(skip(self))]
+    pub(crate) fn modules(&self, modalias: &str) -> Vec<String> {
+        let mut modules = Vec::new();
+
+        for (pattern, module) in &self.aliases {
+            if !modules.contains(module) && glob_match(pattern.as_bytes(), modalias.as_bytes()) {
+                modules.push(module.clone());
+            }
+        }
+
+        modules
+    }
+}
+
+/// matches `text` against a shell-style glob `pattern`.
+///
+/// this supports `*`, `?`, and bracketed sets like `[0-9]`, which is
+/// everything `depmod` uses.
+///
+/// this runs for every alias, so it's not instrumented.
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+
+    // where to go back to if we hit a mismatch after a `*`
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                star = Some((p, t));
+                p += 1;
+                continue;
+            }
+            Some(b'?') => {
+                p += 1;
+                t += 1;
+                continue;
+            }
+            Some(b'[') => {
+                if let Some((matched, len)) = match_set(&pattern[p..], text[t]) {
+                    if matched {
+                        p += len;
+                        t += 1;
+                        continue;
+                    }
+                } else if text[t] == b'[' {
+                    // an unclosed bracket is just a bracket
+                    p += 1;
+                    t += 1;
+                    continue;
+                }
+            }
+            Some(c) if *c == text[t] => {
+                p += 1;
+                t += 1;
+                continue;
+            }
+            _ => (),
+        }
+
+        // mismatch. let the last `*` eat one more character, if there is one
+        let Some((star_p, star_t)) = star else {
+            return false;
+        };
+        star = Some((star_p, star_t + 1));
+        p = star_p + 1;
+        t = star_t + 1;
+    }
+
+    // any leftover pattern must be stars
+    pattern[p..].iter().all(|c| *c == b'*')
+}
+
+/// checks if `c` is in the bracketed set at the start of `pattern`.
+///
+/// returns whether it matched and the length of the set, or `None` if the
+/// set isn't closed.
+fn match_set(pattern: &[u8], c: u8) -> Option<(bool, usize)> {
+    let mut i = 1;
+    let negated = matches!(pattern.get(i), Some(b'!' | b'^'));
+    if negated {
+        i += 1;
+    }
+
+    let mut matched = false;
+    let mut first = true;
+
+    loop {
+        let start = *pattern.get(i)?;
+
+        // a `]` right at the start is part of the set
+        if start == b']' && !first {
+            return Some((matched != negated, i + 1));
+        }
+        first = false;
+
+        // ranges, like `a-z`
+        if pattern.get(i + 1) == Some(&b'-') && pattern.get(i + 2).is_some_and(|end| *end != b']') {
+            let end = pattern[i + 2];
+            matched |= (start..=end).contains(&c);
+            i += 3;
+        } else {
+            matched |= start == c;
+            i += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_glob_match() {
+        let modalias = b"pci:v00008086d00002725sv00008086sd00000024bc02sc80i00";
+
+        assert!(glob_match(
+            b"pci:v00008086d00002725sv*sd*bc*sc*i*",
+            modalias
+        ));
+        assert!(glob_match(b"pci:v*d*sv*sd*bc02sc80i*", modalias));
+        assert!(glob_match(b"pci:v00008086d0000272?sv*", modalias));
+        assert!(!glob_match(
+            b"pci:v00008086d00002723sv*sd*bc*sc*i*",
+            modalias
+        ));
+        assert!(!glob_match(b"usb:v*", modalias));
+
+        // sets and ranges
+        assert!(glob_match(
+            b"usb:v0BDAp8153d[3-9]*",
+            b"usb:v0BDAp8153d3100dc00"
+        ));
+        assert!(!glob_match(
+            b"usb:v0BDAp8153d[3-9]*",
+            b"usb:v0BDAp8153d2100dc00"
+        ));
+        assert!(glob_match(b"a[!b]c", b"axc"));
+        assert!(!glob_match(b"a[!b]c", b"abc"));
+    }
+
+    #[tokio::test]
+    async fn check_modules() {
+        let root = env!("CARGO_MANIFEST_DIR");
+        let aliases = ModAliases::from_dir(format!(
+            "{root}/tests/assets/linux/sysfs/lib/modules/6.11.5"
+        ))
+        .await;
+
+        // a samsung nvme drive is handled by the built-in `nvme` driver
+        assert_eq!(
+            aliases.modules("pci:v0000144Dd0000A80Asv0000144Dsd0000A801bc01sc08i02"),
+            vec!["nvme"]
+        );
+
+        // and nothing claims this made-up device
+        assert!(aliases
+            .modules("pci:v0000FFFFd0000FFFFsv00000000sd00000000bcFFscFFi00")
+            .is_empty());
+    }
+}
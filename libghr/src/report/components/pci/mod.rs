@@ -8,11 +8,160 @@ pub(crate) mod linux;
 #[cfg(target_os = "windows")]
 mod windows;
 
+/// A description of a PCI (or PCIe) function.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct PciDescription {
+    /// The function's address, in `domain:bus:device.function` ("BDF") form,
+    /// like `0000:00:14.0`.
+    pub address: Option<String>,
+
+    /// The driver bound to this function.
+    pub driver: Option<String>,
+
+    /// The kernel modules that can drive this function, found using its
+    /// `modalias`.
+    pub modules: Option<Vec<String>>,
+
+    /// Info about the function's PCIe link.
+    pub link: Option<PciLink>,
+
+    /// The IOMMU group this function is in.
+    ///
+    /// All functions in a group must be passed through to a VM together.
+    pub iommu_group: Option<u32>,
+
+    /// The function's current device power state, like `D0`.
+    pub power_state: Option<PciPowerState>,
+
+    /// Whether the kernel has put the function to sleep while it's unused.
+    pub runtime_status: Option<RuntimeStatus>,
+
+    /// The virtual functions this function can expose, if it supports SR-IOV.
+    pub sriov: Option<SrIov>,
+
+    /// The function's base address registers (BARs).
+    pub bars: Option<Vec<PciBar>>,
+
+    /// How many errors the function has reported through Advanced Error
+    /// Reporting (AER) since boot.
+    pub aer: Option<AerCounters>,
+}
+
+/// The state of a PCIe link.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct PciLink {
+    /// The link's current speed, like `16.0 GT/s PCIe`.
+    pub speed: Option<String>,
+
+    /// The link's current width, in lanes.
+    pub width: Option<u32>,
+
+    /// The fastest speed the link supports.
+    pub max_speed: Option<String>,
+
+    /// The most lanes the link supports.
+    pub max_width: Option<u32>,
+}
+
+/// A PCI device power state.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum PciPowerState {
+    /// Fully on.
+    D0,
+    D1,
+    D2,
+    /// Off, but still powered.
+    D3Hot,
+    /// Off, without power.
+    D3Cold,
+}
+
+/// The runtime power management status of a device.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum RuntimeStatus {
+    /// The device is awake.
+    Active,
+    /// The device is asleep.
+    Suspended,
+    /// The device is going to sleep.
+    Suspending,
+    /// The device is waking up.
+    Resuming,
+    /// Runtime power management failed for this device.
+    Error,
+    /// The device doesn't support runtime power management.
+    Unsupported,
+}
+
+/// Single Root I/O Virtualization (SR-IOV) info for a physical function.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct SrIov {
+    /// The most virtual functions the device supports.
+    pub total_vfs: u32,
+
+    /// How many virtual functions are currently enabled.
+    pub enabled_vfs: u32,
+}
+
+/// A base address register (BAR) of a PCI function.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct PciBar {
+    /// The BAR's number, from 0 to 5.
+    pub index: u8,
+
+    /// The size of the region, in bytes.
+    pub size: u64,
+
+    /// Whether the region is memory or I/O ports.
+    pub kind: PciBarKind,
+
+    /// Whether the region can be prefetched.
+    pub prefetchable: bool,
+
+    /// Whether the region can be placed above 4 GiB.
+    pub is_64_bit: bool,
+}
+
+/// What kind of space a BAR maps.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+pub enum PciBarKind {
+    Memory,
+    Io,
+}
+
+/// Advanced Error Reporting (AER) counters for a PCIe function.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct AerCounters {
+    /// Errors that the hardware recovered from on its own.
+    pub correctable: u64,
+
+    /// Uncorrectable errors that didn't take down the link.
+    pub nonfatal: u64,
+
+    /// Uncorrectable errors that made the link unreliable.
+    pub fatal: u64,
+}
+
 #[tracing::instrument]
 pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
+    get_with(&super::Context::load().await).await
+}
+
+/// does the same as [`get`], looking devices up in the report's shared
+/// `context`.
+#[tracing::instrument(skip(context))]
+#[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+pub(crate) async fn get_with(context: &super::Context) -> GhrResult<Vec<ComponentInfo>> {
     #[cfg(target_os = "windows")]
     return windows::get().await;
 
     #[cfg(target_os = "linux")]
-    return linux::get().await;
+    return linux::get(context).await;
 }
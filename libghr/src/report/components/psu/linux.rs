@@ -1,10 +1,13 @@
 use std::path::{Path, PathBuf};
 
-use crate::{prelude::internal::*, report::components};
+use crate::{
+    prelude::internal::*,
+    report::components::{self, Context},
+};
 
 /// Grabs info about all system power supplies.
-#[tracing::instrument]
-pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
+#[tracing::instrument(skip(context))]
+pub async fn get(context: &Context) -> GhrResult<Vec<ComponentInfo>> {
     // the path to all linux power supplies is located at `/sys/class/power_supply`.
     let power_supply_path = PathBuf::from("/sys/class/power_supply");
 
@@ -13,7 +16,7 @@ pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
         // grab the component's path
         let path = dev.path();
 
-        if let Some(unit) = one(path).await {
+        if let Some(unit) = one(path, context).await {
             psus.push(unit)
         }
     }
@@ -22,8 +25,11 @@ pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
 }
 
 /// returns a representation of a component at `path`, if one exists.
-#[tracing::instrument]
-async fn one<P: AsRef<Path> + std::fmt::Debug>(path: P) -> Option<ComponentInfo> {
+#[tracing::instrument(skip(context))]
+async fn one<P: AsRef<Path> + std::fmt::Debug>(
+    path: P,
+    context: &Context,
+) -> Option<ComponentInfo> {
     // get a reference to the given path
     let path = path.as_ref();
 
@@ -35,31 +41,89 @@ async fn one<P: AsRef<Path> + std::fmt::Debug>(path: P) -> Option<ComponentInfo>
     };
 
     // get extra info depending on the type of supply
-    let psu_info = if let Some(kind) = kind {
-        psu_info(&kind, path)
-            .await
-            .map(ComponentDescription::PowerSupplyDescription)
-            .unwrap_or(ComponentDescription::None)
-    } else {
-        ComponentDescription::None
+    let psu_info = match kind {
+        Some(kind) => psu_info(&kind, path).await,
+        None => None,
+    };
+
+    // batteries are judged on their wear, too
+    let health_percent = match psu_info {
+        Some(PowerSupplyDescription::Battery { health_percent, .. }) => health_percent,
+        _ => None,
     };
+    let status = status(path, health_percent, context).await;
+
+    let psu_info = psu_info
+        .map(ComponentDescription::PowerSupplyDescription)
+        .unwrap_or(ComponentDescription::None);
 
     Some(ComponentInfo {
         bus: ComponentBus::Sys,
         id,
         class: None,
         vendor_id,
-        status: None,
+        status,
         desc: psu_info,
     })
 }
 
+/// checks the supply's own idea of its health, and how worn out it is.
+///
+/// `health_percent` is how much of its design capacity a battery can still
+/// hold.
+#[tracing::instrument(skip(context))]
+async fn status(
+    path: &Path,
+    health_percent: Option<f64>,
+    context: &Context,
+) -> Option<ComponentStatus> {
+    let (mut status, health) = futures::join! {
+        components::linux::status(path, context),
+        sysfs_value_opt::<String>(path.join("health")),
+    };
+
+    // supplies are class devices, and many (like acpi batteries) don't say
+    // which driver is behind them. that's not a problem worth flagging
+    if status.driver_bound == Some(false) {
+        status.driver_bound = None;
+    }
+
+    match health.as_deref() {
+        None | Some("Unknown") => (),
+        Some("Good") => status.health = Health::Ok,
+        Some(
+            bad @ ("Dead"
+            | "Unspecified failure"
+            | "Over voltage"
+            | "Over current"
+            | "Overheat"
+            | "Hot"),
+        ) => status.flag(Health::Critical, format!("The supply reports \"{bad}\".")),
+        Some(other) => status.flag(Health::Warning, format!("The supply reports \"{other}\".")),
+    }
+
+    flag_wear(&mut status, health_percent);
+    status.assess()
+}
+
+/// flags batteries that hold less than half their design capacity, since
+/// they're worn out.
+fn flag_wear(status: &mut ComponentStatus, health_percent: Option<f64>) {
+    if health_percent.is_some_and(|percent| percent < 50.0) {
+        status.flag(
+            Health::Warning,
+            "The battery holds less than half its design capacity.",
+        );
+    }
+}
+
 #[tracing::instrument]
 /// matches on the type of power supply this device is and runs the appropriate
 /// function
 async fn psu_info(kind: &str, path: &Path) -> Option<PowerSupplyDescription> {
     match kind {
         "Battery" => battery_info(path).await,
+        "Mains" | "USB" => ac_info(path).await,
         _ => None,
     }
 }
@@ -77,15 +141,17 @@ async fn battery_info(path: &Path) -> Option<PowerSupplyDescription> {
         charge_full_uah,
         voltage_max_uv,
         voltage_max_design_uv,
+        voltage_min_design_uv,
     ) = futures::join! {
         sysfs_value_opt::<i32>(path.join("cycle_count")),
         sysfs_value_opt::<String>(path.join("technology")),
         sysfs_value_opt::<u64>(path.join("energy_full_design")),
         sysfs_value_opt::<u64>(path.join("energy_full")),
-        sysfs_value_opt::<u64>(path.join("charge_full")),
         sysfs_value_opt::<u64>(path.join("charge_full_design")),
+        sysfs_value_opt::<u64>(path.join("charge_full")),
         sysfs_value_opt::<u64>(path.join("voltage_max")),
         sysfs_value_opt::<u64>(path.join("voltage_max_design")),
+        sysfs_value_opt::<u64>(path.join("voltage_min_design")),
     };
 
     // change technology: `Some("Unknown")` to `None`
@@ -94,12 +160,15 @@ async fn battery_info(path: &Path) -> Option<PowerSupplyDescription> {
     // this lambda calculates the 'actual' battery capacity in wh.
     //
     // we'd prefer reading the `energy` value directly, but when it's not
-    // present, we'll estimate based on the uv + uah values
-    let calc_cap = |energy_uwh, charge_uah, voltage_uv| {
+    // present, we'll estimate based on the uv + uah values. many batteries
+    // only have their nominal voltage, in `voltage_min_design`
+    let calc_cap = |energy_uwh, charge_uah, voltage_uv: Option<u64>| {
         if let Some(known_uwh) = energy_uwh {
             Some(uwh_to_wh(known_uwh))
-        } else if let (Some(known_charge_uah), Some(known_voltage_uv)) = (charge_uah, voltage_uv) {
-            Some(uwh_to_wh(known_charge_uah * known_voltage_uv))
+        } else if let (Some(known_charge_uah), Some(known_voltage_uv)) =
+            (charge_uah, voltage_uv.or(voltage_min_design_uv))
+        {
+            Some(uah_uv_to_wh(known_charge_uah, known_voltage_uv))
         } else {
             None
         }
@@ -113,20 +182,167 @@ async fn battery_info(path: &Path) -> Option<PowerSupplyDescription> {
         voltage_max_design_uv,
     );
 
+    let (charge_percent, status, power_w, time_to_empty_s) = charge_state(path).await;
+
+    let (year, month, day, serial, charge_start_threshold, charge_end_threshold) = futures::join! {
+        sysfs_value_opt::<u16>(path.join("manufacture_year")),
+        sysfs_value_opt::<u8>(path.join("manufacture_month")),
+        sysfs_value_opt::<u8>(path.join("manufacture_day")),
+        sysfs_value_opt::<String>(path.join("serial_number")),
+        sysfs_value_opt::<u8>(path.join("charge_control_start_threshold")),
+        sysfs_value_opt::<u8>(path.join("charge_control_end_threshold")),
+    };
+
+    // some batteries only know the year
+    let manufacture_date = match (year, month, day) {
+        (Some(year), Some(month), Some(day)) => {
+            chrono::NaiveDate::from_ymd_opt(year.into(), month.into(), day.into())
+        }
+        _ => None,
+    };
+
     Some(PowerSupplyDescription::Battery {
         technology,
         real_capacity_wh,
         theoretical_capacity_wh,
         cycle_count,
+        health_percent: super::health_percent(real_capacity_wh, theoretical_capacity_wh),
+        charge_percent,
+        status,
+        power_w,
+        time_to_empty_s,
+        manufacture_date,
+        manufacture_year: year,
+        serial: serial.filter(|s| !s.is_empty()).map(Sensitive::new),
+        charge_start_threshold,
+        charge_end_threshold,
+    })
+}
+
+#[tracing::instrument]
+/// finds how full a battery is, what it's doing, and how long it'll last.
+///
+/// like the capacity, batteries report either energy (`energy_now`,
+/// `power_now`) or charge (`charge_now`, `current_now`) values.
+async fn charge_state(
+    path: &Path,
+) -> (Option<f64>, Option<BatteryStatus>, Option<f64>, Option<u64>) {
+    let (capacity, status, time_to_empty) = futures::join! {
+        sysfs_value_opt::<f64>(path.join("capacity")),
+        sysfs_value_opt::<String>(path.join("status")),
+        sysfs_value_opt::<u64>(path.join("time_to_empty_now")),
+    };
+    let (energy_now_uwh, energy_full_uwh, power_now_uw) = futures::join! {
+        sysfs_value_opt::<u64>(path.join("energy_now")),
+        sysfs_value_opt::<u64>(path.join("energy_full")),
+        sysfs_value_opt::<i64>(path.join("power_now")),
+    };
+    let (charge_now_uah, charge_full_uah, current_now_ua, voltage_now_uv) = futures::join! {
+        sysfs_value_opt::<u64>(path.join("charge_now")),
+        sysfs_value_opt::<u64>(path.join("charge_full")),
+        sysfs_value_opt::<i64>(path.join("current_now")),
+        sysfs_value_opt::<u64>(path.join("voltage_now")),
+    };
+
+    let status = status.and_then(|status| match status.as_str() {
+        "Charging" => Some(BatteryStatus::Charging),
+        "Discharging" => Some(BatteryStatus::Discharging),
+        "Not charging" => Some(BatteryStatus::NotCharging),
+        "Full" => Some(BatteryStatus::Full),
+        other => {
+            tracing::debug!("Unknown battery status: `{other}`");
+            None
+        }
+    });
+
+    // prefer the driver's own percentage
+    let charge_percent = capacity.or_else(|| match (energy_now_uwh, energy_full_uwh) {
+        (Some(now), Some(full)) if full > 0 => Some(now as f64 / full as f64 * 100.0),
+        _ => match (charge_now_uah, charge_full_uah) {
+            (Some(now), Some(full)) if full > 0 => Some(now as f64 / full as f64 * 100.0),
+            _ => None,
+        },
+    });
+
+    // some drivers report a negative draw while discharging
+    let power_w = power_now_uw
+        .map(|uw| micro_to_base(uw.unsigned_abs()))
+        .or_else(|| {
+            let (ua, uv) = current_now_ua.zip(voltage_now_uv)?;
+            Some(uah_uv_to_wh(ua.unsigned_abs(), uv))
+        });
+
+    // when the driver doesn't estimate the time left, we'll do it ourselves
+    let energy_now_wh = energy_now_uwh
+        .map(uwh_to_wh)
+        .or_else(|| Some(uah_uv_to_wh(charge_now_uah?, voltage_now_uv?)));
+    let time_to_empty_s = match (status, time_to_empty) {
+        (Some(BatteryStatus::Discharging), Some(secs)) => Some(secs),
+        (Some(BatteryStatus::Discharging), None) => energy_now_wh
+            .zip(power_w.filter(|w| *w > 0.0))
+            .map(|(wh, w)| (wh / w * 3600.0) as u64),
+        _ => None,
+    };
+
+    (charge_percent, status, power_w, time_to_empty_s)
+}
+
+#[tracing::instrument]
+/// finds info about an ac adapter or usb charger.
+///
+/// usb-c chargers (like `ucsi-source-psy-*`) report their negotiated
+/// contract, while plain adapters often only say whether they're plugged in.
+async fn ac_info(path: &Path) -> Option<PowerSupplyDescription> {
+    let (online, usb_type, voltage_now_uv, voltage_max_uv, current_max_ua) = futures::join! {
+        sysfs_value_opt::<u8>(path.join("online")),
+        sysfs_value_opt::<String>(path.join("usb_type")),
+        sysfs_value_opt::<u64>(path.join("voltage_now")),
+        sysfs_value_opt::<u64>(path.join("voltage_max")),
+        sysfs_value_opt::<u64>(path.join("current_max")),
+    };
+
+    // the usb type is a list with the active one in brackets, like
+    // `C PD [PD_PPS]`
+    let usb_type = usb_type.as_deref().and_then(current_choice);
+
+    // prefer the voltage in use right now. zero means "nothing negotiated"
+    let voltage = voltage_now_uv
+        .or(voltage_max_uv)
+        .filter(|uv| *uv > 0)
+        .map(micro_to_base);
+    let max_current = current_max_ua.filter(|ua| *ua > 0).map(micro_to_base);
+    let max_power = voltage.zip(max_current).map(|(v, a)| v * a);
+
+    Some(PowerSupplyDescription::Ac {
+        online: online.map(|online| online != 0),
+        usb_type,
+        voltage,
+        max_current,
+        max_power,
     })
 }
 
+#[tracing::instrument]
+/// converts a micro-unit (like microvolts) to its base unit (like volts)
+fn micro_to_base(micro: u64) -> f64 {
+    (micro as f64) / 1_000_000_f64
+}
+
 #[tracing::instrument]
 /// converts microwatt-hours to watt-hours
 fn uwh_to_wh(uwh: u64) -> f64 {
     (uwh as f64) / 1_000_000_f64
 }
 
+#[tracing::instrument]
+/// multiplies microamp-hours by microvolts, giving watt-hours.
+///
+/// the product is in picowatt-hours. microamps and microvolts give
+/// picowatts the same way, so this converts power to watts, too.
+fn uah_uv_to_wh(uah: u64, uv: u64) -> f64 {
+    (uah as f64) * (uv as f64) / 1_000_000_000_000_f64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,7 +350,7 @@ mod tests {
     #[tokio::test]
     async fn psu_linux_info() {
         let path = psu_linux_path().join("BAT1");
-        let psu = one(path).await.unwrap();
+        let psu = one(path, &Context::default()).await.unwrap();
 
         // check vendor
         assert_eq!(psu.vendor_id.unwrap(), "SMP");
@@ -146,10 +362,38 @@ mod tests {
         assert!(psu.class.is_none());
     }
 
+    #[tokio::test]
+    async fn psu_linux_status() {
+        let path = psu_linux_path().join("BAT1");
+        let status = one(path, &Context::default())
+            .await
+            .unwrap()
+            .status
+            .unwrap();
+
+        // the sample battery says it's fine, and it's barely worn
+        assert_eq!(status.health, Health::Ok);
+        assert!(status.reasons.is_none());
+        assert!(status.driver_bound.is_none());
+    }
+
+    #[test]
+    fn psu_linux_wear() {
+        // batteries that only report charge are judged the same way
+        let mut worn = ComponentStatus::default();
+        flag_wear(&mut worn, Some(45.0));
+        assert_eq!(worn.health, Health::Warning);
+
+        let mut fine = ComponentStatus::default();
+        flag_wear(&mut fine, Some(90.0));
+        flag_wear(&mut fine, None);
+        assert!(fine.reasons.is_none());
+    }
+
     #[tokio::test]
     async fn psu_linux_specs() {
         let path = psu_linux_path().join("BAT1");
-        let psu = one(path).await.unwrap();
+        let psu = one(path, &Context::default()).await.unwrap();
 
         let ComponentDescription::PowerSupplyDescription(psu_info) = psu.desc else {
             panic!("no psu info found D:");
@@ -160,6 +404,16 @@ mod tests {
             real_capacity_wh,
             theoretical_capacity_wh,
             cycle_count,
+            health_percent,
+            charge_percent,
+            status,
+            power_w,
+            time_to_empty_s,
+            manufacture_date,
+            manufacture_year,
+            serial,
+            charge_start_threshold,
+            charge_end_threshold,
         } = psu_info
         else {
             panic!("wasn't considered a battery");
@@ -176,6 +430,104 @@ mod tests {
 
         // cycle count
         assert_eq!(cycle_count.unwrap(), 37);
+
+        // health
+        assert!(almost::equal(
+            health_percent.unwrap(),
+            52.22 / 56.31 * 100.0
+        ));
+        assert_eq!(
+            manufacture_date,
+            chrono::NaiveDate::from_ymd_opt(2023, 4, 12)
+        );
+        assert_eq!(manufacture_year, Some(2023));
+        assert_eq!(serial, Some(Sensitive::new("0421")));
+
+        // it's plugged in, but held at its charge limit
+        assert!(almost::equal(charge_percent.unwrap(), 80.0));
+        assert_eq!(status, Some(BatteryStatus::NotCharging));
+        assert!(almost::equal(power_w.unwrap(), 0.0));
+        assert!(time_to_empty_s.is_none());
+        assert_eq!(charge_start_threshold, Some(75));
+        assert_eq!(charge_end_threshold, Some(80));
+    }
+
+    #[tokio::test]
+    async fn psu_linux_charge_based() {
+        let path = psu_linux_path().join("CMB0");
+        let psu = one(path, &Context::default()).await.unwrap();
+
+        let ComponentDescription::PowerSupplyDescription(PowerSupplyDescription::Battery {
+            real_capacity_wh,
+            theoretical_capacity_wh,
+            health_percent,
+            charge_percent,
+            status,
+            power_w,
+            time_to_empty_s,
+            manufacture_date,
+            manufacture_year,
+            ..
+        }) = psu.desc
+        else {
+            panic!("wasn't considered a battery");
+        };
+
+        // this battery only reports charge, so its energy comes from its
+        // nominal voltage: 4.5 Ah at 11.55 V, out of 5 Ah
+        assert!(almost::equal(real_capacity_wh.unwrap(), 51.975));
+        assert!(almost::equal(theoretical_capacity_wh.unwrap(), 57.75));
+        assert!(almost::equal(health_percent.unwrap(), 90.0));
+
+        // 2.25 Ah at 11 V lasts 2.25 hours at 11 W
+        assert!(almost::equal(charge_percent.unwrap(), 50.0));
+        assert_eq!(status, Some(BatteryStatus::Discharging));
+        assert!(almost::equal(power_w.unwrap(), 11.0));
+        assert_eq!(time_to_empty_s, Some(8100));
+        assert_eq!(manufacture_date, None);
+        assert_eq!(manufacture_year, Some(2021));
+    }
+
+    #[tokio::test]
+    async fn psu_linux_usb_charger() {
+        let path = psu_linux_path().join("ucsi-source-psy-USBC000:001");
+        let psu = one(path, &Context::default()).await.unwrap();
+
+        let ComponentDescription::PowerSupplyDescription(PowerSupplyDescription::Ac {
+            online,
+            usb_type,
+            voltage,
+            max_current,
+            max_power,
+        }) = psu.desc
+        else {
+            panic!("wasn't considered an ac adapter");
+        };
+
+        // a 20 volt, 3.25 amp (65 watt) contract
+        assert_eq!(online, Some(true));
+        assert_eq!(usb_type.unwrap(), "PD");
+        assert!(almost::equal(voltage.unwrap(), 20.0));
+        assert!(almost::equal(max_current.unwrap(), 3.25));
+        assert!(almost::equal(max_power.unwrap(), 65.0));
+    }
+
+    #[tokio::test]
+    async fn psu_linux_mains() {
+        let path = psu_linux_path().join("ADP1");
+        let psu = one(path, &Context::default()).await.unwrap();
+
+        // plain adapters don't say much
+        assert_eq!(
+            psu.desc,
+            ComponentDescription::PowerSupplyDescription(PowerSupplyDescription::Ac {
+                online: Some(true),
+                usb_type: None,
+                voltage: None,
+                max_current: None,
+                max_power: None,
+            })
+        );
     }
 
     #[test]
@@ -184,6 +536,9 @@ mod tests {
         let uwh = 99_000_000;
 
         assert!(almost::equal(uwh_to_wh(uwh), wh));
+
+        // 5 Ah at 11.55 V
+        assert!(almost::equal(uah_uv_to_wh(5_000_000, 11_550_000), 57.75));
     }
 
     #[tracing::instrument]
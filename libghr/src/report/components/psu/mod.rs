@@ -28,15 +28,112 @@ pub enum PowerSupplyDescription {
         ///
         /// seems like this value can be negative, so using an i32 for now.
         cycle_count: Option<i32>,
+
+        /// how much of its design capacity the battery can still hold, as a
+        /// percentage.
+        ///
+        /// batteries are usually replaced below 80%. some report over 100%
+        /// when they're new.
+        health_percent: Option<f64>,
+
+        /// how full the battery is right now, as a percentage
+        charge_percent: Option<f64>,
+
+        /// whether the battery is charging
+        status: Option<BatteryStatus>,
+
+        /// how much power the battery is putting out (or taking in), in watts
+        power_w: Option<f64>,
+
+        /// how long the battery should last at the current draw, in seconds.
+        ///
+        /// this is only known while discharging.
+        time_to_empty_s: Option<u64>,
+
+        /// when the battery was made
+        manufacture_date: Option<chrono::NaiveDate>,
+
+        /// the year the battery was made.
+        ///
+        /// some batteries only know this, not the full date.
+        manufacture_year: Option<u16>,
+
+        /// the battery's serial number
+        serial: Option<Sensitive>,
+
+        /// the charge percentage the battery starts charging below, if the
+        /// firmware limits charging to preserve the battery
+        charge_start_threshold: Option<u8>,
+
+        /// the charge percentage the battery stops charging at
+        charge_end_threshold: Option<u8>,
     },
 
-    Ac {},
+    Ac {
+        /// whether the adapter is plugged in
+        online: Option<bool>,
+
+        /// the usb charging protocol in use, like `PD` or `PD_PPS`.
+        ///
+        /// this is only known for usb chargers.
+        usb_type: Option<String>,
+
+        /// the negotiated charging voltage, in volts
+        voltage: Option<f64>,
+
+        /// the most current the charger offers at that voltage, in amps
+        max_current: Option<f64>,
+
+        /// the negotiated charger wattage, in watts
+        max_power: Option<f64>,
+    },
+}
+
+/// What a battery is doing.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    TypeScript,
+)]
+#[non_exhaustive]
+pub enum BatteryStatus {
+    Charging,
+    Discharging,
+    /// The battery is plugged in, but isn't charging, like when it's held
+    /// below a charge threshold.
+    NotCharging,
+    Full,
+}
+
+/// finds how much of its design capacity a battery can still hold.
+#[tracing::instrument]
+fn health_percent(
+    real_capacity_wh: Option<f64>,
+    theoretical_capacity_wh: Option<f64>,
+) -> Option<f64> {
+    let (real, theoretical) = real_capacity_wh.zip(theoretical_capacity_wh)?;
+    (theoretical > 0.0).then(|| real / theoretical * 100.0)
 }
 
 #[tracing::instrument]
 pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
+    get_with(&super::Context::load().await).await
+}
+
+/// does the same as [`get`], looking devices up in the report's shared
+/// `context`.
+#[tracing::instrument(skip(context))]
+#[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+pub(crate) async fn get_with(context: &super::Context) -> GhrResult<Vec<ComponentInfo>> {
     #[cfg(target_os = "linux")]
-    return linux::get().await;
+    return linux::get(context).await;
 
     #[cfg(target_os = "windows")]
     return windows::get().await;
@@ -64,6 +64,32 @@ async fn one(fields: HashMap<String, Variant>) -> ComponentInfo {
             }
         });
 
+    // `BatteryStatus` mixes in how full the battery is
+    let status = fields
+        .get("BatteryStatus")
+        .u32_from_variant()
+        .and_then(|status| match status {
+            1 | 4 | 5 => Some(BatteryStatus::Discharging),
+            2 | 11 => Some(BatteryStatus::NotCharging),
+            3 => Some(BatteryStatus::Full),
+            6..=9 => Some(BatteryStatus::Charging),
+            _ => None,
+        });
+
+    let charge_percent = fields
+        .get("EstimatedChargeRemaining")
+        .u32_from_variant()
+        .map(f64::from);
+
+    // the run time is in minutes. while plugged in, it's a huge sentinel
+    // value instead
+    let time_to_empty_s = fields
+        .get("EstimatedRunTime")
+        .u32_from_variant()
+        .filter(|_| status == Some(BatteryStatus::Discharging))
+        .filter(|minutes| *minutes != 71_582_788)
+        .map(|minutes| u64::from(minutes) * 60);
+
     // make the device
     ComponentInfo {
         bus: ComponentBus::Unknown,
@@ -76,6 +102,18 @@ async fn one(fields: HashMap<String, Variant>) -> ComponentInfo {
             real_capacity_wh,
             theoretical_capacity_wh,
             cycle_count: None,
+            health_percent: super::health_percent(real_capacity_wh, theoretical_capacity_wh),
+            charge_percent,
+            status,
+            // TODO: `BatteryStatus` and `BatteryFullChargedCapacity` in the
+            // `root\wmi` namespace have the rest
+            power_w: None,
+            time_to_empty_s,
+            manufacture_date: None,
+            manufacture_year: None,
+            serial: None,
+            charge_start_threshold: None,
+            charge_end_threshold: None,
         }),
     }
 }
@@ -103,6 +141,11 @@ mod tests {
             real_capacity_wh,
             theoretical_capacity_wh,
             cycle_count,
+            health_percent,
+            charge_percent,
+            status,
+            time_to_empty_s,
+            ..
         }) = cmp.desc()
         else {
             panic!("wrong one!");
@@ -113,6 +156,12 @@ mod tests {
         assert!(almost::equal(real_capacity_wh.unwrap(), 49.5));
         assert!(almost::equal(theoretical_capacity_wh.unwrap(), 50.0));
         assert!(cycle_count.is_none()); // windows doesn't seem to support this
+
+        // and its current state
+        assert!(almost::equal(health_percent.unwrap(), 99.0));
+        assert!(almost::equal(charge_percent.unwrap(), 67.0));
+        assert_eq!(status, Some(BatteryStatus::Discharging));
+        assert_eq!(time_to_empty_s, Some(180 * 60));
     }
 
     #[tracing::instrument]
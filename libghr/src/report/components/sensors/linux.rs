@@ -0,0 +1,399 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{prelude::internal::*, report::components};
+
+/// where the kernel lists hardware monitoring chips
+pub(crate) const HWMON_PATH: &str = "/sys/class/hwmon";
+
+/// where the kernel lists thermal zones and cooling devices
+pub(crate) const THERMAL_PATH: &str = "/sys/class/thermal";
+
+/// the names of hwmon chips that report on the processor
+const CPU_CHIPS: &[&str] = &["coretemp", "k10temp", "zenpower", "cpu_thermal"];
+
+/// the thermal zone type for the processor package on intel
+const CPU_ZONE: &str = "x86_pkg_temp";
+
+/// finds the sensors watching each device, keyed by the device's real path
+/// (like `/sys/devices/pci0000:00/0000:00:01.1/0000:01:00.0`).
+///
+/// hwmon chips (and some thermal zones) have a `device` link pointing to the
+/// device they're watching. resolving each link once here lets every device
+/// just look itself up.
+#[tracing::instrument]
+pub(crate) async fn by_owner(
+    hwmon_path: impl AsRef<Path> + std::fmt::Debug,
+    thermal_path: impl AsRef<Path> + std::fmt::Debug,
+) -> BTreeMap<PathBuf, Vec<Sensor>> {
+    let mut sensors = BTreeMap::<PathBuf, Vec<Sensor>>::new();
+    for chip in chips(hwmon_path).await {
+        if let Some(owner) = owner(&chip).await {
+            sensors
+                .entry(owner)
+                .or_default()
+                .extend(chip_sensors(&chip).await);
+        }
+    }
+    for zone in numbered_entries(thermal_path, "thermal_zone").await {
+        if let Some(owner) = owner(&zone).await {
+            sensors
+                .entry(owner)
+                .or_default()
+                .extend(zone_sensor(&zone).await);
+        }
+    }
+
+    sensors.retain(|_, sensors| !sensors.is_empty());
+    sensors
+}
+
+/// finds the sensors for the processor in socket `index`.
+///
+/// processor sensors usually aren't linked to anything we can find from the
+/// cpu itself, so we look for them by name. when there's more than one
+/// socket, each gets the chip (and zone) in the same position.
+#[tracing::instrument]
+pub(crate) async fn cpu_sensors(index: usize, sockets: usize) -> Option<Vec<Sensor>> {
+    cpu_sensors_in(HWMON_PATH, THERMAL_PATH, index, sockets).await
+}
+
+/// finds the processor's sensors using the given class directories.
+#[tracing::instrument]
+async fn cpu_sensors_in(
+    hwmon_path: impl AsRef<Path> + std::fmt::Debug,
+    thermal_path: impl AsRef<Path> + std::fmt::Debug,
+    index: usize,
+    sockets: usize,
+) -> Option<Vec<Sensor>> {
+    // find the chips and zones, keeping them in a stable order
+    let mut cpu_chips = Vec::new();
+    for chip in chips(hwmon_path).await {
+        let name = sysfs_value_opt::<String>(chip.join("name")).await;
+        if name.is_some_and(|name| CPU_CHIPS.contains(&name.as_str())) {
+            cpu_chips.push((owner(&chip).await, chip));
+        }
+    }
+    cpu_chips.sort();
+
+    let mut cpu_zones = Vec::new();
+    for zone in numbered_entries(thermal_path, "thermal_zone").await {
+        let kind = sysfs_value_opt::<String>(zone.join("type")).await;
+        if kind.as_deref() == Some(CPU_ZONE) {
+            cpu_zones.push(zone);
+        }
+    }
+
+    let mut sensors = Vec::new();
+    for (i, (_, chip)) in cpu_chips.iter().enumerate() {
+        if sockets <= 1 || i == index {
+            sensors.extend(chip_sensors(chip).await);
+        }
+    }
+    for (i, zone) in cpu_zones.iter().enumerate() {
+        if sockets <= 1 || i == index {
+            sensors.extend(zone_sensor(zone).await);
+        }
+    }
+
+    (!sensors.is_empty()).then_some(sensors)
+}
+
+/// finds sensors that don't belong to any one component, like fans on the
+/// motherboard or in a laptop's embedded controller.
+///
+/// these are chips on platform (or similar) devices that aren't watching the
+/// processor. chips without any device just mirror a thermal zone, so they're
+/// skipped.
+#[tracing::instrument]
+pub(crate) async fn board_sensors(
+    hwmon_path: impl AsRef<Path> + std::fmt::Debug,
+) -> Option<Vec<Sensor>> {
+    let mut sensors = Vec::new();
+    for chip in chips(hwmon_path).await {
+        let (name, subsystem) = futures::join! {
+            sysfs_value_opt::<String>(chip.join("name")),
+            async_fs::read_link(chip.join("device/subsystem")),
+        };
+
+        let subsystem = subsystem
+            .ok()
+            .and_then(|s| s.file_name().map(|s| s.to_string_lossy().to_string()));
+        let on_board = matches!(
+            subsystem.as_deref(),
+            Some("platform" | "acpi" | "isa" | "i2c")
+        );
+        let cpu = name.is_some_and(|name| CPU_CHIPS.contains(&name.as_str()));
+
+        if on_board && !cpu {
+            sensors.extend(chip_sensors(&chip).await);
+        }
+    }
+
+    (!sensors.is_empty()).then_some(sensors)
+}
+
+/// lists the hwmon chips, like `/sys/class/hwmon/hwmon0`.
+#[tracing::instrument]
+async fn chips(hwmon_path: impl AsRef<Path> + std::fmt::Debug) -> Vec<PathBuf> {
+    let Ok(entries) = components::linux::devices(hwmon_path).await else {
+        return Vec::new();
+    };
+
+    let mut chips = entries.iter().map(|e| e.path()).collect::<Vec<_>>();
+    chips.sort();
+    chips
+}
+
+/// finds the real path of the device that a chip or zone is watching.
+#[tracing::instrument]
+async fn owner(path: &Path) -> Option<PathBuf> {
+    async_fs::canonicalize(path.join("device")).await.ok()
+}
+
+/// reads every sensor on a hwmon chip.
+///
+/// each sensor is a set of files like `temp1_input`, `temp1_label`, and
+/// `temp1_crit`.
+#[tracing::instrument]
+async fn chip_sensors(chip: &Path) -> Vec<Sensor> {
+    let name = sysfs_value_opt::<String>(chip.join("name")).await;
+
+    // find each sensor's prefix, like `temp1`
+    let mut prefixes = Vec::new();
+    if let Ok(entries) = components::linux::devices(chip).await {
+        for entry in entries {
+            let file = entry.file_name().to_string_lossy().to_string();
+            let Some((prefix, "input" | "average")) = file.split_once('_') else {
+                continue;
+            };
+
+            if let Some(kind) = sensor_kind(prefix) {
+                let number = prefix.trim_start_matches(char::is_alphabetic);
+                prefixes.push((
+                    kind,
+                    number.parse::<u32>().unwrap_or_default(),
+                    prefix.to_string(),
+                ));
+            }
+        }
+    }
+    prefixes.sort();
+    prefixes.dedup();
+
+    let mut sensors = Vec::new();
+    for (kind, _, prefix) in prefixes {
+        let read = |suffix: &str| {
+            let path = chip.join(format!("{prefix}_{suffix}"));
+            async move {
+                sysfs_value_opt::<f64>(path)
+                    .await
+                    .map(|value| value / scale(kind))
+            }
+        };
+
+        let (label, input, average, min, max, cap, crit, alarm) = futures::join! {
+            sysfs_value_opt::<String>(chip.join(format!("{prefix}_label"))),
+            read("input"),
+            read("average"),
+            read("min"),
+            read("max"),
+            read("cap"),
+            read("crit"),
+            sysfs_value_opt::<u8>(chip.join(format!("{prefix}_alarm"))),
+        };
+
+        sensors.push(Sensor {
+            chip: name.clone(),
+            label,
+            kind,
+            value: input.or(average),
+            min,
+            max: max.or(cap),
+            crit,
+            alarm: alarm.map(|alarm| alarm != 0),
+        });
+    }
+
+    sensors
+}
+
+/// finds the kind of sensor from its file prefix, like `temp1`.
+fn sensor_kind(prefix: &str) -> Option<SensorKind> {
+    let kind = prefix.trim_end_matches(|c: char| c.is_ascii_digit());
+    if kind.len() == prefix.len() {
+        return None;
+    }
+
+    match kind {
+        "temp" => Some(SensorKind::Temperature),
+        "fan" => Some(SensorKind::Fan),
+        "in" => Some(SensorKind::Voltage),
+        "curr" => Some(SensorKind::Current),
+        "power" => Some(SensorKind::Power),
+        _ => None,
+    }
+}
+
+/// what to divide hwmon values by to get base units.
+///
+/// hwmon uses millidegrees, millivolts, milliamps, and microwatts.
+fn scale(kind: SensorKind) -> f64 {
+    match kind {
+        SensorKind::Temperature | SensorKind::Voltage | SensorKind::Current => 1_000.0,
+        SensorKind::Power => 1_000_000.0,
+        SensorKind::Fan => 1.0,
+    }
+}
+
+/// reads a thermal zone as a temperature sensor.
+///
+/// its critical trip point (if any) becomes the sensor's `crit`.
+#[tracing::instrument]
+async fn zone_sensor(zone: &Path) -> Option<Sensor> {
+    let (kind, temp) = futures::join! {
+        sysfs_value_opt::<String>(zone.join("type")),
+        sysfs_value_opt::<f64>(zone.join("temp")),
+    };
+
+    let crit = trip_points(zone)
+        .await
+        .into_iter()
+        .find(|trip| trip.kind.as_deref() == Some("critical"))
+        .and_then(|trip| trip.temperature);
+
+    Some(Sensor {
+        chip: kind,
+        label: zone.file_name().map(|n| n.to_string_lossy().to_string()),
+        kind: SensorKind::Temperature,
+        value: temp.map(|t| t / 1_000.0),
+        min: None,
+        max: None,
+        crit,
+        alarm: None,
+    })
+}
+
+/// reads a thermal zone's trip points, like `trip_point_0_temp`.
+#[tracing::instrument]
+pub(crate) async fn trip_points(zone: &Path) -> Vec<TripPoint> {
+    let mut trips = Vec::new();
+    for i in 0.. {
+        let (kind, temp) = futures::join! {
+            sysfs_value_opt::<String>(zone.join(format!("trip_point_{i}_type"))),
+            sysfs_value_opt::<f64>(zone.join(format!("trip_point_{i}_temp"))),
+        };
+
+        if kind.is_none() && temp.is_none() {
+            break;
+        }
+
+        trips.push(TripPoint {
+            kind,
+            // disabled trip points are usually at zero (or below)
+            temperature: temp.filter(|t| *t > 0.0).map(|t| t / 1_000.0),
+        });
+    }
+
+    trips
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn check_nvme_sensors() {
+        let device = async_fs::canonicalize(root().join("sys/class/block/nvme0n1/device"))
+            .await
+            .unwrap();
+        let sensors = by_owner(hwmon_path(), thermal_path()).await;
+        let sensors = &sensors[&device];
+
+        assert_eq!(sensors.len(), 2);
+        let composite = &sensors[0];
+        assert_eq!(composite.chip.as_deref(), Some("nvme"));
+        assert_eq!(composite.label.as_deref(), Some("Composite"));
+        assert_eq!(composite.kind, SensorKind::Temperature);
+        assert_eq!(composite.value, Some(38.85));
+        assert_eq!(composite.crit, Some(84.85));
+        assert_eq!(composite.alarm, Some(false));
+    }
+
+    #[tokio::test]
+    async fn check_gpu_sensors() {
+        let device = async_fs::canonicalize(root().join("sys/class/drm/card1/device"))
+            .await
+            .unwrap();
+        let sensors = by_owner(hwmon_path(), thermal_path()).await;
+        let sensors = &sensors[&device];
+
+        let find = |kind| sensors.iter().find(|s| s.kind == kind).unwrap();
+        assert_eq!(
+            sensors
+                .iter()
+                .filter(|s| s.kind == SensorKind::Temperature)
+                .count(),
+            2
+        );
+        assert_eq!(find(SensorKind::Fan).max, Some(3200.0));
+        assert_eq!(find(SensorKind::Voltage).value, Some(0.806));
+
+        // amdgpu only has an average power, and a cap instead of a max
+        let power = find(SensorKind::Power);
+        assert_eq!(power.value, Some(12.0));
+        assert_eq!(power.max, Some(186.0));
+    }
+
+    #[tokio::test]
+    async fn check_cpu_sensors() {
+        let sensors = cpu_sensors_in(hwmon_path(), thermal_path(), 0, 1)
+            .await
+            .unwrap();
+
+        let labels = sensors
+            .iter()
+            .map(|s| s.label.clone().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(labels, ["Tctl", "Tccd1", "thermal_zone1"]);
+        assert_eq!(sensors[0].value, Some(54.25));
+    }
+
+    #[tokio::test]
+    async fn check_board_sensors() {
+        let sensors = board_sensors(hwmon_path()).await.unwrap();
+
+        // the acpitz mirror and cpu chip aren't included
+        assert_eq!(sensors.len(), 2);
+        assert!(sensors
+            .iter()
+            .all(|s| s.chip.as_deref() == Some("thinkpad") && s.kind == SensorKind::Fan));
+        assert_eq!(sensors[0].value, Some(2900.0));
+    }
+
+    #[test]
+    fn check_sensor_kind() {
+        assert_eq!(sensor_kind("temp1"), Some(SensorKind::Temperature));
+        assert_eq!(sensor_kind("in0"), Some(SensorKind::Voltage));
+        assert_eq!(sensor_kind("power"), None);
+        assert_eq!(sensor_kind("pwm1"), None);
+    }
+
+    #[tracing::instrument]
+    fn root() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs"))
+    }
+
+    #[tracing::instrument]
+    fn hwmon_path() -> PathBuf {
+        root().join("sys/class/hwmon")
+    }
+
+    #[tracing::instrument]
+    fn thermal_path() -> PathBuf {
+        root().join("sys/class/thermal")
+    }
+}
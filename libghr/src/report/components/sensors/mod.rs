@@ -0,0 +1,62 @@
+//! sensors: temperatures, fans, and other readings from hardware monitors
+
+#[cfg(target_os = "linux")]
+pub(crate) mod linux;
+
+use crate::prelude::internal::*;
+
+/// One reading from a hardware sensor.
+///
+/// Temperatures are in degrees Celsius, fans are in RPM, voltages are in
+/// volts, currents are in amps, and power is in watts.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct Sensor {
+    /// The name of the chip (or driver) the reading came from, like
+    /// `k10temp` or `nvme`.
+    pub chip: Option<String>,
+
+    /// What the reading is of, like `Tctl` or `Composite`.
+    pub label: Option<String>,
+
+    /// What kind of reading this is.
+    pub kind: SensorKind,
+
+    /// The value when the report was made.
+    pub value: Option<f64>,
+
+    /// The lowest value the hardware considers normal.
+    pub min: Option<f64>,
+
+    /// The highest value the hardware considers normal.
+    pub max: Option<f64>,
+
+    /// The value where the hardware will take emergency action, like
+    /// shutting down.
+    pub crit: Option<f64>,
+
+    /// Whether the hardware says the reading is out of range.
+    pub alarm: Option<bool>,
+}
+
+/// A kind of sensor reading.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    TypeScript,
+)]
+#[non_exhaustive]
+pub enum SensorKind {
+    Temperature,
+    Fan,
+    Voltage,
+    Current,
+    Power,
+}
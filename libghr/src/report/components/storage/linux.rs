@@ -1,43 +1,83 @@
 use futures::StreamExt as _;
 
-use crate::prelude::internal::*;
+use crate::{
+    prelude::internal::*,
+    report::components::{self, Context},
+};
 use std::{
     fmt::Debug,
     path::{Path, PathBuf},
 };
 
 /// grabs all known linux `block` devices from `sysfs`.
-#[tracing::instrument]
-pub(crate) async fn get() -> GhrResult<Vec<ComponentInfo>> {
+#[tracing::instrument(skip(context))]
+pub(crate) async fn get(context: &Context) -> GhrResult<Vec<ComponentInfo>> {
     // grab storage devices attached to the system
     let devices = storage_device_entries("/sys/class/block").await?;
 
     // for each device, we want to grab its info
     Ok(futures::stream::iter(devices)
-        .filter_map(one)
+        .filter_map(|path| one(path, context))
         .collect::<Vec<_>>()
         .await)
 }
 
 /// finds info for one device.
-#[tracing::instrument]
-async fn one<P: AsRef<Path> + Debug>(path: P) -> Option<ComponentInfo> {
+#[tracing::instrument(skip(context))]
+async fn one<P: AsRef<Path> + Debug>(path: P, context: &Context) -> Option<ComponentInfo> {
     // grab general info
     let (id, vendor_id) = general_info(&path).await;
 
     // and the specialized desc
-    let desc = ComponentDescription::StorageDescription(storage_desc(path).await);
+    let device = path.as_ref().join("device");
+    let (desc, status) = futures::join! {
+        storage_desc(&path),
+        status(&device, context),
+    };
+
+    // use the connector to find which bus the drive is on
+    let bus = desc
+        .connector
+        .as_ref()
+        .map_or(ComponentBus::Unknown, bus_from_connector);
 
     Some(ComponentInfo {
-        bus: ComponentBus::Unknown, // FIXME: convert the desc's if we got it
+        bus,
         id,
         class: None,
         vendor_id,
-        status: None,
-        desc,
+        status,
+        desc: ComponentDescription::StorageDescription(desc),
     })
 }
 
+/// finds the health of the drive whose device is at `device`, like
+/// `/sys/class/block/sda/device`.
+#[tracing::instrument(skip(context))]
+async fn status(device: &Path, context: &Context) -> Option<ComponentStatus> {
+    let (mut status, nvme_firmware, scsi_firmware, io_errors) = futures::join! {
+        components::linux::status(device, context),
+        sysfs_value_opt::<String>(device.join("firmware_rev")),
+        sysfs_value_opt::<String>(device.join("rev")),
+        sysfs_value_opt::<String>(device.join("ioerr_cnt")),
+    };
+
+    // nvme controllers call it `firmware_rev`, while scsi disks say `rev`
+    status.firmware_version = nvme_firmware.or(scsi_firmware);
+
+    // scsi disks count the commands that failed, in hex
+    status.errors =
+        io_errors.and_then(|count| u64::from_str_radix(count.trim_start_matches("0x"), 16).ok());
+    if let Some(errors) = status.errors.filter(|errors| *errors > 0) {
+        status.flag(
+            Health::Warning,
+            format!("The drive reported {errors} I/O errors."),
+        );
+    }
+
+    status.assess()
+}
+
 /// finds general info for the given device.
 ///
 /// currently, the return type is: (id, vendor_id)
@@ -58,12 +98,19 @@ async fn storage_desc<P: AsRef<Path> + Debug>(path: P) -> StorageDescription {
     // grab the real path
     let path = path.as_ref();
 
-    let (kind, capacity, speed, connector, is_removable) = futures::join! {
+    let (kind, capacity, speed, connector, is_removable, queue) = futures::join! {
         kind(path),
         capacity(path),
         sysfs_value_opt::<u32>(path.join("queue/rotation_rate")), // rare?
         connector(path),
         removable(path),
+        queue(path),
+    };
+
+    // the link depends on how the drive is connected
+    let link = match connector {
+        Some(ref connector) => link(path, connector).await,
+        None => None,
     };
 
     // FIXME: `rotation_rate` isn't well-documented online.
@@ -81,51 +128,313 @@ async fn storage_desc<P: AsRef<Path> + Debug>(path: P) -> StorageDescription {
         speed,
         connector,
         is_removable,
+        link,
+        queue,
     }
 }
 
-/// finds the connector for the device at `path`.
+/// finds the connector (transport) for the device at `path`.
 #[tracing::instrument]
 async fn connector<P: AsRef<Path> + Debug>(path: P) -> Option<StorageConnector> {
     // grab the real path
     let path = path.as_ref();
 
-    // follow any symlinks
-    let abs = path.canonicalize().unwrap_or(path.to_path_buf());
+    // nvme controllers tell us their transport directly
+    if let Some(transport) = sysfs_value_opt::<String>(path.join("device/transport")).await {
+        return Some(match transport.as_str() {
+            "pcie" => StorageConnector::Nvme,
+            "tcp" | "rdma" | "fc" => StorageConnector::NvmeOverFabrics,
+            other => StorageConnector::Other(format!("nvme-{other}")),
+        });
+    }
 
-    // we now have a `<sysroot>/sys/class/<connector>` path.
+    // follow any symlinks.
     //
-    // let's try to match a `/class/<connector>` from its components.
-    let mut chunks = futures::stream::iter(abs.components()).chunks(2);
-    while let Some(chunk) = chunks.next().await {
-        let first = chunk.first();
+    // we'll get something like:
+    // `/sys/devices/pci0000:00/0000:00:17.0/ata1/host0/target0:0:0/0:0:0:0/block/sda`
+    let abs = async_fs::canonicalize(path)
+        .await
+        .unwrap_or(path.to_path_buf());
+
+    // look at each parent device, starting from the drive itself, to see
+    // what it's attached to
+    for dir in abs.ancestors() {
+        let Some(name) = dir.file_name().map(|n| n.to_string_lossy().to_string()) else {
+            continue;
+        };
 
-        if first.is_none() {
-            break;
+        // usb interfaces tell us which protocol is used through their driver
+        if is_usb_interface(&name) {
+            return Some(match driver_name(dir).await.as_deref() {
+                Some("uas") => StorageConnector::Uas,
+                _ => StorageConnector::Usb,
+            });
         }
 
-        if let (Some(class), Some(connector)) = (first, chunk.get(1)) {
-            // make sure it's a fr path
-            if class.as_os_str().to_string_lossy() == "class" {
-                // try matching off a connector
-                if let Some(connector) = connector.as_os_str().to_str() {
-                    match connector {
-                        "ata_port" => return Some(StorageConnector::Sata),
-                        "scsi_disk" => return Some(StorageConnector::Scsi),
-                        other => {
-                            tracing::debug!("Failed to map storage connector `{other}`.");
-                        } //
-                          //
-                          // TODO: consider pci? i didn't see it in `/sys/class` though
-                    };
+        // mmc cards (like `mmc0:0001`) report their type
+        if name.starts_with("mmc") && name.contains(':') {
+            return Some(
+                match sysfs_value_opt::<String>(dir.join("type")).await.as_deref() {
+                    Some("SD") => StorageConnector::Sd,
+                    Some(other) if other != "MMC" => StorageConnector::Other(other.to_string()),
+                    _ => StorageConnector::Mmc,
+                },
+            );
+        }
+
+        // libata ports are named `ataN`. only sata links report a speed
+        if is_numbered(&name, "ata") {
+            return Some(if sata_link_speed(dir).await.is_some() {
+                StorageConnector::Sata
+            } else {
+                StorageConnector::Ide
+            });
+        }
+
+        if name.starts_with("end_device-") {
+            return Some(StorageConnector::Sas);
+        }
+
+        if is_numbered(&name, "session") {
+            return Some(StorageConnector::Iscsi);
+        }
+
+        if is_numbered(&name, "virtio") {
+            return Some(StorageConnector::Virtio);
+        }
+
+        if name.starts_with("nvme-subsys") {
+            return Some(StorageConnector::Nvme);
+        }
+
+        if name == "nvme-fabrics" {
+            return Some(StorageConnector::NvmeOverFabrics);
+        }
+    }
+
+    // we don't know anything specific, but anything with a scsi target is
+    // at least scsi
+    if abs.components().any(|c| {
+        c.as_os_str()
+            .to_string_lossy()
+            .strip_prefix("target")
+            .is_some_and(|t| t.contains(':'))
+    }) {
+        return Some(StorageConnector::Scsi);
+    }
+
+    tracing::debug!("Failed to map storage connector for `{abs:?}`.");
+    None
+}
+
+/// finds the negotiated link for the device at `path`.
+///
+/// each connector has its own way of reporting this, so you'll need to pass
+/// the one you found with `connector`.
+#[tracing::instrument]
+async fn link<P: AsRef<Path> + Debug>(
+    path: P,
+    connector: &StorageConnector,
+) -> Option<StorageLink> {
+    let path = path.as_ref();
+    let abs = async_fs::canonicalize(path)
+        .await
+        .unwrap_or(path.to_path_buf());
+
+    for dir in abs.ancestors() {
+        let Some(name) = dir.file_name().map(|n| n.to_string_lossy().to_string()) else {
+            continue;
+        };
+
+        match connector {
+            // sata links are found on the `ataN` port
+            StorageConnector::Sata if is_numbered(&name, "ata") => {
+                return Some(StorageLink {
+                    speed: sata_link_speed(dir).await,
+                    width: None,
+                });
+            }
+
+            // the usb device (like `2-1`) is the parent of its interface
+            // (`2-1:1.0`) and reports its speed in Mbps
+            StorageConnector::Usb | StorageConnector::Uas if is_usb_interface(&name) => {
+                let speed = match dir.parent() {
+                    Some(usb_device) => sysfs_value_opt::<String>(usb_device.join("speed"))
+                        .await
+                        .map(|mbps| format!("{mbps} Mbps")),
+                    None => None,
+                };
+
+                return Some(StorageLink { speed, width: None });
+            }
+
+            // for anything pcie, find the closest pci device with a link
+            StorageConnector::Nvme | StorageConnector::Pcie | StorageConnector::M2 => {
+                let (speed, width) = futures::join! {
+                    sysfs_value_opt::<String>(dir.join("current_link_speed")),
+                    sysfs_value_opt::<u32>(dir.join("current_link_width")),
+                };
+
+                if speed.is_some() || width.is_some() {
+                    return Some(StorageLink { speed, width });
                 }
             }
+
+            _ => (),
         }
     }
 
     None
 }
 
+/// reads the negotiated speed of a sata link from an `ataN` port directory.
+///
+/// these are at `ataN/linkM/ata_link/linkM/sata_spd`.
+#[tracing::instrument]
+async fn sata_link_speed(ata_port: &Path) -> Option<String> {
+    let mut entries = async_fs::read_dir(ata_port).await.ok()?;
+
+    while let Some(Ok(entry)) = entries.next().await {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !is_numbered(&name, "link") {
+            continue;
+        }
+
+        let speed =
+            sysfs_value_opt::<String>(entry.path().join("ata_link").join(&name).join("sata_spd"))
+                .await
+                // links without a device say `<unknown>`
+                .filter(|spd| !spd.starts_with('<'));
+
+        if speed.is_some() {
+            return speed;
+        }
+    }
+
+    None
+}
+
+/// checks if a `sysfs` dir name is a usb interface, like `2-1.4:1.0`.
+#[tracing::instrument]
+fn is_usb_interface(name: &str) -> bool {
+    let Some((device, interface)) = name.split_once(':') else {
+        return false;
+    };
+
+    // devices look like `bus-port(.port)*` and interfaces like `config.interface`
+    let is_device = device.split_once('-').is_some_and(|(bus, ports)| {
+        !bus.is_empty()
+            && bus.chars().all(|c| c.is_ascii_digit())
+            && !ports.is_empty()
+            && ports.chars().all(|c| c.is_ascii_digit() || c == '.')
+    });
+    let is_interface = interface.split_once('.').is_some_and(|(config, num)| {
+        !config.is_empty()
+            && !num.is_empty()
+            && config.chars().all(|c| c.is_ascii_digit())
+            && num.chars().all(|c| c.is_ascii_digit())
+    });
+
+    is_device && is_interface
+}
+
+/// checks if a `sysfs` dir name is `prefix` followed by a number, like `ata1`.
+#[tracing::instrument]
+fn is_numbered(name: &str, prefix: &str) -> bool {
+    name.strip_prefix(prefix)
+        .is_some_and(|num| !num.is_empty() && num.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// maps a storage connector onto the bus it uses.
+#[tracing::instrument]
+fn bus_from_connector(connector: &StorageConnector) -> ComponentBus {
+    match connector {
+        StorageConnector::Ide => ComponentBus::Ide,
+        StorageConnector::Nvme => ComponentBus::Nvme,
+        StorageConnector::Pcie | StorageConnector::M2 => ComponentBus::Pcie,
+        StorageConnector::Scsi | StorageConnector::Sas | StorageConnector::Iscsi => {
+            ComponentBus::Scsi
+        }
+        StorageConnector::Usb | StorageConnector::Uas => ComponentBus::Usb,
+        StorageConnector::Sata => ComponentBus::Other("SATA".into()),
+        StorageConnector::NvmeOverFabrics => ComponentBus::Other("NVMe-oF".into()),
+        StorageConnector::Sd | StorageConnector::Mmc => ComponentBus::Other("MMC".into()),
+        StorageConnector::Virtio => ComponentBus::Other("virtio".into()),
+        StorageConnector::Other(other) => ComponentBus::Other(other.clone()),
+    }
+}
+
+/// grabs info about the request queue for the device at `path`.
+#[tracing::instrument]
+async fn queue<P: AsRef<Path> + Debug>(path: P) -> Option<StorageQueue> {
+    let path = path.as_ref();
+    let queue = path.join("queue");
+
+    let (scheduler, scsi_depth, nr_requests, discard_max, discard_granularity, write_cache, zoned) = futures::join! {
+        sysfs_value_opt::<String>(queue.join("scheduler")),
+        sysfs_value_opt::<u32>(path.join("device/queue_depth")),
+        sysfs_value_opt::<u32>(queue.join("nr_requests")),
+        sysfs_value_opt::<u64>(queue.join("discard_max_bytes")),
+        sysfs_value_opt::<u64>(queue.join("discard_granularity")),
+        sysfs_value_opt::<String>(queue.join("write_cache")),
+        sysfs_value_opt::<String>(queue.join("zoned")),
+    };
+
+    // the scheduler file lists them all, with brackets around the one in use:
+    // `[mq-deadline] kyber bfq none`
+    let scheduler = scheduler.and_then(|s| {
+        s.split_ascii_whitespace()
+            .find_map(|sched| sched.strip_prefix('[')?.strip_suffix(']'))
+            .map(|sched| sched.to_string())
+    });
+
+    // scsi devices report their real queue depth. otherwise, use the block
+    // layer's
+    let depth = scsi_depth.or(nr_requests);
+
+    // a max discard size of zero means discard is unsupported
+    let supports_discard = discard_max.map(|max| max > 0);
+    let discard_granularity = discard_granularity.filter(|_| supports_discard == Some(true));
+
+    let write_cache = write_cache.and_then(|wc| match wc.as_str() {
+        "write back" => Some(WriteCacheMode::WriteBack),
+        "write through" => Some(WriteCacheMode::WriteThrough),
+        other => {
+            tracing::debug!("Unknown write cache mode: `{other}`");
+            None
+        }
+    });
+
+    let zoned = zoned.and_then(|z| match z.as_str() {
+        "none" => Some(ZonedModel::NotZoned),
+        "host-aware" => Some(ZonedModel::HostAware),
+        "host-managed" => Some(ZonedModel::HostManaged),
+        other => {
+            tracing::debug!("Unknown zoned model: `{other}`");
+            None
+        }
+    });
+
+    // don't bother if nothing was found
+    if scheduler.is_none()
+        && depth.is_none()
+        && supports_discard.is_none()
+        && write_cache.is_none()
+        && zoned.is_none()
+    {
+        return None;
+    }
+
+    Some(StorageQueue {
+        scheduler,
+        depth,
+        supports_discard,
+        discard_granularity,
+        write_cache,
+        zoned,
+    })
+}
+
 /// sees if the device at `path` is removable.
 #[tracing::instrument]
 async fn removable<P: AsRef<Path> + Debug>(path: P) -> Option<bool> {
@@ -260,7 +569,7 @@ mod tests {
         let path = ssd_path();
 
         // get all info
-        let info = one(path).await.unwrap();
+        let info = one(path, &Context::default()).await.unwrap();
 
         // check name, vendor
         assert_eq!(info.id.unwrap(), "Samsung SSD 860");
@@ -273,7 +582,7 @@ mod tests {
         let path = ssd_path();
 
         // get all info
-        let info = one(path).await.unwrap();
+        let info = one(path, &Context::default()).await.unwrap();
 
         // ensure we've got a drive
         let ComponentDescription::StorageDescription(desc) = info.desc() else {
@@ -281,10 +590,7 @@ mod tests {
         };
 
         // do checks
-        //
-        // removed til' i can simulate symlinks
-        // assert_eq!(desc.connector.unwrap(), StorageConnector::Sata);
-        //
+        assert_eq!(desc.connector.unwrap(), StorageConnector::Sata);
         assert_eq!(desc.is_removable, Some(false));
         assert_eq!(desc.kind.unwrap(), StorageKind::Ssd);
         assert!(desc.speed.is_none());
@@ -298,21 +604,104 @@ mod tests {
     async fn check_connector() {
         logger();
 
-        let _a = connector("/home/farts/sys/class/scsi_disk/15:0:0:0/")
-            .await
-            .unwrap();
-        let _b = connector("/sys/class/ata_port/1:0:0:0/").await.unwrap();
-        let _c = connector("/sys/class/scsi_disk/any_name_will_do")
+        // these use real devices from the fixtures
+        let sata = connector(ssd_path()).await.unwrap();
+        let nvme = connector(nvme_path()).await.unwrap();
+        let uas = connector(usb_path()).await.unwrap();
+
+        assert_eq!(sata, StorageConnector::Sata);
+        assert_eq!(nvme, StorageConnector::Nvme);
+        assert_eq!(uas, StorageConnector::Uas);
+
+        // and these only need their paths
+        let sas = connector(
+            "/sys/devices/pci0000:00/0000:02:00.0/host0/port-0:0/end_device-0:0/target0:0:0/0:0:0:0/block/sdd",
+        )
+        .await
+        .unwrap();
+        let virtio = connector("/sys/devices/pci0000:00/0000:00:04.0/virtio1/block/vda")
             .await
             .unwrap();
-        let _d = connector("/home/sys/class/real_sysroot/sys/class/scsi_disk/15:0:0:0/")
+        let iscsi = connector("/sys/devices/platform/host3/session1/target3:0:0/3:0:0:0/block/sde")
             .await
             .unwrap();
+        let scsi =
+            connector("/sys/devices/pci0000:00/0000:03:00.0/host2/target2:0:0/2:0:0:0/block/sdf")
+                .await
+                .unwrap();
+
+        assert_eq!(sas, StorageConnector::Sas);
+        assert_eq!(virtio, StorageConnector::Virtio);
+        assert_eq!(iscsi, StorageConnector::Iscsi);
+        assert_eq!(scsi, StorageConnector::Scsi);
+    }
 
-        assert_eq!(_a, StorageConnector::Scsi);
-        assert_eq!(_b, StorageConnector::Sata);
-        assert_eq!(_c, StorageConnector::Scsi);
-        assert_eq!(_d, StorageConnector::Scsi);
+    #[tokio::test]
+    async fn check_link() {
+        logger();
+
+        let sata = link(ssd_path(), &StorageConnector::Sata).await.unwrap();
+        assert_eq!(sata.speed.unwrap(), "6.0 Gbps");
+        assert!(sata.width.is_none());
+
+        let nvme = link(nvme_path(), &StorageConnector::Nvme).await.unwrap();
+        assert_eq!(nvme.speed.unwrap(), "16.0 GT/s PCIe");
+        assert_eq!(nvme.width.unwrap(), 4);
+
+        let uas = link(usb_path(), &StorageConnector::Uas).await.unwrap();
+        assert_eq!(uas.speed.unwrap(), "5000 Mbps");
+    }
+
+    #[tokio::test]
+    async fn check_queue() {
+        logger();
+
+        // sata ssd with trim
+        let sata = queue(ssd_path()).await.unwrap();
+        assert_eq!(sata.scheduler.unwrap(), "mq-deadline");
+        assert_eq!(
+            sata.depth.unwrap(),
+            32,
+            "scsi devices use their queue depth"
+        );
+        assert_eq!(sata.supports_discard, Some(true));
+        assert_eq!(sata.discard_granularity, Some(512));
+        assert_eq!(sata.write_cache.unwrap(), WriteCacheMode::WriteBack);
+        assert_eq!(sata.zoned.unwrap(), ZonedModel::NotZoned);
+
+        // nvme uses the block layer's queue depth
+        let nvme = queue(nvme_path()).await.unwrap();
+        assert_eq!(nvme.scheduler.unwrap(), "none");
+        assert_eq!(nvme.depth.unwrap(), 1023);
+
+        // the usb enclosure doesn't pass trim through
+        let usb = queue(usb_path()).await.unwrap();
+        assert_eq!(usb.supports_discard, Some(false));
+        assert!(usb.discard_granularity.is_none());
+        assert_eq!(usb.write_cache.unwrap(), WriteCacheMode::WriteThrough);
+    }
+
+    #[tokio::test]
+    async fn check_bus() {
+        logger();
+
+        let nvme = one(nvme_path(), &Context::default()).await.unwrap();
+        let usb = one(usb_path(), &Context::default()).await.unwrap();
+
+        assert_eq!(nvme.bus(), ComponentBus::Nvme);
+        assert_eq!(usb.bus(), ComponentBus::Usb);
+        assert_eq!(usb.id().unwrap(), "Extreme 55AE");
+    }
+
+    #[test]
+    fn check_usb_interface_names() {
+        assert!(is_usb_interface("2-1:1.0"));
+        assert!(is_usb_interface("3-10.4.1:1.12"));
+
+        assert!(!is_usb_interface("2-1"));
+        assert!(!is_usb_interface("usb2"));
+        assert!(!is_usb_interface("6:0:0:0"));
+        assert!(!is_usb_interface("target6:0:0"));
     }
 
     #[tokio::test]
@@ -332,7 +721,8 @@ mod tests {
     #[tokio::test]
     async fn check_speed() {
         logger();
-        let ComponentDescription::StorageDescription(desc) = one(hdd_path()).await.unwrap().desc()
+        let ComponentDescription::StorageDescription(desc) =
+            one(hdd_path(), &Context::default()).await.unwrap().desc()
         else {
             panic!("wrong desc ty!");
         };
@@ -341,6 +731,35 @@ mod tests {
         assert_eq!(desc.speed.unwrap(), 7200);
     }
 
+    #[tokio::test]
+    async fn check_status() {
+        logger();
+
+        // the sata drive has had a couple of failed commands
+        let sata = one(ssd_path(), &Context::default())
+            .await
+            .unwrap()
+            .status()
+            .unwrap();
+        assert_eq!(sata.health, Health::Warning);
+        assert_eq!(sata.driver_bound, Some(true));
+        assert_eq!(sata.firmware_version.unwrap(), "W6T0");
+        assert_eq!(sata.errors, Some(2));
+
+        // nvme keeps its driver (and firmware) on the controller
+        let root = env!("CARGO_MANIFEST_DIR");
+        let context = components::linux::context(format!("{root}/tests/assets/linux/sysfs")).await;
+        let nvme = one(nvme_path(), &context).await.unwrap().status().unwrap();
+        assert_eq!(nvme.health, Health::Ok);
+        assert_eq!(nvme.driver_bound, Some(true));
+        assert_eq!(nvme.module.as_deref(), Some("nvme"));
+
+        // its sensors are found through the report's context
+        assert_eq!(nvme.sensors.map(|sensors| sensors.len()), Some(2));
+        assert_eq!(nvme.firmware_version.unwrap(), "1B2QGXA7");
+        assert!(nvme.errors.is_none());
+    }
+
     #[tracing::instrument]
     fn ssd_path() -> PathBuf {
         let root = env!("CARGO_MANIFEST_DIR");
@@ -349,6 +768,22 @@ mod tests {
         ))
     }
 
+    #[tracing::instrument]
+    fn nvme_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!(
+            "{root}/tests/assets/linux/sysfs/sys/class/block/nvme0n1"
+        ))
+    }
+
+    #[tracing::instrument]
+    fn usb_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!(
+            "{root}/tests/assets/linux/sysfs/sys/class/block/sdc"
+        ))
+    }
+
     #[tracing::instrument]
     fn hdd_path() -> PathBuf {
         let root = env!("CARGO_MANIFEST_DIR");
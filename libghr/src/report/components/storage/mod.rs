@@ -24,6 +24,12 @@ pub struct StorageDescription {
 
     /// Whether or not the drive is known to be removable.
     pub is_removable: Option<bool>,
+
+    /// The negotiated link between the drive and its host controller.
+    pub link: Option<StorageLink>,
+
+    /// Characteristics of the drive's I/O request queue.
+    pub queue: Option<StorageQueue>,
 }
 
 /// A "kind" describing a storage device.
@@ -51,6 +57,9 @@ pub struct StorageUsage {
 }
 
 /// A storage device's connector.
+///
+/// This is the transport used to talk to the drive, which isn't always the
+/// physical connector. For example, a SATA SSD in a USB enclosure is `Usb`.
 #[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
 #[non_exhaustive]
 pub enum StorageConnector {
@@ -60,14 +69,99 @@ pub enum StorageConnector {
     M2,
     Pcie,
     Scsi,
+    /// Serial Attached SCSI.
+    Sas,
+    /// NVMe over a local PCIe link.
+    Nvme,
+    /// NVMe over a network fabric, like TCP, RDMA, or Fibre Channel.
+    NvmeOverFabrics,
+    /// USB mass storage using the older "Bulk-Only Transport" protocol.
+    Usb,
+    /// USB Attached SCSI, the faster USB storage protocol.
+    Uas,
+    /// An SD card.
+    Sd,
+    /// An MMC device. Soldered-on eMMC storage is reported as this.
+    Mmc,
+    /// A paravirtualized `virtio` block device, usually inside a VM.
+    Virtio,
+    /// SCSI over a network connection.
+    Iscsi,
     Other(String),
 }
 
+/// The link between a storage device and its host controller.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct StorageLink {
+    /// The negotiated link speed, as reported by the system.
+    ///
+    /// ex: `6.0 Gbps` (SATA), `16.0 GT/s PCIe` (NVMe), or `5000 Mbps` (USB)
+    pub speed: Option<String>,
+
+    /// The negotiated number of lanes. Only PCIe devices report this.
+    pub width: Option<u32>,
+}
+
+/// Characteristics of a storage device's request queue.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct StorageQueue {
+    /// The I/O scheduler currently in use, like `mq-deadline` or `none`.
+    pub scheduler: Option<String>,
+
+    /// The number of requests that can be queued on the device at once.
+    pub depth: Option<u32>,
+
+    /// Whether the device accepts discard (TRIM/UNMAP) requests.
+    pub supports_discard: Option<bool>,
+
+    /// The smallest region the device can discard, in bytes.
+    pub discard_granularity: Option<u64>,
+
+    /// How the device's volatile write cache is used.
+    pub write_cache: Option<WriteCacheMode>,
+
+    /// The device's zoned storage model.
+    pub zoned: Option<ZonedModel>,
+}
+
+/// The mode of a drive's write cache.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+pub enum WriteCacheMode {
+    /// Writes are acknowledged once they hit the cache.
+    WriteBack,
+    /// Writes are acknowledged only once they hit the medium.
+    WriteThrough,
+}
+
+/// The zoned storage model of a drive.
+///
+/// Zoned drives, like SMR hard drives, must be written sequentially within
+/// each zone.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+pub enum ZonedModel {
+    /// A regular drive that can be written anywhere.
+    NotZoned,
+    /// The drive is zoned, but handles random writes itself.
+    HostAware,
+    /// The host must write each zone sequentially.
+    HostManaged,
+}
+
 /// Grabs storage devices from the system.
 #[tracing::instrument]
 pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
+    get_with(&super::Context::load().await).await
+}
+
+/// does the same as [`get`], looking devices up in the report's shared
+/// `context`.
+#[tracing::instrument(skip(context))]
+#[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+pub(crate) async fn get_with(context: &super::Context) -> GhrResult<Vec<ComponentInfo>> {
     #[cfg(target_os = "linux")]
-    return linux::get().await;
+    return linux::get(context).await;
 
     #[cfg(target_os = "windows")]
     return windows::get().await;
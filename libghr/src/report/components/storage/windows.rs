@@ -52,6 +52,17 @@ async fn one(device: HashMap<String, Variant>) -> Option<ComponentInfo> {
         .and_then(|mt| mt.string_from_variant())
         .map(|mt| matches!(mt.as_str(), "Removable media"));
 
+    // windows reports nvme and sata drives as "SCSI", so we only trust the
+    // interface type for the less ambiguous ones
+    let connector = device
+        .get("InterfaceType")
+        .and_then(|it| it.string_from_variant())
+        .and_then(|it| match it.as_str() {
+            "USB" => Some(StorageConnector::Usb),
+            "IDE" => Some(StorageConnector::Ide),
+            _ => None,
+        });
+
     // build the description
     let desc = ComponentDescription::StorageDescription(StorageDescription {
         kind: None,
@@ -60,8 +71,10 @@ async fn one(device: HashMap<String, Variant>) -> Option<ComponentInfo> {
             total_capacity: size,
         },
         speed: None,
-        connector: None,
+        connector,
         is_removable,
+        link: None,
+        queue: None,
     });
 
     Some(ComponentInfo {
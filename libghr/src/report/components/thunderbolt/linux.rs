@@ -0,0 +1,286 @@
+use std::path::Path;
+
+use crate::{prelude::internal::*, report::components};
+
+/// finds info about all thunderbolt domains and routers on the system.
+#[tracing::instrument]
+pub(crate) async fn get() -> GhrResult<Vec<ComponentInfo>> {
+    all("/sys/bus/thunderbolt/devices").await
+}
+
+/// gets info about everything at the given path.
+#[tracing::instrument]
+async fn all(path: impl AsRef<Path> + std::fmt::Debug) -> GhrResult<Vec<ComponentInfo>> {
+    let mut tb = Vec::new();
+    for dev in components::linux::devices(path).await? {
+        if let Some(info) = one(&dev.path()).await {
+            tb.push(info);
+        }
+    }
+
+    Ok(tb)
+}
+
+/// grabs info about the domain or router at `path`, like
+/// `/sys/bus/thunderbolt/devices/0-1`.
+///
+/// other things on the bus, like retimers and host-to-host links, are
+/// skipped.
+#[tracing::instrument]
+async fn one(path: &Path) -> Option<ComponentInfo> {
+    let name = path.file_name()?.to_string_lossy().to_string();
+
+    // the bus's real layout tells us what each thing is connected to
+    let real_path = async_fs::canonicalize(path)
+        .await
+        .unwrap_or(path.to_path_buf());
+    let parent_path = real_path.parent();
+    let parent = parent_path
+        .and_then(|parent| parent.file_name())
+        .map(|parent| parent.to_string_lossy().to_string());
+
+    let kind = match devtype(path).await.as_deref() {
+        Some("thunderbolt_domain") => ThunderboltKind::Domain,
+        Some("thunderbolt_device") if name.ends_with("-0") => ThunderboltKind::HostRouter,
+        Some("thunderbolt_device") => ThunderboltKind::Device,
+        other => {
+            tracing::debug!("Skipping `{name}` on the Thunderbolt bus. (type: {other:?})");
+            return None;
+        }
+    };
+
+    if kind == ThunderboltKind::Domain {
+        return Some(domain(path, name, parent_path).await);
+    }
+
+    let (vendor_name, device_name, generation, authorized, link, nvm_version) = futures::join! {
+        sysfs_value_opt::<String>(path.join("vendor_name")),
+        sysfs_value_opt::<String>(path.join("device_name")),
+        sysfs_value_opt::<u8>(path.join("generation")),
+        authorized(path),
+        link(path),
+        sysfs_value_opt::<String>(path.join("nvm_version")),
+    };
+
+    let class = match kind {
+        ThunderboltKind::HostRouter => "Thunderbolt Host Router",
+        _ => "Thunderbolt Device",
+    };
+
+    Some(ComponentInfo {
+        bus: ComponentBus::Thunderbolt,
+        id: device_name,
+        class: Some(class.into()),
+        vendor_id: vendor_name,
+        status: None,
+        desc: ComponentDescription::ThunderboltDescription(ThunderboltDescription {
+            name: Some(name),
+            kind,
+            parent,
+            security: None,
+            iommu_dma_protection: None,
+            controller: None,
+            generation,
+            authorized,
+            link,
+            nvm_version,
+        }),
+    })
+}
+
+/// grabs info about a domain.
+///
+/// a domain's parent is its host controller (nhi), which is a pci device.
+#[tracing::instrument]
+async fn domain(path: &Path, name: String, controller_path: Option<&Path>) -> ComponentInfo {
+    let (security, iommu_dma_protection) = futures::join! {
+        security(path),
+        sysfs_value_opt::<u8>(path.join("iommu_dma_protection")),
+    };
+
+    // name the domain after its controller
+    let (id, vendor_id, controller) = match controller_path {
+        Some(controller_path) => {
+            let (vendor, device) = futures::join! {
+                sysfs_value_opt::<String>(controller_path.join("vendor")),
+                sysfs_value_opt::<String>(controller_path.join("device")),
+            };
+            let (id, vendor_id) = convert_to_pci_names(device, vendor);
+            let controller = controller_path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string());
+
+            (id, vendor_id, controller)
+        }
+        None => (None, None, None),
+    };
+
+    ComponentInfo {
+        bus: ComponentBus::Thunderbolt,
+        id,
+        class: Some("Thunderbolt Domain".into()),
+        vendor_id,
+        status: None,
+        desc: ComponentDescription::ThunderboltDescription(ThunderboltDescription {
+            name: Some(name),
+            kind: ThunderboltKind::Domain,
+            parent: None,
+            security,
+            iommu_dma_protection: iommu_dma_protection.map(|p| p == 1),
+            controller,
+            generation: None,
+            authorized: None,
+            link: None,
+            nvm_version: None,
+        }),
+    }
+}
+
+/// reads the `DEVTYPE` of something on the bus, like `thunderbolt_device`.
+#[tracing::instrument]
+async fn devtype(path: &Path) -> Option<String> {
+    let uevent = sysfs_value_opt::<String>(path.join("uevent")).await?;
+
+    uevent
+        .lines()
+        .find_map(|line| line.strip_prefix("DEVTYPE="))
+        .map(|devtype| devtype.trim().to_string())
+}
+
+/// reads the domain's security level.
+#[tracing::instrument]
+async fn security(path: &Path) -> Option<ThunderboltSecurity> {
+    match sysfs_value_opt::<String>(path.join("security"))
+        .await?
+        .as_str()
+    {
+        "none" => Some(ThunderboltSecurity::None),
+        "user" => Some(ThunderboltSecurity::User),
+        "secure" => Some(ThunderboltSecurity::Secure),
+        "dponly" => Some(ThunderboltSecurity::DpOnly),
+        "usbonly" => Some(ThunderboltSecurity::UsbOnly),
+        "nopcie" => Some(ThunderboltSecurity::NoPcie),
+        other => {
+            tracing::debug!("Unknown Thunderbolt security level: `{other}`");
+            None
+        }
+    }
+}
+
+/// reads whether the router is allowed to connect.
+///
+/// host routers don't have this file.
+#[tracing::instrument]
+async fn authorized(path: &Path) -> Option<ThunderboltAuthorization> {
+    match sysfs_value_opt::<u8>(path.join("authorized")).await? {
+        0 => Some(ThunderboltAuthorization::Unauthorized),
+        1 => Some(ThunderboltAuthorization::Authorized),
+        2 => Some(ThunderboltAuthorization::AuthorizedSecure),
+        other => {
+            tracing::debug!("Unknown Thunderbolt authorization: `{other}`");
+            None
+        }
+    }
+}
+
+/// reads the router's link speed and lanes.
+#[tracing::instrument]
+async fn link(path: &Path) -> Option<ThunderboltLink> {
+    let (rx_speed, rx_lanes, tx_speed, tx_lanes) = futures::join! {
+        sysfs_value_opt::<String>(path.join("rx_speed")),
+        sysfs_value_opt::<u8>(path.join("rx_lanes")),
+        sysfs_value_opt::<String>(path.join("tx_speed")),
+        sysfs_value_opt::<u8>(path.join("tx_lanes")),
+    };
+
+    if rx_speed.is_none() && rx_lanes.is_none() && tx_speed.is_none() && tx_lanes.is_none() {
+        return None;
+    }
+
+    Some(ThunderboltLink {
+        rx_speed,
+        rx_lanes,
+        tx_speed,
+        tx_lanes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{collections::HashMap, path::PathBuf};
+
+    #[tokio::test]
+    async fn check_thunderbolt_tree() {
+        let all = all(tb_path()).await.unwrap();
+
+        // the retimer isn't listed
+        let descs = all
+            .iter()
+            .filter_map(|info| match info.desc() {
+                ComponentDescription::ThunderboltDescription(desc) => {
+                    Some((desc.name.clone()?, desc))
+                }
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+        assert_eq!(descs.len(), 4);
+
+        // the egpu is chained through the dock, which is on the host router
+        assert_eq!(descs["0-301"].parent.as_deref(), Some("0-1"));
+        assert_eq!(descs["0-1"].parent.as_deref(), Some("0-0"));
+        assert_eq!(descs["0-0"].parent.as_deref(), Some("domain0"));
+        assert_eq!(descs["0-0"].kind, ThunderboltKind::HostRouter);
+        assert!(descs["domain0"].parent.is_none());
+    }
+
+    #[tokio::test]
+    async fn check_thunderbolt_domain() {
+        let info = one(&tb_path().join("domain0")).await.unwrap();
+
+        assert_eq!(info.bus(), ComponentBus::Thunderbolt);
+        assert_eq!(info.vendor_id().unwrap(), "Intel Corporation");
+        assert_eq!(info.id().unwrap(), "Alder Lake-P Thunderbolt 4 NHI #0");
+
+        let ComponentDescription::ThunderboltDescription(desc) = info.desc() else {
+            panic!("wrong desc");
+        };
+
+        assert_eq!(desc.kind, ThunderboltKind::Domain);
+        assert_eq!(desc.security.unwrap(), ThunderboltSecurity::User);
+        assert_eq!(desc.iommu_dma_protection, Some(true));
+        assert_eq!(desc.controller.unwrap(), "0000:00:0d.2");
+    }
+
+    #[tokio::test]
+    async fn check_thunderbolt_dock() {
+        let info = one(&tb_path().join("0-1")).await.unwrap();
+
+        assert_eq!(info.vendor_id().unwrap(), "Lenovo");
+        assert_eq!(info.id().unwrap(), "ThinkPad Thunderbolt 4 Dock");
+
+        let ComponentDescription::ThunderboltDescription(desc) = info.desc() else {
+            panic!("wrong desc");
+        };
+
+        assert_eq!(desc.kind, ThunderboltKind::Device);
+        assert_eq!(desc.generation, Some(4));
+        assert_eq!(
+            desc.authorized.unwrap(),
+            ThunderboltAuthorization::Authorized
+        );
+        assert_eq!(desc.nvm_version.unwrap(), "36.0");
+
+        let link = desc.link.unwrap();
+        assert_eq!(link.rx_speed.unwrap(), "20.0 Gb/s");
+        assert_eq!(link.rx_lanes, Some(2));
+    }
+
+    #[tracing::instrument]
+    fn tb_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!(
+            "{root}/tests/assets/linux/sysfs/sys/bus/thunderbolt/devices"
+        ))
+    }
+}
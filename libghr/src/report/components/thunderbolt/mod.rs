@@ -0,0 +1,119 @@
+//! thunderbolt: thunderbolt and usb4 domains, routers, and devices
+
+use crate::prelude::internal::*;
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+/// A description of something on a Thunderbolt (or USB4) bus.
+///
+/// Each bus is a "domain" that's run by a host controller. Under it, the host
+/// router connects to any docks, displays, or eGPUs, which can also connect
+/// to each other in a chain.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct ThunderboltDescription {
+    /// The name of this domain or router, like `domain0` or `0-1`.
+    pub name: Option<String>,
+
+    /// What this is.
+    pub kind: ThunderboltKind,
+
+    /// The name of what this is connected to, like `0-0`.
+    ///
+    /// Host routers point to their domain, while devices point to the
+    /// router (host or device) upstream of them. Domains don't have one.
+    pub parent: Option<String>,
+
+    /// The domain's security level.
+    pub security: Option<ThunderboltSecurity>,
+
+    /// Whether the domain's IOMMU protects against DMA attacks.
+    pub iommu_dma_protection: Option<bool>,
+
+    /// The PCI address of the domain's host controller (NHI), like
+    /// `0000:00:0d.2`.
+    pub controller: Option<String>,
+
+    /// The router's generation: 1 to 3 for Thunderbolt 1 to 3, or 4 for
+    /// USB4 (and Thunderbolt 4).
+    pub generation: Option<u8>,
+
+    /// Whether the router is allowed to connect.
+    pub authorized: Option<ThunderboltAuthorization>,
+
+    /// The router's link to its parent.
+    pub link: Option<ThunderboltLink>,
+
+    /// The version of the router's firmware.
+    pub nvm_version: Option<String>,
+}
+
+/// A kind of thing on a Thunderbolt bus.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum ThunderboltKind {
+    /// A whole bus, run by one host controller.
+    Domain,
+    /// The router inside the computer's host controller.
+    HostRouter,
+    /// A connected device, like a dock or eGPU.
+    Device,
+}
+
+/// The security level of a Thunderbolt domain.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum ThunderboltSecurity {
+    /// Devices connect automatically.
+    None,
+    /// The user has to approve each device.
+    User,
+    /// The user has to approve each device, which is then checked with a key.
+    Secure,
+    /// Only DisplayPort works.
+    DpOnly,
+    /// Only USB and DisplayPort work, through the first dock.
+    UsbOnly,
+    /// PCIe tunneling is turned off.
+    NoPcie,
+}
+
+/// Whether a Thunderbolt device is allowed to connect.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+pub enum ThunderboltAuthorization {
+    /// The device is waiting for approval.
+    Unauthorized,
+    /// The device is connected.
+    Authorized,
+    /// The device is connected, and was checked with its key.
+    AuthorizedSecure,
+}
+
+/// The link between a Thunderbolt router and its parent.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct ThunderboltLink {
+    /// The speed of each receiving lane, like `20.0 Gb/s`.
+    pub rx_speed: Option<String>,
+
+    /// How many lanes are receiving.
+    pub rx_lanes: Option<u8>,
+
+    /// The speed of each transmitting lane.
+    pub tx_speed: Option<String>,
+
+    /// How many lanes are transmitting.
+    pub tx_lanes: Option<u8>,
+}
+
+/// Finds and returns info about Thunderbolt domains and devices.
+#[tracing::instrument]
+pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
+    #[cfg(target_os = "linux")]
+    return linux::get().await;
+
+    // windows only exposes thunderbolt through the vendor's control center
+    #[cfg(target_os = "windows")]
+    return Ok(Vec::new());
+}
@@ -0,0 +1,528 @@
+use std::path::{Path, PathBuf};
+
+use crate::{prelude::internal::*, report::components};
+
+/// the standard id (svid) for displayport alternate mode
+const SVID_DISPLAYPORT: u16 = 0xff01;
+
+/// intel's vendor id, used for thunderbolt alternate mode
+const SVID_THUNDERBOLT: u16 = 0x8087;
+
+/// finds info about all type-c ports on the system.
+#[tracing::instrument]
+pub(crate) async fn get() -> GhrResult<Vec<ComponentInfo>> {
+    all("/sys/class/typec").await
+}
+
+/// gets info about all type-c ports at the given path.
+#[tracing::instrument]
+async fn all(path: impl AsRef<Path> + std::fmt::Debug) -> GhrResult<Vec<ComponentInfo>> {
+    let mut ports = Vec::new();
+    for dev in components::linux::devices(path).await? {
+        let name = dev.file_name().to_string_lossy().to_string();
+
+        // partners, cables, and plugs are also in this class. we only want
+        // the ports, like `port0`
+        if !name.starts_with("port") || name.contains('-') {
+            continue;
+        }
+
+        ports.push(one(&dev.path()).await);
+    }
+
+    Ok(ports)
+}
+
+/// grabs info about the type-c port at `path`, like `/sys/class/typec/port0`.
+#[tracing::instrument]
+async fn one(path: &Path) -> ComponentInfo {
+    let port = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let (port_type, data_role, power_role, power_operation_mode, pd_revision) = futures::join! {
+        port_type(path),
+        data_role(path),
+        power_role(path),
+        power_operation_mode(path),
+        pd_revision(path),
+    };
+
+    // the partner and cable are children of the port
+    let (alt_modes, power_delivery, partner, cable) = futures::join! {
+        alt_modes(path, &port),
+        power_delivery(path.join("usb_power_delivery")),
+        partner(path.join(format!("{port}-partner"))),
+        cable(path.join(format!("{port}-cable"))),
+    };
+
+    ComponentInfo {
+        bus: ComponentBus::Usb,
+        id: Some(port.clone()),
+        class: Some("USB Type-C Port".into()),
+        vendor_id: None,
+        status: None,
+        desc: ComponentDescription::TypeCDescription(TypeCDescription {
+            port: Some(port),
+            port_type,
+            data_role,
+            power_role,
+            power_operation_mode,
+            pd_revision,
+            alt_modes,
+            power_delivery,
+            partner,
+            cable,
+        }),
+    }
+}
+
+/// reads which power roles the port supports.
+#[tracing::instrument]
+async fn port_type(path: &Path) -> Option<TypeCPortType> {
+    let port_type = sysfs_value_opt::<String>(path.join("port_type")).await?;
+
+    match selected(&port_type).as_str() {
+        "dual" => Some(TypeCPortType::Dual),
+        "source" => Some(TypeCPortType::Source),
+        "sink" => Some(TypeCPortType::Sink),
+        other => {
+            tracing::debug!("Unknown Type-C port type: `{other}`");
+            None
+        }
+    }
+}
+
+/// reads the port's current data role.
+#[tracing::instrument]
+async fn data_role(path: &Path) -> Option<DataRole> {
+    let data_role = sysfs_value_opt::<String>(path.join("data_role")).await?;
+
+    match selected(&data_role).as_str() {
+        "host" => Some(DataRole::Host),
+        "device" => Some(DataRole::Device),
+        _ => None,
+    }
+}
+
+/// reads the port's current power role.
+#[tracing::instrument]
+async fn power_role(path: &Path) -> Option<PowerRole> {
+    let power_role = sysfs_value_opt::<String>(path.join("power_role")).await?;
+
+    match selected(&power_role).as_str() {
+        "source" => Some(PowerRole::Source),
+        "sink" => Some(PowerRole::Sink),
+        _ => None,
+    }
+}
+
+/// reads how the port's power contract was made.
+#[tracing::instrument]
+async fn power_operation_mode(path: &Path) -> Option<PowerOperationMode> {
+    match sysfs_value_opt::<String>(path.join("power_operation_mode"))
+        .await?
+        .as_str()
+    {
+        "default" => Some(PowerOperationMode::Default),
+        "1.5A" => Some(PowerOperationMode::Current1_5A),
+        "3.0A" => Some(PowerOperationMode::Current3A),
+        "usb_power_delivery" => Some(PowerOperationMode::PowerDelivery),
+        other => {
+            tracing::debug!("Unknown Type-C power operation mode: `{other}`");
+            None
+        }
+    }
+}
+
+/// reads the usb power delivery revision of a port, partner, or cable.
+///
+/// the kernel says `0.0` when it doesn't know.
+#[tracing::instrument]
+async fn pd_revision(path: &Path) -> Option<String> {
+    sysfs_value_opt::<String>(path.join("usb_power_delivery_revision"))
+        .await
+        .filter(|rev| rev != "0.0")
+}
+
+/// finds the alternate modes of a port or partner.
+///
+/// each mode is a child directory named like `<parent>.<n>`.
+#[tracing::instrument]
+async fn alt_modes(path: &Path, parent: &str) -> Option<Vec<AltMode>> {
+    let prefix = format!("{parent}.");
+
+    let mut paths = components::linux::devices(path)
+        .await
+        .ok()?
+        .into_iter()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let mut modes = Vec::with_capacity(paths.len());
+    for mode_path in paths {
+        let (svid, mode, active) = futures::join! {
+            sysfs_value_opt::<String>(mode_path.join("svid")),
+            sysfs_value_opt::<u32>(mode_path.join("mode")),
+            sysfs_value_opt::<String>(mode_path.join("active")),
+        };
+
+        let Some(svid) = svid
+            .and_then(|svid| parse_hex(&svid))
+            .and_then(|svid| u16::try_from(svid).ok())
+        else {
+            tracing::debug!("Alternate mode at `{mode_path:?}` has no SVID.");
+            continue;
+        };
+
+        let kind = match svid {
+            SVID_DISPLAYPORT => AltModeKind::DisplayPort,
+            SVID_THUNDERBOLT => AltModeKind::Thunderbolt,
+            _ => AltModeKind::Other,
+        };
+
+        modes.push(AltMode {
+            kind,
+            svid,
+            mode,
+            active: active.map(|active| active == "yes"),
+        });
+    }
+
+    (!modes.is_empty()).then_some(modes)
+}
+
+/// grabs info about the port's partner, if one is plugged in.
+#[tracing::instrument]
+async fn partner(path: PathBuf) -> Option<TypeCPartner> {
+    if async_fs::metadata(&path).await.is_err() {
+        return None;
+    }
+
+    let name = path.file_name()?.to_string_lossy().to_string();
+
+    let (identity, supports_pd, pd_revision, alt_modes, power_delivery) = futures::join! {
+        identity(&path),
+        sysfs_value_opt::<String>(path.join("supports_usb_power_delivery")),
+        pd_revision(&path),
+        alt_modes(&path, &name),
+        power_delivery(path.join("usb_power_delivery")),
+    };
+
+    let (vendor, vendor_id, product_id) = identity.unwrap_or_default();
+
+    Some(TypeCPartner {
+        vendor,
+        vendor_id,
+        product_id,
+        supports_pd: supports_pd.map(|pd| pd == "yes"),
+        pd_revision,
+        alt_modes,
+        power_delivery,
+    })
+}
+
+/// grabs info about the port's cable, if it's e-marked.
+///
+/// the cable's speed and current limit are in its "cable vdo", which is the
+/// first product type vdo.
+#[tracing::instrument]
+async fn cable(path: PathBuf) -> Option<TypeCCable> {
+    if async_fs::metadata(&path).await.is_err() {
+        return None;
+    }
+
+    let (kind, plug_type, pd_revision, identity, cable_vdo) = futures::join! {
+        sysfs_value_opt::<String>(path.join("type")),
+        sysfs_value_opt::<String>(path.join("plug_type")),
+        pd_revision(&path),
+        identity(&path),
+        sysfs_value_opt::<String>(path.join("identity/product_type_vdo1")),
+    };
+
+    let (vendor, vendor_id, product_id) = identity.unwrap_or_default();
+    let cable_vdo = cable_vdo
+        .and_then(|vdo| parse_hex(&vdo))
+        .filter(|vdo| *vdo != 0);
+
+    // bits 6..5 are the vbus current limit
+    let max_current_ma = cable_vdo.and_then(|vdo| match (vdo >> 5) & 0b11 {
+        0b01 => Some(3000),
+        0b10 => Some(5000),
+        _ => None,
+    });
+
+    // bits 2..0 are the usb speed
+    let max_speed = cable_vdo.and_then(|vdo| match vdo & 0b111 {
+        0b000 => Some("USB 2.0".to_string()),
+        0b001 => Some("USB 3.2 Gen1".to_string()),
+        0b010 => Some("USB4 Gen2".to_string()),
+        0b011 => Some("USB4 Gen3".to_string()),
+        0b100 => Some("USB4 Gen4".to_string()),
+        _ => None,
+    });
+
+    Some(TypeCCable {
+        active: kind.map(|kind| kind == "active"),
+        plug_type: plug_type.filter(|plug| plug != "unknown"),
+        pd_revision,
+        vendor,
+        vendor_id,
+        product_id,
+        max_current_ma,
+        max_speed,
+    })
+}
+
+/// reads the (vendor name, vendor id, product id) from a partner or cable's
+/// discovered identity.
+///
+/// the vendor id is in the low half of the `id_header` vdo, while the product
+/// id is in the high half of the `product` vdo.
+#[tracing::instrument]
+async fn identity(path: &Path) -> Option<(Option<String>, Option<u16>, Option<u16>)> {
+    use usb_ids::FromId as _;
+
+    let (id_header, product) = futures::join! {
+        sysfs_value_opt::<String>(path.join("identity/id_header")),
+        sysfs_value_opt::<String>(path.join("identity/product")),
+    };
+
+    // these are all zeroes until the identity is discovered
+    let id_header = id_header.and_then(|h| parse_hex(&h)).filter(|h| *h != 0)?;
+    let vendor_id = (id_header & 0xffff) as u16;
+    let product_id = product
+        .and_then(|p| parse_hex(&p))
+        .map(|p| (p >> 16) as u16);
+
+    let vendor = usb_ids::Vendor::from_id(vendor_id).map(|v| v.name().to_string());
+
+    Some((vendor, Some(vendor_id), product_id))
+}
+
+/// reads the source and sink capabilities of a usb power delivery device.
+///
+/// `path` is its `usb_power_delivery` link, which points to something like
+/// `/sys/class/usb_power_delivery/pd0`.
+#[tracing::instrument]
+async fn power_delivery(path: PathBuf) -> Option<PowerDelivery> {
+    let (source, sink) = futures::join! {
+        pdos(path.join("source-capabilities")),
+        pdos(path.join("sink-capabilities")),
+    };
+
+    if source.is_none() && sink.is_none() {
+        return None;
+    }
+
+    Some(PowerDelivery { source, sink })
+}
+
+/// reads the pdos in a capabilities directory.
+///
+/// each pdo is a directory named like `<n>:<kind>`, such as `1:fixed_supply`.
+#[tracing::instrument]
+async fn pdos(path: PathBuf) -> Option<Vec<Pdo>> {
+    let mut entries = components::linux::devices(&path)
+        .await
+        .ok()?
+        .into_iter()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let (index, kind) = name.split_once(':')?;
+            Some((index.parse::<u32>().ok()?, kind.to_string(), entry.path()))
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by_key(|(index, ..)| *index);
+
+    let mut pdos = Vec::with_capacity(entries.len());
+    for (_, kind, pdo_path) in entries {
+        // values have units attached, like `5000mV`
+        let value = |file: &'static str| {
+            let pdo_path = pdo_path.clone();
+            async move {
+                let value = sysfs_value_opt::<String>(pdo_path.join(file)).await?;
+                value
+                    .trim_end_matches(char::is_alphabetic)
+                    .parse::<u32>()
+                    .ok()
+            }
+        };
+
+        let pdo = match kind.as_str() {
+            "fixed_supply" => {
+                let (voltage_mv, max_current_ma) =
+                    futures::join!(value("voltage"), value("maximum_current"));
+
+                Pdo::Fixed {
+                    voltage_mv: voltage_mv?,
+                    max_current_ma: max_current_ma?,
+                }
+            }
+            "variable_supply" => {
+                let (min_voltage_mv, max_voltage_mv, max_current_ma) = futures::join!(
+                    value("minimum_voltage"),
+                    value("maximum_voltage"),
+                    value("maximum_current")
+                );
+
+                Pdo::Variable {
+                    min_voltage_mv: min_voltage_mv?,
+                    max_voltage_mv: max_voltage_mv?,
+                    max_current_ma: max_current_ma?,
+                }
+            }
+            "battery" => {
+                let (min_voltage_mv, max_voltage_mv, max_power_mw) = futures::join!(
+                    value("minimum_voltage"),
+                    value("maximum_voltage"),
+                    value("maximum_power")
+                );
+
+                Pdo::Battery {
+                    min_voltage_mv: min_voltage_mv?,
+                    max_voltage_mv: max_voltage_mv?,
+                    max_power_mw: max_power_mw?,
+                }
+            }
+            "programmable_supply" => {
+                let (min_voltage_mv, max_voltage_mv, max_current_ma) = futures::join!(
+                    value("minimum_voltage"),
+                    value("maximum_voltage"),
+                    value("maximum_current")
+                );
+
+                Pdo::Programmable {
+                    min_voltage_mv: min_voltage_mv?,
+                    max_voltage_mv: max_voltage_mv?,
+                    max_current_ma: max_current_ma?,
+                }
+            }
+            other => {
+                tracing::debug!("Skipping unknown PDO kind `{other}`.");
+                continue;
+            }
+        };
+
+        pdos.push(pdo);
+    }
+
+    (!pdos.is_empty()).then_some(pdos)
+}
+
+/// picks the selected value from a `sysfs` choice list, like `[host] device`.
+///
+/// ports with a fixed role just list that role.
+fn selected(choices: &str) -> String {
+    current_choice(choices).unwrap_or_else(|| choices.trim().to_string())
+}
+
+/// parses a hex number, with or without its `0x` prefix.
+fn parse_hex(s: &str) -> Option<u32> {
+    u32::from_str_radix(s.trim().trim_start_matches("0x"), 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn check_typec_dock() {
+        let info = one(&typec_path().join("port0")).await;
+
+        let ComponentDescription::TypeCDescription(desc) = info.desc else {
+            panic!("wrong desc");
+        };
+
+        assert_eq!(desc.port.unwrap(), "port0");
+        assert_eq!(desc.port_type.unwrap(), TypeCPortType::Dual);
+        assert_eq!(desc.data_role.unwrap(), DataRole::Host);
+        assert_eq!(desc.power_role.unwrap(), PowerRole::Sink);
+        assert_eq!(
+            desc.power_operation_mode.unwrap(),
+            PowerOperationMode::PowerDelivery
+        );
+        assert_eq!(desc.pd_revision.unwrap(), "3.0");
+
+        // the port supports displayport
+        let port_modes = desc.alt_modes.unwrap();
+        assert_eq!(port_modes[0].kind, AltModeKind::DisplayPort);
+
+        // the dock is using displayport, and offers up to 100 watts
+        let partner = desc.partner.unwrap();
+        assert_eq!(partner.vendor_id, Some(0x17ef));
+        assert_eq!(partner.vendor.unwrap(), "Lenovo");
+        assert_eq!(partner.supports_pd, Some(true));
+
+        let modes = partner.alt_modes.unwrap();
+        assert_eq!(modes.len(), 2);
+        assert!(modes
+            .iter()
+            .any(|m| m.kind == AltModeKind::DisplayPort && m.active == Some(true)));
+        assert!(modes
+            .iter()
+            .any(|m| m.kind == AltModeKind::Thunderbolt && m.active == Some(false)));
+
+        let source = partner.power_delivery.unwrap().source.unwrap();
+        assert_eq!(
+            source[0],
+            Pdo::Fixed {
+                voltage_mv: 5000,
+                max_current_ma: 3000
+            }
+        );
+        assert_eq!(source.iter().map(Pdo::max_power_mw).max(), Some(100_000));
+        assert!(matches!(source.last().unwrap(), Pdo::Programmable { .. }));
+
+        // the port can sink up to 20 volts
+        let sink = desc.power_delivery.unwrap().sink.unwrap();
+        assert_eq!(sink.len(), 2);
+
+        // and the cable is a passive 5 amp cable
+        let cable = desc.cable.unwrap();
+        assert_eq!(cable.active, Some(false));
+        assert_eq!(cable.max_current_ma, Some(5000));
+        assert_eq!(cable.max_speed.unwrap(), "USB 3.2 Gen1");
+        assert_eq!(cable.plug_type.unwrap(), "type-c");
+    }
+
+    #[tokio::test]
+    async fn check_typec_all() {
+        let ports = all(typec_path()).await.unwrap();
+
+        // only ports are listed, not partners or cables
+        assert_eq!(ports.len(), 2);
+
+        // the empty port has nothing plugged in
+        let empty = ports
+            .into_iter()
+            .find_map(|info| match info.desc {
+                ComponentDescription::TypeCDescription(desc)
+                    if desc.port.as_deref() == Some("port1") =>
+                {
+                    Some(desc)
+                }
+                _ => None,
+            })
+            .unwrap();
+        assert!(empty.partner.is_none());
+        assert!(empty.cable.is_none());
+        assert_eq!(empty.port_type.unwrap(), TypeCPortType::Sink);
+    }
+
+    #[test]
+    fn check_selected() {
+        assert_eq!(selected("[host] device"), "host");
+        assert_eq!(selected("source [sink]"), "sink");
+        assert_eq!(selected("sink"), "sink");
+    }
+
+    #[tracing::instrument]
+    fn typec_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs/sys/class/typec"))
+    }
+}
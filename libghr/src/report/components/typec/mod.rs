@@ -0,0 +1,247 @@
+//! typec: usb type-c ports and usb power delivery
+
+use crate::prelude::internal::*;
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+/// A description of a USB Type-C port, including what's plugged into it.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct TypeCDescription {
+    /// The port's name, like `port0`.
+    pub port: Option<String>,
+
+    /// Which power roles the port is able to take.
+    pub port_type: Option<TypeCPortType>,
+
+    /// The port's current data role.
+    pub data_role: Option<DataRole>,
+
+    /// The port's current power role.
+    pub power_role: Option<PowerRole>,
+
+    /// How the port's power contract was made.
+    pub power_operation_mode: Option<PowerOperationMode>,
+
+    /// The USB Power Delivery revision the port supports, like `3.0`.
+    pub pd_revision: Option<String>,
+
+    /// The alternate modes the port supports, like DisplayPort.
+    pub alt_modes: Option<Vec<AltMode>>,
+
+    /// The port's own power capabilities.
+    pub power_delivery: Option<PowerDelivery>,
+
+    /// The device plugged into this port, if any.
+    pub partner: Option<TypeCPartner>,
+
+    /// The cable plugged into this port, if it has an e-marker.
+    pub cable: Option<TypeCCable>,
+}
+
+/// The power roles a Type-C port is able to take.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+pub enum TypeCPortType {
+    /// The port can both provide and take power.
+    Dual,
+    /// The port can only provide power.
+    Source,
+    /// The port can only take power.
+    Sink,
+}
+
+/// Which end of a USB data connection a port is acting as.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+pub enum DataRole {
+    Host,
+    Device,
+}
+
+/// Which way power flows through a port.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+pub enum PowerRole {
+    /// The port is providing power.
+    Source,
+    /// The port is taking power.
+    Sink,
+}
+
+/// How a Type-C port's power contract was made.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum PowerOperationMode {
+    /// Default USB power (500 mA for USB 2.0, or 900 mA for USB 3).
+    Default,
+    /// Type-C current at 1.5 A.
+    Current1_5A,
+    /// Type-C current at 3 A.
+    Current3A,
+    /// A contract negotiated using USB Power Delivery.
+    PowerDelivery,
+}
+
+/// An alternate mode, which lets a Type-C port carry another protocol.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct AltMode {
+    /// Which protocol this alternate mode is for.
+    pub kind: AltModeKind,
+
+    /// The Standard or Vendor ID (SVID) of the mode.
+    pub svid: u16,
+
+    /// The index of the mode within its SVID.
+    pub mode: Option<u32>,
+
+    /// Whether the mode is in use.
+    pub active: Option<bool>,
+}
+
+/// A known kind of alternate mode.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum AltModeKind {
+    DisplayPort,
+    Thunderbolt,
+    /// Some other (usually vendor-specific) mode.
+    Other,
+}
+
+/// The device on the other end of a Type-C port, like a charger or dock.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct TypeCPartner {
+    /// The partner's vendor, found using its USB vendor ID.
+    pub vendor: Option<String>,
+
+    /// The partner's USB vendor ID.
+    pub vendor_id: Option<u16>,
+
+    /// The partner's USB product ID.
+    pub product_id: Option<u16>,
+
+    /// Whether the partner can use USB Power Delivery.
+    pub supports_pd: Option<bool>,
+
+    /// The USB Power Delivery revision the partner supports.
+    pub pd_revision: Option<String>,
+
+    /// The partner's alternate modes.
+    pub alt_modes: Option<Vec<AltMode>>,
+
+    /// What the partner is able to provide (or use) in power.
+    pub power_delivery: Option<PowerDelivery>,
+}
+
+/// An electronically-marked (e-marked) Type-C cable.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct TypeCCable {
+    /// Whether the cable has active electronics inside.
+    pub active: Option<bool>,
+
+    /// The kind of plug on the cable's far end, like `type-c`.
+    pub plug_type: Option<String>,
+
+    /// The USB Power Delivery revision the cable supports.
+    pub pd_revision: Option<String>,
+
+    /// The cable's vendor, found using its USB vendor ID.
+    pub vendor: Option<String>,
+
+    /// The cable's USB vendor ID.
+    pub vendor_id: Option<u16>,
+
+    /// The cable's USB product ID.
+    pub product_id: Option<u16>,
+
+    /// The most current the cable can carry, in milliamps.
+    pub max_current_ma: Option<u32>,
+
+    /// The fastest data speed the cable supports, like `USB4 Gen3`.
+    pub max_speed: Option<String>,
+}
+
+/// The Power Data Objects (PDOs) of a USB Power Delivery device.
+///
+/// Each one is a "power level" that the device can provide (as a source) or
+/// use (as a sink).
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct PowerDelivery {
+    /// What the device can provide.
+    pub source: Option<Vec<Pdo>>,
+
+    /// What the device can use.
+    pub sink: Option<Vec<Pdo>>,
+}
+
+/// One USB Power Delivery power level.
+///
+/// Voltages are in millivolts, currents are in milliamps, and power is in
+/// milliwatts.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum Pdo {
+    /// A fixed voltage.
+    Fixed {
+        voltage_mv: u32,
+        max_current_ma: u32,
+    },
+
+    /// A range of voltages, with a current limit.
+    Variable {
+        min_voltage_mv: u32,
+        max_voltage_mv: u32,
+        max_current_ma: u32,
+    },
+
+    /// A range of voltages, with a power limit.
+    Battery {
+        min_voltage_mv: u32,
+        max_voltage_mv: u32,
+        max_power_mw: u32,
+    },
+
+    /// A Programmable Power Supply (PPS) range, where the sink picks a voltage.
+    Programmable {
+        min_voltage_mv: u32,
+        max_voltage_mv: u32,
+        max_current_ma: u32,
+    },
+}
+
+impl Pdo {
+    /// The most power this level can provide, in milliwatts.
+    pub fn max_power_mw(&self) -> u32 {
+        match *self {
+            Pdo::Fixed {
+                voltage_mv,
+                max_current_ma,
+            } => voltage_mv * max_current_ma / 1000,
+            Pdo::Variable {
+                max_voltage_mv,
+                max_current_ma,
+                ..
+            }
+            | Pdo::Programmable {
+                max_voltage_mv,
+                max_current_ma,
+                ..
+            } => max_voltage_mv * max_current_ma / 1000,
+            Pdo::Battery { max_power_mw, .. } => max_power_mw,
+        }
+    }
+}
+
+/// Finds and returns info about the system's USB Type-C ports.
+#[tracing::instrument]
+pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
+    #[cfg(target_os = "linux")]
+    return linux::get().await;
+
+    // windows doesn't expose type-c ports through wmi
+    #[cfg(target_os = "windows")]
+    return Ok(Vec::new());
+}
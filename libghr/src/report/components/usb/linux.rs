@@ -0,0 +1,332 @@
+//! i am usb (on linux)
+
+use crate::prelude::internal::*;
+
+use futures::join;
+use futures::try_join;
+use std::path::Path;
+
+#[tracing::instrument]
+pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
+    all("/sys/bus/usb/devices").await
+}
+
+/// gets info about all usb devices at the given path.
+#[tracing::instrument]
+async fn all(path: impl AsRef<Path> + std::fmt::Debug) -> GhrResult<Vec<ComponentInfo>> {
+    // grab info about usb devices and construct reprs
+    let mut usb = Vec::new();
+    for dev in crate::report::components::linux::devices(path).await? {
+        let name = dev.file_name().to_string_lossy().to_string();
+
+        // interfaces (like `1-1:1.0`) are folded into their device
+        if name.contains(':') {
+            continue;
+        }
+
+        // and root hubs (like `usb1`) are just part of the host controller
+        if name.starts_with("usb") {
+            tracing::debug!("Skipping root hub `{name}`.");
+            continue;
+        }
+
+        usb.push(one(&dev.path()).await);
+    }
+
+    Ok(usb)
+}
+
+/// grabs info about the usb device at `path`, like
+/// `/sys/bus/usb/devices/1-2.3`.
+#[tracing::instrument]
+async fn one(path: &Path) -> ComponentInfo {
+    // read a few files to get important info about this thang
+    let ((vendor_id, id), device_class, interfaces) = join! {
+        usb_vendor_and_id(path),
+        sysfs_value_opt::<String>(path.join("bDeviceClass")),
+        interfaces(path),
+    };
+
+    // devices with class `00` give each interface its own class. use the
+    // first one to describe the device
+    let class = if device_class.as_deref() == Some("00") {
+        interfaces
+            .as_ref()
+            .and_then(|interfaces| interfaces.first())
+            .and_then(|interface| interface.class.clone())
+    } else {
+        usb_class(path, "bDevice").await
+    };
+
+    let (speed, version, max_power, removability, configuration, configuration_count) = join! {
+        speed(path),
+        sysfs_value_opt::<String>(path.join("version")),
+        max_power(path),
+        removability(path),
+        sysfs_value_opt::<u8>(path.join("bConfigurationValue")),
+        sysfs_value_opt::<u8>(path.join("bNumConfigurations")),
+    };
+
+    ComponentInfo {
+        bus: ComponentBus::Usb,
+        id,
+        class,
+        vendor_id,
+        status: None,
+        desc: ComponentDescription::UsbDescription(UsbDescription {
+            port_path: path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string()),
+            speed,
+            version,
+            max_power,
+            removability,
+            configuration,
+            configuration_count,
+            interfaces,
+        }),
+    }
+}
+
+/// lists the interfaces of the device's active configuration.
+///
+/// each one is a child directory named like `<device>:<config>.<interface>`.
+#[tracing::instrument]
+async fn interfaces(path: &Path) -> Option<Vec<UsbInterface>> {
+    let name = path.file_name()?.to_string_lossy().to_string();
+    let prefix = format!("{name}:");
+
+    let mut paths = crate::report::components::linux::devices(path)
+        .await
+        .ok()?
+        .into_iter()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let mut interfaces = Vec::with_capacity(paths.len());
+    for interface in paths {
+        let (number, class, driver) = join! {
+            sysfs_value_opt::<String>(interface.join("bInterfaceNumber")),
+            usb_class(&interface, "bInterface"),
+            driver_name(&interface),
+        };
+
+        interfaces.push(UsbInterface {
+            number: number.and_then(|n| u8::from_str_radix(&n, 16).ok()),
+            class,
+            driver,
+        });
+    }
+
+    (!interfaces.is_empty()).then_some(interfaces)
+}
+
+/// reads the speed the device is running at.
+///
+/// `sysfs` reports this in Mbps.
+#[tracing::instrument]
+async fn speed(path: &Path) -> Option<UsbSpeed> {
+    let speed = sysfs_value_opt::<String>(path.join("speed")).await?;
+
+    Some(match speed.as_str() {
+        "1.5" => UsbSpeed::Low,
+        "12" => UsbSpeed::Full,
+        "480" => UsbSpeed::High,
+        "5000" => UsbSpeed::Super,
+        "10000" => UsbSpeed::SuperPlus,
+        "20000" => UsbSpeed::SuperPlusGen2x2,
+        _ => UsbSpeed::Other(speed),
+    })
+}
+
+/// reads the device's max power draw, like `896mA`.
+#[tracing::instrument]
+async fn max_power(path: &Path) -> Option<u32> {
+    let max_power = sysfs_value_opt::<String>(path.join("bMaxPower")).await?;
+    max_power.trim_end_matches("mA").parse().ok()
+}
+
+/// checks whether the port the device is plugged into is user-accessible.
+#[tracing::instrument]
+async fn removability(path: &Path) -> Option<Removability> {
+    match sysfs_value_opt::<String>(path.join("removable"))
+        .await?
+        .as_str()
+    {
+        "removable" => Some(Removability::Removable),
+        "fixed" => Some(Removability::NonRemovable),
+        _ => None,
+    }
+}
+
+/// on Linux, this grabs the class codes for our devices.
+///
+/// `prefix` picks which class to read: `bDevice` for the device, or
+/// `bInterface` for one of its interfaces.
+#[tracing::instrument]
+async fn usb_class(path: &Path, prefix: &str) -> Option<String> {
+    // read the files
+    let Ok((class, subclass)) = try_join!(
+        async_fs::read_to_string(path.join(format!("{prefix}Class"))),
+        async_fs::read_to_string(path.join(format!("{prefix}SubClass"))),
+    ) else {
+        tracing::warn!(
+            "Failed to find device class/subclass! Yielding 'Unknown' for these values."
+        );
+        return None;
+    };
+
+    let (class, subclass) = (class.trim(), subclass.trim());
+
+    // try grabbing a class
+    // look it up
+    if let (Ok(parsed_class), Ok(parsed_subclass)) = (
+        u8::from_str_radix(class, 16),
+        u8::from_str_radix(subclass, 16),
+    ) {
+        if let Some(c) = usb_ids::SubClass::from_cid_scid(parsed_class, parsed_subclass) {
+            return Some(format!("{} ({})", c.class().name(), c.name()));
+        }
+    }
+
+    // return the raw ID if we must
+    return Some(format!("{} ({})", class, subclass));
+}
+
+#[tracing::instrument]
+pub(crate) async fn usb_vendor_and_id(path: &Path) -> (Option<String>, Option<String>) {
+    use usb_ids::FromId as _;
+
+    // look for human-readable string repr. these come from the device's own
+    // string descriptors, and not every device has them
+    let (manufacturer, product) = futures::join! {
+        sysfs_value_opt::<String>(path.join("manufacturer")),
+        sysfs_value_opt::<String>(path.join("product")),
+    };
+    let (manufacturer, product) = (
+        manufacturer.filter(|s| !s.is_empty()),
+        product.filter(|s| !s.is_empty()),
+    );
+
+    // return human-readable strings if they exist for us!
+    if let (Some(vendor_str), Some(product_str)) = (&manufacturer, &product) {
+        tracing::debug!(
+            "oh cool, we got human readable strings! vendor: `{vendor_str}`. product: {product_str}."
+        );
+        return (manufacturer, product);
+    }
+
+    // otherwise, grab lame number
+    let (vend, prod) = futures::join! {
+        sysfs_value::<String>(path.join("idVendor")),
+        sysfs_value::<String>(path.join("idProduct")),
+    };
+
+    // convert them if they exist.
+    let (mut db_vend, mut db_prod) = (None, None);
+    if let (Ok(ref num_vend), Ok(ref num_prod)) = (&vend, &prod) {
+        tracing::debug!("lame numbers {}; {}", num_vend, num_prod);
+
+        // try to convert them to human readable names
+        let (parsed_vend, parsed_prod) = (
+            u16::from_str_radix(num_vend, 16),
+            u16::from_str_radix(num_prod, 16),
+        );
+
+        if let (Ok(parsed_vend), Ok(parsed_prod)) = (parsed_vend, parsed_prod) {
+            if let Some(dev) = usb_ids::Device::from_vid_pid(parsed_vend, parsed_prod) {
+                db_vend = Some(dev.vendor().name().to_string());
+                db_prod = Some(dev.name().to_string());
+            }
+            // sometimes, the linux usb list is incomplete. let's try just the vendor!
+            else if let Some(readable_vend) = usb_ids::Vendor::from_id(parsed_vend) {
+                db_vend = Some(readable_vend.name().to_string());
+            }
+        }
+    }
+
+    // otherwise, report errors
+    if let Err(prod_err) = &prod {
+        tracing::warn!(
+            "Couldn't find product for USB device at path {path:?}. (error: {prod_err})"
+        );
+    }
+    if let Err(vend_err) = &vend {
+        tracing::warn!("Couldn't find vendor for USB device at path {path:?}. (error: {vend_err})");
+    }
+
+    // prefer the device's own strings, then the database's names
+    (manufacturer.or(db_vend), product.or(db_prod))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn check_usb_linux() {
+        let all = all(usb_path()).await.unwrap();
+
+        // the root hub and interfaces aren't devices of their own
+        assert_eq!(all.len(), 2);
+
+        let enclosure = all
+            .iter()
+            .find(|info| info.id().as_deref() == Some("ASM1153E"))
+            .unwrap();
+
+        // these names come from the device's string descriptors
+        assert_eq!(enclosure.vendor_id().unwrap(), "ASMedia");
+        assert_eq!(enclosure.bus(), ComponentBus::Usb);
+
+        // its class is defined by its interface
+        assert_eq!(enclosure.class().unwrap(), "Mass Storage (SCSI)");
+
+        let ComponentDescription::UsbDescription(desc) = enclosure.desc() else {
+            panic!("wrong desc");
+        };
+
+        assert_eq!(desc.port_path.unwrap(), "2-1");
+        assert_eq!(desc.speed.unwrap(), UsbSpeed::Super);
+        assert_eq!(desc.version.unwrap(), "3.00");
+        assert_eq!(desc.max_power, Some(896));
+        assert_eq!(desc.removability.unwrap(), Removability::Removable);
+        assert_eq!(desc.configuration, Some(1));
+
+        assert_eq!(
+            desc.interfaces.unwrap(),
+            vec![UsbInterface {
+                number: Some(0),
+                class: Some("Mass Storage (SCSI)".into()),
+                driver: Some("uas".into()),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn check_usb_linux_ids() {
+        // this one has no string descriptors, so we use the usb id database
+        let info = one(&usb_path().join("2-2")).await;
+
+        assert_eq!(info.vendor_id().unwrap(), "Realtek Semiconductor Corp.");
+        assert_eq!(info.id().unwrap(), "RTL8153 Gigabit Ethernet Adapter");
+
+        let ComponentDescription::UsbDescription(desc) = info.desc() else {
+            panic!("wrong desc");
+        };
+
+        assert_eq!(desc.configuration_count, Some(2));
+        assert_eq!(desc.interfaces.unwrap()[0].driver.as_deref(), Some("r8152"));
+    }
+
+    #[tracing::instrument]
+    fn usb_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!(
+            "{root}/tests/assets/linux/sysfs/sys/bus/usb/devices"
+        ))
+    }
+}
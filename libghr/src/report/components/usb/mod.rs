@@ -0,0 +1,83 @@
+//! i am usb
+
+use crate::prelude::internal::*;
+
+#[cfg(target_os = "linux")]
+pub(crate) mod linux;
+
+#[cfg(target_os = "windows")]
+mod windows;
+
+/// A description of a USB device.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct UsbDescription {
+    /// Where the device is plugged in, like `1-2.3` (bus 1, port 2, then
+    /// port 3 on the hub plugged in there).
+    pub port_path: Option<String>,
+
+    /// The speed the device negotiated with its port.
+    pub speed: Option<UsbSpeed>,
+
+    /// The version of USB the device supports, like `3.20`.
+    pub version: Option<String>,
+
+    /// The most power the device may draw from the bus, in milliamps.
+    pub max_power: Option<u32>,
+
+    /// Whether the device can be unplugged.
+    pub removability: Option<Removability>,
+
+    /// The active configuration's number.
+    pub configuration: Option<u8>,
+
+    /// How many configurations the device offers.
+    pub configuration_count: Option<u8>,
+
+    /// The interfaces of the active configuration.
+    pub interfaces: Option<Vec<UsbInterface>>,
+}
+
+/// One interface (function) of a USB device, like the storage part of a
+/// storage enclosure.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct UsbInterface {
+    /// The interface's number within its configuration.
+    pub number: Option<u8>,
+
+    /// The interface's class, like `Mass Storage (SCSI)`.
+    pub class: Option<String>,
+
+    /// The driver bound to this interface.
+    pub driver: Option<String>,
+}
+
+/// A USB signaling speed.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum UsbSpeed {
+    /// 1.5 Mbps (USB 1.0).
+    Low,
+    /// 12 Mbps (USB 1.1).
+    Full,
+    /// 480 Mbps (USB 2.0).
+    High,
+    /// 5 Gbps (USB 3.2 Gen 1).
+    Super,
+    /// 10 Gbps (USB 3.2 Gen 2).
+    SuperPlus,
+    /// 20 Gbps (USB 3.2 Gen 2x2).
+    SuperPlusGen2x2,
+    /// Some other speed, in Mbps.
+    Other(String),
+}
+
+#[tracing::instrument]
+pub async fn get() -> GhrResult<Vec<ComponentInfo>> {
+    #[cfg(target_os = "windows")]
+    return windows::get().await;
+
+    #[cfg(target_os = "linux")]
+    return linux::get().await;
+}
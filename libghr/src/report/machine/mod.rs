@@ -17,6 +17,19 @@ pub struct MachineInfo {
     vendor: Option<String>,
     /// This machine's model number.
     model: Option<String>,
+    /// The product line this machine belongs to, like `ThinkPad X1 Carbon Gen 9`.
+    family: Option<String>,
+    /// The machine's Stock Keeping Unit, which identifies its exact configuration.
+    sku: Option<String>,
+    /// The version (or revision) of this machine's model.
+    version: Option<String>,
+    /// The machine's serial number.
+    ///
+    /// This is redacted unless the user asks otherwise.
+    serial: Option<Sensitive>,
+
+    /// Info about the computer's motherboard.
+    board: BoardInfo,
 
     /// Info about the computer's BIOS.
     bios: BiosInfo,
@@ -35,45 +48,87 @@ impl MachineInfo {
     #[tracing::instrument(skip(machine_id))]
     #[cfg(target_os = "linux")]
     pub async fn new(machine_id: MachineIdentifier) -> Self {
-        use std::path::PathBuf;
-
         // the path to the machine info on linux
-        let sysfs_info = PathBuf::from("/sys/devices/virtual/dmi/id");
+        Self::from_dmi(machine_id, "/sys/devices/virtual/dmi/id").await
+    }
+
+    /// reads machine info from the `dmi` directory at `sysfs_info`.
+    #[tracing::instrument(skip(machine_id))]
+    #[cfg(target_os = "linux")]
+    async fn from_dmi(
+        machine_id: MachineIdentifier,
+        sysfs_info: impl AsRef<std::path::Path> + std::fmt::Debug,
+    ) -> Self {
+        let sysfs_info = sysfs_info.as_ref();
+        let value = |name: &str| sysfs_value_opt::<String>(sysfs_info.join(name));
+
+        // serial numbers are only readable by root
+        let serial = |name: &str| {
+            let path = sysfs_info.join(name);
+            async move { sensitive_serial(sysfs_value_opt::<String>(path).await) }
+        };
 
         // read the vendor + model from `sysfs`
-        let vendor = sysfs_value(sysfs_info.join("sys_vendor")).await.ok();
-        let model = sysfs_value("product_name").await.ok();
+        let (vendor, model, family, sku, version, serial_number) = futures::join! {
+            value("sys_vendor"),
+            value("product_name"),
+            value("product_family"),
+            value("product_sku"),
+            value("product_version"),
+            serial("product_serial"),
+        };
+
+        // motherboard info
+        let (board_vendor, board_name, board_version, board_serial) = futures::join! {
+            value("board_vendor"),
+            value("board_name"),
+            value("board_version"),
+            serial("board_serial"),
+        };
+        let board = BoardInfo {
+            vendor: board_vendor,
+            name: board_name,
+            version: board_version,
+            serial: board_serial,
+        };
 
         // chassis info
+        let (chassis_type, chassis_vendor, chassis_version, chassis_serial) = futures::join! {
+            sysfs_value_opt::<u8>(sysfs_info.join("chassis_type")),
+            value("chassis_vendor"),
+            value("chassis_version"),
+            serial("chassis_serial"),
+        };
         let chassis = ChassisInfo {
-            kind: sysfs_value::<String>(sysfs_info.join("chassis_type"))
-                .await
-                .ok(),
-            vendor: sysfs_value::<String>(sysfs_info.join("chassis_vendor"))
-                .await
-                .ok(),
-            version: sysfs_value::<String>(sysfs_info.join("chassis_version"))
-                .await
-                .ok(),
+            kind: chassis_type.map(ChassisKind::from_smbios),
+            vendor: chassis_vendor,
+            version: chassis_version,
+            serial: chassis_serial,
         };
 
         // bios info
+        let (bios_vendor, bios_version, bios_date, bios_release) = futures::join! {
+            value("bios_vendor"),
+            value("bios_version"),
+            value("bios_date"),
+            value("bios_release"),
+        };
         let bios = BiosInfo {
-            vendor: sysfs_value::<String>(sysfs_info.join("bios_vendor"))
-                .await
-                .ok(),
-            version: sysfs_value::<String>(sysfs_info.join("bios_version"))
-                .await
-                .ok(),
-            date: sysfs_value::<String>(sysfs_info.join("bios_date"))
-                .await
-                .ok()
+            vendor: bios_vendor,
+            version: bios_version,
+            date: bios_date
                 .and_then(|date_str| chrono::NaiveDate::parse_from_str(&date_str, "%m/%d/%Y").ok()),
+            release: bios_release,
         };
 
         Self {
             vendor,
             model,
+            family,
+            sku,
+            version,
+            serial: serial_number,
+            board,
             bios,
             chassis,
             hash: machine_id,
@@ -87,11 +142,20 @@ impl MachineInfo {
         let vendor = None;
         let model = None;
 
+        // motherboard info
+        let board = BoardInfo {
+            vendor: None,
+            name: None,
+            version: None,
+            serial: None,
+        };
+
         // chassis info
         let chassis = ChassisInfo {
             kind: None,
             vendor: None,
             version: None,
+            serial: None,
         };
 
         // bios info
@@ -99,11 +163,17 @@ impl MachineInfo {
             vendor: None,
             version: None,
             date: None,
+            release: None,
         };
 
         Self {
             vendor,
             model,
+            family: None,
+            sku: None,
+            version: None,
+            serial: None,
+            board,
             bios,
             chassis,
             hash: machine_id,
@@ -111,6 +181,58 @@ impl MachineInfo {
     }
 }
 
+impl MachineInfo {
+    /// applies the redaction policy to the machine's serial numbers.
+    #[tracing::instrument(skip(self))]
+    pub(crate) fn redact(&mut self, redaction: Redaction) {
+        [
+            &mut self.serial,
+            &mut self.board.serial,
+            &mut self.chassis.serial,
+        ]
+        .into_iter()
+        .flatten()
+        .for_each(|serial| serial.redact(redaction));
+    }
+}
+
+/// wraps a serial number from smbios, skipping the placeholders that vendors
+/// leave in, like `To Be Filled By O.E.M.`.
+#[tracing::instrument(skip(serial))]
+fn sensitive_serial(serial: Option<String>) -> Option<Sensitive> {
+    const PLACEHOLDERS: &[&str] = &[
+        "",
+        "0",
+        "None",
+        "Default string",
+        "System Serial Number",
+        "Chassis Serial Number",
+        "To Be Filled By O.E.M.",
+        "To be filled by O.E.M.",
+        "Not Specified",
+    ];
+
+    serial
+        .filter(|serial| !PLACEHOLDERS.contains(&serial.as_str()))
+        .map(Sensitive::new)
+}
+
+/// Information about the motherboard (or "baseboard").
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct BoardInfo {
+    /// The creator of the motherboard.
+    pub vendor: Option<String>,
+    /// The motherboard's model, like `20XWCTO1WW` or `PRIME X570-PRO`.
+    pub name: Option<String>,
+    /// The motherboard's revision.
+    pub version: Option<String>,
+    /// The motherboard's serial number.
+    ///
+    /// This is redacted unless the user asks otherwise.
+    pub serial: Option<Sensitive>,
+}
+
 /// Information about the chassis.
 #[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
 #[non_exhaustive]
@@ -118,10 +240,108 @@ pub struct ChassisInfo {
     /// The kind of chassis this machine is inside.
     ///
     /// Examples include "Desktop" or "Tablet".
-    pub kind: Option<String>,
+    pub kind: Option<ChassisKind>,
     /// The creator of the system chassis.
     pub vendor: Option<String>,
     pub version: Option<String>,
+    /// The chassis' serial number.
+    ///
+    /// This is redacted unless the user asks otherwise.
+    pub serial: Option<Sensitive>,
+}
+
+/// A kind of chassis, from the SMBIOS chassis type table.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum ChassisKind {
+    Other,
+    Unknown,
+    Desktop,
+    LowProfileDesktop,
+    PizzaBox,
+    MiniTower,
+    Tower,
+    Portable,
+    Laptop,
+    Notebook,
+    HandHeld,
+    DockingStation,
+    AllInOne,
+    SubNotebook,
+    SpaceSaving,
+    LunchBox,
+    MainServer,
+    Expansion,
+    SubChassis,
+    BusExpansion,
+    Peripheral,
+    Raid,
+    RackMount,
+    SealedCasePc,
+    MultiSystem,
+    CompactPci,
+    AdvancedTca,
+    Blade,
+    BladeEnclosure,
+    Tablet,
+    Convertible,
+    Detachable,
+    IotGateway,
+    EmbeddedPc,
+    MiniPc,
+    StickPc,
+}
+
+impl ChassisKind {
+    /// converts an smbios chassis type number into a kind.
+    ///
+    /// the top bit says whether the chassis has a lock, so we ignore it.
+    #[tracing::instrument]
+    #[cfg(target_os = "linux")]
+    fn from_smbios(kind: u8) -> Self {
+        match kind & 0x7f {
+            1 => Self::Other,
+            3 => Self::Desktop,
+            4 => Self::LowProfileDesktop,
+            5 => Self::PizzaBox,
+            6 => Self::MiniTower,
+            7 => Self::Tower,
+            8 => Self::Portable,
+            9 => Self::Laptop,
+            10 => Self::Notebook,
+            11 => Self::HandHeld,
+            12 => Self::DockingStation,
+            13 => Self::AllInOne,
+            14 => Self::SubNotebook,
+            15 => Self::SpaceSaving,
+            16 => Self::LunchBox,
+            17 => Self::MainServer,
+            18 => Self::Expansion,
+            19 => Self::SubChassis,
+            20 => Self::BusExpansion,
+            21 => Self::Peripheral,
+            22 => Self::Raid,
+            23 => Self::RackMount,
+            24 => Self::SealedCasePc,
+            25 => Self::MultiSystem,
+            26 => Self::CompactPci,
+            27 => Self::AdvancedTca,
+            28 => Self::Blade,
+            29 => Self::BladeEnclosure,
+            30 => Self::Tablet,
+            31 => Self::Convertible,
+            32 => Self::Detachable,
+            33 => Self::IotGateway,
+            34 => Self::EmbeddedPc,
+            35 => Self::MiniPc,
+            36 => Self::StickPc,
+            2 => Self::Unknown,
+            other => {
+                tracing::debug!("Unknown SMBIOS chassis type `{other}`.");
+                Self::Unknown
+            }
+        }
+    }
 }
 
 /// Information about the system BIOS.
@@ -134,6 +354,8 @@ pub struct BiosInfo {
     pub version: Option<String>,
     /// The date the BIOS was compiled.
     pub date: Option<chrono::NaiveDate>,
+    /// The BIOS's major and minor release, like `1.31`.
+    pub release: Option<String>,
 }
 
 /// A unique identifier for each computer.
@@ -172,3 +394,56 @@ impl Report {
         Ok(MachineInfo::new(MachineIdentifier::new_true()?).await)
     }
 }
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn check_machine_info() {
+        let info = MachineInfo::from_dmi(MachineIdentifier::new_random(), dmi_path()).await;
+
+        assert_eq!(info.vendor.as_deref(), Some("LENOVO"));
+        assert_eq!(info.model.as_deref(), Some("20XWCTO1WW"));
+        assert_eq!(info.family.as_deref(), Some("ThinkPad X1 Carbon Gen 9"));
+        assert_eq!(
+            info.sku.as_deref(),
+            Some("LENOVO_MT_20XW_BU_Think_FM_ThinkPad X1 Carbon Gen 9")
+        );
+        assert_eq!(info.board.name.as_deref(), Some("20XWCTO1WW"));
+        assert_eq!(info.bios.release.as_deref(), Some("1.31"));
+        assert_eq!(info.chassis.kind, Some(ChassisKind::Notebook));
+
+        // placeholder serials are skipped
+        assert_eq!(info.serial, Some(Sensitive::new("PF3ABCDE")));
+        assert!(info.board.serial.is_some());
+        assert!(info.chassis.serial.is_none());
+
+        // and the others are redacted by default
+        let mut info = info;
+        info.redact(Redaction::default());
+        assert_eq!(info.serial, Some(Sensitive::Redacted));
+        assert_eq!(info.board.serial, Some(Sensitive::Redacted));
+    }
+
+    #[test]
+    fn check_chassis_kind() {
+        assert_eq!(ChassisKind::from_smbios(3), ChassisKind::Desktop);
+        assert_eq!(ChassisKind::from_smbios(23), ChassisKind::RackMount);
+        // locked convertible
+        assert_eq!(
+            ChassisKind::from_smbios(0x80 | 31),
+            ChassisKind::Convertible
+        );
+        assert_eq!(ChassisKind::from_smbios(99), ChassisKind::Unknown);
+    }
+
+    #[tracing::instrument]
+    fn dmi_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!(
+            "{root}/tests/assets/linux/sysfs/sys/devices/virtual/dmi/id"
+        ))
+    }
+}
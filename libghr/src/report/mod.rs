@@ -1,13 +1,17 @@
 pub mod components;
 pub mod machine;
 pub mod os;
+pub mod privacy;
 pub mod system_config;
 pub(crate) mod util;
 
 use machine::MachineIdentifier;
+use privacy::Redaction;
 
 use crate::prelude::internal::*;
 
+use std::time::Duration;
+
 #[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
 #[non_exhaustive]
 pub struct Report {
@@ -22,28 +26,83 @@ pub struct Report {
     pub sys_conf: SystemConfInfo,
 }
 
-impl Report {
+/// Options for creating a [`Report`].
+#[derive(Clone, Debug, Default)]
+pub struct ReportBuilder {
+    redaction: Redaction,
+    power_sampling: Option<Duration>,
+}
+
+impl ReportBuilder {
+    /// Sets how identifying values, like MAC addresses and serial numbers,
+    /// are placed into the report.
+    ///
+    /// By default, they're redacted.
+    pub fn redaction(mut self, redaction: Redaction) -> Self {
+        self.redaction = redaction;
+        self
+    }
+
+    /// Sets how long to measure power draw for, up to
+    /// [`system_config::powercap::MAX_POWER_SAMPLING`].
+    ///
+    /// Measuring makes the report take that much longer to create, so it's
+    /// off (`None`) by default.
+    pub fn power_sampling(mut self, window: Option<Duration>) -> Self {
+        self.power_sampling = window;
+        self
+    }
+
+    /// Attempts to assemble a new `Report` with these options.
     #[tracing::instrument]
-    /// Attempts to assemble a new `Report`.
-    pub async fn new() -> Result<Self, GhrError> {
+    pub async fn build(self) -> Result<Report, GhrError> {
         // get the machine identifier (used to uniquely identify the report)
         let machine_id = MachineIdentifier::new_random();
 
         let (os, machine, components, sys_conf) = futures::join! {
-            Self::os_info(),
+            Report::os_info(),
             MachineInfo::new(machine_id),
             components::get_components(),
-            SystemConfInfo::new()
+            SystemConfInfo::new(self.power_sampling)
         };
 
         tracing::debug!("Finished report creation!");
 
-        Ok(Self {
+        let mut report = Report {
             os: os?,
             machine, // TODO: use the real one
             components: components?,
             sys_conf,
-        })
+        };
+        report.redact(self.redaction);
+
+        Ok(report)
+    }
+}
+
+impl Report {
+    #[tracing::instrument]
+    /// Attempts to assemble a new `Report` with the default options.
+    pub async fn new() -> Result<Self, GhrError> {
+        Self::builder().build().await
+    }
+
+    /// Creates a builder to assemble a `Report` with custom options.
+    pub fn builder() -> ReportBuilder {
+        ReportBuilder::default()
+    }
+
+    /// applies the redaction policy to each identifying value in the report.
+    #[tracing::instrument(skip(self))]
+    fn redact(&mut self, redaction: Redaction) {
+        self.machine.redact(redaction);
+        self.components
+            .iter_mut()
+            .for_each(|component| component.redact(redaction));
+
+        if let Some(cmdline) = &mut self.sys_conf.kernel.cmdline {
+            cmdline.redact(redaction);
+        }
     }
 
     /// Returns the CPUs attached to this report.
@@ -23,6 +23,108 @@ pub struct OperatingSystemInfo {
 
     /// Additional stuff that's kinda operating-system dependent.
     other: BTreeMap<String, String>,
+
+    /// The identifiers from the distro's `os-release` file.
+    os_release: Option<OsRelease>,
+
+    /// The program that started the rest of the system, like systemd.
+    init_system: Option<InitSystem>,
+
+    /// The C library the system uses, found by its dynamic loader.
+    libc: Option<Libc>,
+
+    /// The display server the session runs on, like Wayland.
+    display_server: Option<DisplayServer>,
+
+    /// The session's desktop environments, like `["ubuntu", "GNOME"]`.
+    desktop: Option<Vec<String>>,
+
+    /// The kernel build options that affect how hardware behaves, like
+    /// `CONFIG_HZ` and `CONFIG_PREEMPT_DYNAMIC`.
+    ///
+    /// Only a few options are kept. The full config is thousands of lines.
+    kernel_config: Option<BTreeMap<String, String>>,
+}
+
+/// The identifiers from a Linux distribution's `os-release` file.
+///
+/// See `os-release(5)` for what each one means.
+#[derive(
+    Clone, Debug, Default, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript,
+)]
+#[non_exhaustive]
+pub struct OsRelease {
+    /// The distro's name, like `Fedora Linux`.
+    pub name: Option<String>,
+
+    /// The distro's ID, like `fedora`.
+    pub id: Option<String>,
+
+    /// The IDs of distros this one is based on, closest first, like
+    /// `["rhel", "fedora"]` on CentOS.
+    pub id_like: Option<Vec<String>>,
+
+    /// The distro's version, like `40 (Workstation Edition)`.
+    pub version: Option<String>,
+
+    /// The distro's version number, like `40` or `24.04`.
+    pub version_id: Option<String>,
+
+    /// The version's codename, like `noble`.
+    pub version_codename: Option<String>,
+
+    /// The edition of the distro, like `Workstation Edition`.
+    pub variant: Option<String>,
+
+    /// The edition's ID, like `workstation`.
+    pub variant_id: Option<String>,
+
+    /// The build of the system image, for distros that have them.
+    pub build_id: Option<String>,
+
+    /// When the distro version stops getting updates, like `2025-05-13`.
+    pub support_end: Option<String>,
+}
+
+/// The program that starts the rest of the system.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum InitSystem {
+    Systemd,
+    OpenRc,
+    Runit,
+    S6,
+    Dinit,
+    SysVinit,
+    Other(String),
+}
+
+/// The C standard library the system uses.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct Libc {
+    /// Which C library it is.
+    pub kind: LibcKind,
+
+    /// The library's version, like `2.39`.
+    pub version: Option<String>,
+}
+
+/// A C standard library.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum LibcKind {
+    Glibc,
+    Musl,
+}
+
+/// The display server used by the desktop session.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum DisplayServer {
+    X11,
+    Wayland,
+    Other(String),
 }
 
 impl OperatingSystemInfo {
@@ -67,6 +169,52 @@ impl OperatingSystemInfo {
     pub fn other(&self) -> BTreeMap<String, String> {
         self.other.clone()
     }
+
+    /// Grabs the identifiers from the distro's `os-release` file.
+    ///
+    /// These are only available on Linux.
+    #[tracing::instrument(skip(self))]
+    pub fn os_release(&self) -> Option<OsRelease> {
+        self.os_release.clone()
+    }
+
+    /// Grabs the init system.
+    ///
+    /// ex: `InitSystem::Systemd`
+    #[tracing::instrument(skip(self))]
+    pub fn init_system(&self) -> Option<InitSystem> {
+        self.init_system.clone()
+    }
+
+    /// Grabs the C standard library the system uses.
+    #[tracing::instrument(skip(self))]
+    pub fn libc(&self) -> Option<Libc> {
+        self.libc.clone()
+    }
+
+    /// Grabs the display server used by the desktop session, if the report
+    /// was made from one.
+    #[tracing::instrument(skip(self))]
+    pub fn display_server(&self) -> Option<DisplayServer> {
+        self.display_server.clone()
+    }
+
+    /// Grabs the desktop environments in use, most specific first.
+    ///
+    /// ex: `["ubuntu", "GNOME"]`
+    #[tracing::instrument(skip(self))]
+    pub fn desktop(&self) -> Option<Vec<String>> {
+        self.desktop.clone()
+    }
+
+    /// Grabs the options the kernel was built with, like `CONFIG_HZ` =
+    /// `1000`.
+    ///
+    /// Options that aren't set are left out.
+    #[tracing::instrument(skip(self))]
+    pub fn kernel_config(&self) -> Option<BTreeMap<String, String>> {
+        self.kernel_config.clone()
+    }
 }
 
 #[cfg(test)]
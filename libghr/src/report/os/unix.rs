@@ -1,8 +1,42 @@
 use crate::prelude::internal::*;
 
 use std::collections::BTreeMap;
+use std::io::Read as _;
 use std::path::Path;
 
+/// the kernel config options we report.
+///
+/// a full config is thousands of options, and most don't change how the
+/// hardware behaves. names ending in `_` match every option starting with
+/// them.
+const KERNEL_CONFIG_OPTIONS: &[&str] = &[
+    // timer and scheduling
+    "CONFIG_HZ",
+    "CONFIG_HZ_",
+    "CONFIG_NO_HZ_",
+    "CONFIG_PREEMPT",
+    "CONFIG_PREEMPT_",
+    // security
+    "CONFIG_LSM",
+    "CONFIG_SECURITY_LOCKDOWN_LSM",
+    "CONFIG_LOCK_DOWN_",
+    "CONFIG_SECURITY_SELINUX",
+    "CONFIG_SECURITY_APPARMOR",
+    "CONFIG_MODULE_SIG",
+    "CONFIG_MODULE_SIG_FORCE",
+    // numa and memory
+    "CONFIG_NUMA",
+    "CONFIG_NUMA_BALANCING",
+    "CONFIG_NODES_SHIFT",
+    "CONFIG_NR_CPUS",
+    "CONFIG_TRANSPARENT_HUGEPAGE",
+    "CONFIG_TRANSPARENT_HUGEPAGE_",
+    // power management
+    "CONFIG_CPU_FREQ_DEFAULT_GOV_",
+    "CONFIG_X86_AMD_PSTATE",
+    "CONFIG_X86_INTEL_PSTATE",
+];
+
 impl Report {
     /// Grabs operating system info for the hardware report.
     #[tracing::instrument]
@@ -33,48 +67,58 @@ impl Report {
             ),
         ]);
 
-        // we'll also grab the "real" name of the system
-        let distro_name = OperatingSystemInfo::find_distro_name("/etc/os-release").await;
+        let root = Path::new("/");
+        let (os_release, init_system, libc, kernel_config) = futures::join! {
+            OperatingSystemInfo::os_release_at(root),
+            OperatingSystemInfo::find_init_system(root),
+            OperatingSystemInfo::find_libc(root),
+            OperatingSystemInfo::find_kernel_config(root, &release),
+        };
+        let env = |key: &str| std::env::var(key).ok();
 
         Ok(OperatingSystemInfo {
             name,
-            distro_name,
+            // we'll also grab the "real" name of the system
+            distro_name: os_release.as_ref().and_then(Self::distro_name),
             version: release,
             architecture: arch,
             other: other_info,
+            os_release: os_release.as_ref().map(OperatingSystemInfo::os_release_ids),
+            init_system,
+            libc: OperatingSystemInfo::system_libc(libc, OperatingSystemInfo::running_libc()),
+            display_server: OperatingSystemInfo::find_display_server(env),
+            desktop: OperatingSystemInfo::find_desktop(env),
+            kernel_config,
         })
     }
+
+    /// finds the distro's name, like "Fedora Linux 40 (Forty)".
+    #[tracing::instrument]
+    fn distro_name(os_release: &BTreeMap<String, String>) -> Option<String> {
+        os_release
+            .get("PRETTY_NAME")
+            .or_else(|| os_release.get("NAME"))
+            .cloned()
+    }
 }
 
 impl OperatingSystemInfo {
-    /// finds the `distro_name` field, like "Fedora Linux 40 (Forty)".
+    /// reads the `os-release` file under `root`.
+    ///
+    /// it's usually in `/etc`, but distros can leave it in `/usr/lib` alone.
     #[tracing::instrument]
-    async fn find_distro_name<P: AsRef<Path> + std::fmt::Debug>(
-        os_release_path: P,
-    ) -> Option<String> {
-        // read `os-release` from the given path
-        let os_release = Self::read_os_release(os_release_path).await?;
-
-        // find the `NAME="Distro Name"` part of the file
-        let line = Self::os_release_field(os_release, "PRETTY_NAME").await?;
-
-        // clean it up
-        let Some(quoted) = line.split("=").last() else {
-            tracing::warn!("Line didn't have an equals sign! (line: `{line}`).");
-            return None;
-        };
-        let whitespaced = quoted.replace('\"', "");
-
-        // send it off
-        Some(whitespaced.trim().to_string())
+    async fn os_release_at(root: &Path) -> Option<BTreeMap<String, String>> {
+        match Self::read_os_release(root.join("etc/os-release")).await {
+            Some(os_release) => Some(os_release),
+            None => Self::read_os_release(root.join("usr/lib/os-release")).await,
+        }
     }
 
-    /// reads the `os-release` file given and returns it, in entirety, as a
-    /// string if found.
+    /// reads the `os-release` file given and parses its fields, if found.
     #[tracing::instrument]
     async fn read_os_release<P: AsRef<Path> + std::fmt::Debug>(
         os_release_path: P,
-    ) -> Option<String> {
+    ) -> Option<BTreeMap<String, String>> {
         let os_release_path = os_release_path.as_ref();
 
         let Some(os_release) = sysfs_value_opt::<String>(os_release_path).await else {
@@ -85,20 +129,259 @@ impl OperatingSystemInfo {
             return None;
         };
 
-        Some(os_release)
+        Some(Self::parse_os_release(&os_release))
     }
 
-    /// finds some key in the given `os-release` file.
+    /// parses an `os-release` file into its keys and values.
     ///
-    /// if found, it'll return it as a string.
+    /// it's a list of shell-style assignments, like `NAME="Fedora Linux"`.
+    /// values can be quoted (with escapes inside) or bare, and can contain
+    /// `=` themselves.
+    #[tracing::instrument(skip(os_release))]
+    fn parse_os_release(os_release: &str) -> BTreeMap<String, String> {
+        os_release
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let Some((key, value)) = line.split_once('=') else {
+                    tracing::warn!("Line didn't have an equals sign! (line: `{line}`).");
+                    return None;
+                };
+
+                Some((key.trim().to_string(), Self::unquote(value.trim())))
+            })
+            .collect()
+    }
+
+    /// removes the quotes around an `os-release` value, and the escapes
+    /// inside double quotes.
     #[tracing::instrument]
-    async fn os_release_field(os_release: String, key: &str) -> Option<String> {
-        let Some(field) = os_release.lines().find(|l| l.contains(&format!("{key}="))) else {
-            tracing::warn!("Failed to find line with key `{key}` in `os-release` file.");
-            return None;
+    fn unquote(value: &str) -> String {
+        if let Some(inner) = value
+            .strip_prefix('\'')
+            .and_then(|value| value.strip_suffix('\''))
+        {
+            return inner.to_string();
+        }
+
+        let Some(inner) = value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+        else {
+            return value.to_string();
+        };
+
+        // only `\"`, `\\`, `\$`, and `` \` `` are escapes
+        let mut unquoted = String::with_capacity(inner.len());
+        let mut chars = inner.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, chars.peek()) {
+                ('\\', Some(next @ ('"' | '\\' | '$' | '`'))) => {
+                    unquoted.push(*next);
+                    chars.next();
+                }
+                _ => unquoted.push(c),
+            }
+        }
+        unquoted
+    }
+
+    /// picks the identifiers out of a parsed `os-release` file.
+    #[tracing::instrument(skip(os_release))]
+    fn os_release_ids(os_release: &BTreeMap<String, String>) -> OsRelease {
+        // empty values mean the same thing as missing ones
+        let field = |key: &str| os_release.get(key).filter(|v| !v.is_empty()).cloned();
+
+        OsRelease {
+            name: field("NAME"),
+            id: field("ID"),
+            id_like: field("ID_LIKE").map(|ids| ids.split_whitespace().map(String::from).collect()),
+            version: field("VERSION"),
+            version_id: field("VERSION_ID"),
+            version_codename: field("VERSION_CODENAME"),
+            variant: field("VARIANT"),
+            variant_id: field("VARIANT_ID"),
+            build_id: field("BUILD_ID"),
+            support_end: field("SUPPORT_END"),
+        }
+    }
+
+    /// finds the init system by looking at the first process under `root`.
+    #[tracing::instrument]
+    async fn find_init_system(root: &Path) -> Option<InitSystem> {
+        let (comm, openrc) = futures::join! {
+            sysfs_value_opt::<String>(root.join("proc/1/comm")),
+            async_fs::metadata(root.join("run/openrc")),
+        };
+
+        let init = match comm?.as_str() {
+            "systemd" => InitSystem::Systemd,
+            "openrc-init" => InitSystem::OpenRc,
+            "runit" => InitSystem::Runit,
+            "s6-svscan" => InitSystem::S6,
+            "dinit" => InitSystem::Dinit,
+            // sysvinit is just `init`, but openrc often runs on top of it
+            "init" if openrc.is_ok() => InitSystem::OpenRc,
+            "init" => InitSystem::SysVinit,
+            other => InitSystem::Other(other.to_string()),
+        };
+
+        Some(init)
+    }
+
+    /// finds the libc we're running with.
+    ///
+    /// glibc can tell us its version, but only when we're linked against it.
+    /// that's the libc `ghr` was built for, which isn't always the system's.
+    #[tracing::instrument]
+    fn running_libc() -> Option<Libc> {
+        #[cfg(all(target_os = "linux", target_env = "gnu"))]
+        {
+            // SAFETY: glibc returns a static, nul-terminated string
+            let version = unsafe { std::ffi::CStr::from_ptr(libc::gnu_get_libc_version()) };
+
+            Some(Libc {
+                kind: LibcKind::Glibc,
+                version: Some(version.to_string_lossy().to_string()),
+            })
+        }
+
+        #[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+        None
+    }
+
+    /// picks the system's libc, found on disk, taking the running libc's
+    /// version when they're the same kind.
+    #[tracing::instrument]
+    fn system_libc(on_disk: Option<Libc>, running: Option<Libc>) -> Option<Libc> {
+        let mut libc = on_disk?;
+        if let Some(running) = running.filter(|running| running.kind == libc.kind) {
+            libc.version = running.version;
+        }
+
+        Some(libc)
+    }
+
+    /// finds the system's libc under `root` by its dynamic loader, like
+    /// `/lib/ld-musl-x86_64.so.1`.
+    #[tracing::instrument]
+    async fn find_libc(root: &Path) -> Option<Libc> {
+        use futures::StreamExt as _;
+
+        for dir in ["lib", "lib64", "usr/lib", "usr/lib64"] {
+            let Ok(entries) = async_fs::read_dir(root.join(dir)).await else {
+                continue;
+            };
+
+            let names = entries
+                .filter_map(|entry| async move {
+                    Some(entry.ok()?.file_name().to_string_lossy().to_string())
+                })
+                .collect::<Vec<_>>()
+                .await;
+
+            let kind = names.iter().find_map(|name| {
+                if name.starts_with("ld-musl-") {
+                    Some(LibcKind::Musl)
+                } else if name.starts_with("ld-linux") {
+                    Some(LibcKind::Glibc)
+                } else {
+                    None
+                }
+            });
+
+            if let Some(kind) = kind {
+                return Some(Libc {
+                    kind,
+                    version: None,
+                });
+            }
+        }
+
+        None
+    }
+
+    /// finds the session's display server from its environment.
+    #[tracing::instrument(skip(env))]
+    fn find_display_server(env: impl Fn(&str) -> Option<String>) -> Option<DisplayServer> {
+        match env("XDG_SESSION_TYPE").as_deref() {
+            Some("wayland") => return Some(DisplayServer::Wayland),
+            Some("x11") => return Some(DisplayServer::X11),
+            Some(other @ "mir") => return Some(DisplayServer::Other(other.to_string())),
+            _ => (),
+        }
+
+        // the session type isn't always set, like under `sudo`. but the
+        // display variables usually make it through
+        if env("WAYLAND_DISPLAY").is_some() {
+            Some(DisplayServer::Wayland)
+        } else if env("DISPLAY").is_some() {
+            Some(DisplayServer::X11)
+        } else {
+            None
+        }
+    }
+
+    /// finds the session's desktop environments from its environment.
+    #[tracing::instrument(skip(env))]
+    fn find_desktop(env: impl Fn(&str) -> Option<String>) -> Option<Vec<String>> {
+        // `XDG_CURRENT_DESKTOP` is a list, like `ubuntu:GNOME`
+        let desktops = env("XDG_CURRENT_DESKTOP")
+            .or_else(|| env("DESKTOP_SESSION"))?
+            .split(':')
+            .filter(|desktop| !desktop.is_empty())
+            .map(String::from)
+            .collect::<Vec<_>>();
+
+        (!desktops.is_empty()).then_some(desktops)
+    }
+
+    /// reads the options the kernel was built with under `root`.
+    ///
+    /// `/proc/config.gz` only exists when the kernel was built to share it.
+    /// otherwise, many distros leave a copy in `/boot`.
+    #[tracing::instrument]
+    async fn find_kernel_config(root: &Path, release: &str) -> Option<BTreeMap<String, String>> {
+        let config = match async_fs::read(root.join("proc/config.gz")).await {
+            Ok(compressed) => {
+                let mut config = String::new();
+                flate2::read::GzDecoder::new(compressed.as_slice())
+                    .read_to_string(&mut config)
+                    .inspect_err(|e| tracing::debug!("Couldn't decompress the config. (err: {e})"))
+                    .ok()?;
+                config
+            }
+            Err(_) => async_fs::read_to_string(root.join(format!("boot/config-{release}")))
+                .await
+                .inspect_err(|e| tracing::debug!("Couldn't find the kernel's config. (err: {e})"))
+                .ok()?,
         };
 
-        Some(field.to_string())
+        Some(Self::parse_kernel_config(&config))
+    }
+
+    /// parses a kernel config, like `CONFIG_HZ=1000`, keeping only the
+    /// options in [`KERNEL_CONFIG_OPTIONS`].
+    ///
+    /// comments are skipped, along with the options that aren't set, which
+    /// are written as comments.
+    #[tracing::instrument(skip(config))]
+    fn parse_kernel_config(config: &str) -> BTreeMap<String, String> {
+        let wanted = |key: &str| {
+            KERNEL_CONFIG_OPTIONS
+                .iter()
+                .any(|option| key == *option || (option.ends_with('_') && key.starts_with(option)))
+        };
+
+        config
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                wanted(key).then(|| (key.to_string(), Self::unquote(value)))
+            })
+            .collect()
     }
 }
 
@@ -110,10 +393,139 @@ mod tests {
 
     #[tokio::test]
     async fn get_distro_name() {
-        let crate_root = env!("CARGO_MANIFEST_DIR");
-        let path = PathBuf::from(crate_root).join("tests/assets/linux/sysfs/etc/os-release");
-        let distro_name = OperatingSystemInfo::find_distro_name(path).await.unwrap();
+        let os_release = OperatingSystemInfo::os_release_at(&root_path())
+            .await
+            .unwrap();
+        let distro_name = Report::distro_name(&os_release).unwrap();
 
         assert_eq!(distro_name, "GHR Linux 40 (Forty)");
     }
+
+    #[tokio::test]
+    async fn check_os_release() {
+        let os_release = OperatingSystemInfo::os_release_at(&root_path())
+            .await
+            .unwrap();
+        let ids = OperatingSystemInfo::os_release_ids(&os_release);
+
+        assert_eq!(ids.id.as_deref(), Some("fedora"));
+        assert_eq!(ids.id_like, Some(vec!["rhel".into(), "centos".into()]));
+        assert_eq!(ids.version_id.as_deref(), Some("40"));
+        assert_eq!(ids.variant.as_deref(), Some("Workstation Edition"));
+        assert_eq!(ids.variant_id.as_deref(), Some("workstation"));
+        assert_eq!(ids.support_end.as_deref(), Some("2025-05-13"));
+
+        // empty values aren't kept
+        assert!(ids.version_codename.is_none());
+        assert!(ids.build_id.is_none());
+    }
+
+    #[test]
+    fn check_os_release_parsing() {
+        let os_release = OperatingSystemInfo::parse_os_release(
+            "# a comment\n\
+             PRETTY_NAME=\"Some=Linux \\\"Edge\\\"\"\n\
+             ID=some\n\
+             VARIANT='Server Edition'\n",
+        );
+
+        // values can have `=` and escaped quotes inside
+        assert_eq!(os_release["PRETTY_NAME"], "Some=Linux \"Edge\"");
+        assert_eq!(os_release["ID"], "some");
+        assert_eq!(os_release["VARIANT"], "Server Edition");
+        assert_eq!(os_release.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn check_system() {
+        let root = root_path();
+
+        let init = OperatingSystemInfo::find_init_system(&root).await;
+        assert_eq!(init, Some(InitSystem::Systemd));
+
+        let libc = OperatingSystemInfo::find_libc(&root).await;
+        assert_eq!(libc.as_ref().map(|libc| &libc.kind), Some(&LibcKind::Glibc));
+
+        // the running libc only adds its version when it's the same kind
+        let glibc = |version: &str| Libc {
+            kind: LibcKind::Glibc,
+            version: Some(version.into()),
+        };
+        let musl = Libc {
+            kind: LibcKind::Musl,
+            version: None,
+        };
+        assert_eq!(
+            OperatingSystemInfo::system_libc(libc, Some(glibc("2.40"))),
+            Some(glibc("2.40"))
+        );
+        assert_eq!(
+            OperatingSystemInfo::system_libc(Some(musl.clone()), Some(glibc("2.40"))),
+            Some(musl)
+        );
+        assert_eq!(
+            OperatingSystemInfo::system_libc(None, Some(glibc("2.40"))),
+            None
+        );
+
+        let config = OperatingSystemInfo::find_kernel_config(&root, "6.11.5")
+            .await
+            .unwrap();
+        assert_eq!(config.get("CONFIG_HZ").map(String::as_str), Some("1000"));
+        assert_eq!(
+            config.get("CONFIG_PREEMPT_DYNAMIC").map(String::as_str),
+            Some("y")
+        );
+        assert_eq!(
+            config.get("CONFIG_LSM").map(String::as_str),
+            Some("lockdown,yama,integrity,selinux,bpf")
+        );
+        assert!(!config.contains_key("CONFIG_PREEMPT_RT"));
+
+        // options that don't describe the hardware's behavior are left out
+        assert!(!config.contains_key("CONFIG_LOCALVERSION"));
+        assert!(!config.contains_key("CONFIG_DEFAULT_HOSTNAME"));
+        assert_eq!(config.len(), 6);
+    }
+
+    #[test]
+    fn check_session() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                vars.iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+
+        let gnome = env(&[
+            ("XDG_SESSION_TYPE", "wayland"),
+            ("XDG_CURRENT_DESKTOP", "ubuntu:GNOME"),
+        ]);
+        assert_eq!(
+            OperatingSystemInfo::find_display_server(gnome),
+            Some(DisplayServer::Wayland)
+        );
+        assert_eq!(
+            OperatingSystemInfo::find_desktop(gnome),
+            Some(vec!["ubuntu".into(), "GNOME".into()])
+        );
+
+        // under `sudo`, we only have `DISPLAY`
+        let sudo = env(&[("XDG_SESSION_TYPE", "tty"), ("DISPLAY", ":0")]);
+        assert_eq!(
+            OperatingSystemInfo::find_display_server(sudo),
+            Some(DisplayServer::X11)
+        );
+        assert_eq!(OperatingSystemInfo::find_desktop(sudo), None);
+
+        // and over ssh, there's no session at all
+        assert_eq!(OperatingSystemInfo::find_display_server(env(&[])), None);
+    }
+
+    #[tracing::instrument]
+    fn root_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs"))
+    }
 }
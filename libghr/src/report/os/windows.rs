@@ -20,6 +20,12 @@ impl Report {
             version: format!("{}.{}.{}", ver.major, ver.minor, ver.build),
             architecture: arch,
             other: BTreeMap::new(),
+            os_release: None,
+            init_system: None,
+            libc: None,
+            display_server: None,
+            desktop: None,
+            kernel_config: None,
         })
     }
 }
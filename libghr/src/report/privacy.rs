@@ -0,0 +1,83 @@
+//! `privacy`: Handles values that could identify a specific user or machine.
+//!
+//! Things like MAC addresses and serial numbers are useful when debugging
+//! hardware, but they shouldn't end up in a public report without the user
+//! asking for it. So, `libghr` wraps them in a [`Sensitive`] value, which
+//! follows the [`Redaction`] policy given to [`crate::ReportBuilder`].
+
+use crate::prelude::internal::*;
+
+/// How identifying values, like MAC addresses and serial numbers, are placed
+/// into a report.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    serde::Serialize,
+    serde::Deserialize,
+    TypeScript,
+)]
+pub enum Redaction {
+    /// Identifying values are left out of the report.
+    #[default]
+    Redact,
+
+    /// Identifying values are reported as-is.
+    Keep,
+}
+
+/// A value that could identify a specific user or machine.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+pub enum Sensitive {
+    /// The original value. Only used when the user opts out of redaction.
+    Plain(String),
+
+    /// The value exists, but was removed from the report.
+    Redacted,
+}
+
+impl Sensitive {
+    /// wraps a value.
+    ///
+    /// it's kept until the report applies its redaction policy, so
+    /// collectors don't need to know about it.
+    #[tracing::instrument(skip(value))]
+    pub(crate) fn new(value: impl Into<String>) -> Self {
+        Self::Plain(value.into())
+    }
+
+    /// applies the given redaction policy to the value.
+    #[tracing::instrument(skip(self))]
+    pub(crate) fn redact(&mut self, redaction: Redaction) {
+        match redaction {
+            Redaction::Redact => *self = Self::Redacted,
+            Redaction::Keep => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacted_by_default() {
+        let mut mac = Sensitive::new("a4:bb:6d:12:34:56");
+        mac.redact(Redaction::default());
+
+        assert_eq!(Redaction::default(), Redaction::Redact);
+        assert_eq!(mac, Sensitive::Redacted);
+    }
+
+    #[test]
+    fn kept_values_are_plain() {
+        let mut serial = Sensitive::new("PF3ABCDE");
+        serial.redact(Redaction::Keep);
+
+        assert_eq!(serial, Sensitive::Plain("PF3ABCDE".into()));
+    }
+}
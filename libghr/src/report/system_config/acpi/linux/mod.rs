@@ -0,0 +1,257 @@
+use std::{collections::BTreeMap, path::Path};
+
+use crate::prelude::internal::*;
+use futures::StreamExt as _;
+
+pub(crate) mod tables;
+
+/// the tables we decode, beyond their headers
+const DECODED: &[&str] = &["FACP", "APIC", "DMAR", "IVRS", "LPIT", "BGRT", "HPET"];
+
+/// the contents of each table we decode, by name
+type Contents = BTreeMap<String, Vec<u8>>;
+
+/// gets information from the firmware's acpi tables.
+#[tracing::instrument]
+pub(super) async fn get() -> Acpi {
+    acpi("/").await
+}
+
+/// reads the acpi tables under `root`.
+///
+/// the tables are in `/sys/firmware/acpi/tables`, but only root can read
+/// them. everyone else can still list them, and read the few values the
+/// kernel shares elsewhere in `/sys/firmware/acpi`.
+#[tracing::instrument]
+async fn acpi(root: impl AsRef<Path> + std::fmt::Debug) -> Acpi {
+    let acpi_path = root.as_ref().join("sys/firmware/acpi");
+    let Some((tables, contents)) = read_tables(&acpi_path.join("tables")).await else {
+        return Acpi::default();
+    };
+
+    let (fadt, bgrt) = futures::join! {
+        async {
+            match contents.get("FACP") {
+                Some(fadt) => tables::fadt(fadt),
+                None => fadt_fallback(&acpi_path).await,
+            }
+        },
+        async {
+            match contents.get("BGRT") {
+                Some(bgrt) => tables::bgrt(bgrt),
+                None => bgrt_fallback(&acpi_path.join("bgrt")).await,
+            }
+        },
+    };
+
+    // intel computers have a dmar, while amd ones have an ivrs
+    let iommu = contents
+        .get("DMAR")
+        .and_then(|dmar| tables::dmar(dmar))
+        .or_else(|| contents.get("IVRS").and_then(|ivrs| tables::ivrs(ivrs)));
+
+    Acpi {
+        fadt,
+        madt: contents.get("APIC").and_then(|madt| tables::madt(madt)),
+        iommu,
+        lpit: contents.get("LPIT").and_then(|lpit| tables::lpit(lpit)),
+        bgrt,
+        hpet: contents.get("HPET").and_then(|hpet| tables::hpet(hpet)),
+        tables: Some(tables),
+    }
+}
+
+/// lists the tables in `tables_path`, keeping the contents of the ones we
+/// decode.
+///
+/// tables we can't read only get their name and length.
+#[tracing::instrument]
+async fn read_tables(tables_path: &Path) -> Option<(Vec<AcpiTable>, Contents)> {
+    let mut entries = async_fs::read_dir(tables_path)
+        .await
+        .inspect_err(|e| tracing::debug!("Couldn't list the ACPI tables. (err: {e})"))
+        .ok()?;
+
+    let mut tables = Vec::new();
+    let mut contents = BTreeMap::new();
+    while let Some(Ok(entry)) = entries.next().await {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        // skip `data` and `dynamic`, which hold other tables
+        let Ok(metadata) = async_fs::metadata(&path).await else {
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+
+        match async_fs::read(&path).await {
+            Ok(table) => {
+                tables.push(tables::header(&name, &table));
+                if DECODED.contains(&name.as_str()) {
+                    contents.insert(name, table);
+                }
+            }
+            Err(e) => {
+                tracing::debug!("Couldn't read the `{name}` ACPI table. (err: {e})");
+                tables.push(unreadable(name, metadata.len()));
+            }
+        }
+    }
+
+    tables.sort_by(|a, b| a.name.cmp(&b.name));
+    Some((tables, contents))
+}
+
+/// describes a table we can only see in the listing.
+#[tracing::instrument]
+fn unreadable(name: String, length: u64) -> AcpiTable {
+    // the kernel numbers repeated tables, like `SSDT3`
+    let signature = name
+        .trim_end_matches(|c: char| c.is_ascii_digit())
+        .to_string();
+
+    AcpiTable {
+        signature: (signature.len() == 4).then_some(signature),
+        // `sysfs` reports a size of zero when it doesn't know
+        length: (length != 0).then_some(length),
+        revision: None,
+        checksum_valid: None,
+        oem_id: None,
+        oem_table_id: None,
+        oem_revision: None,
+        creator_id: None,
+        creator_revision: None,
+        name,
+    }
+}
+
+/// gets the fadt's pm profile from `acpi_path` (usually `/sys/firmware/acpi`),
+/// which anyone can read.
+#[tracing::instrument]
+async fn fadt_fallback(acpi_path: &Path) -> Option<Fadt> {
+    let profile = sysfs_value_opt::<u8>(acpi_path.join("pm_profile")).await?;
+
+    Some(Fadt {
+        version: None,
+        pm_profile: Some(tables::pm_profile(profile)),
+        flags: None,
+        boot_flags: None,
+    })
+}
+
+/// gets the bgrt from `bgrt_path` (usually `/sys/firmware/acpi/bgrt`), which
+/// anyone can read.
+#[tracing::instrument]
+async fn bgrt_fallback(bgrt_path: &Path) -> Option<Bgrt> {
+    let (status, image_type) = futures::join! {
+        sysfs_value_opt::<u8>(bgrt_path.join("status")),
+        sysfs_value_opt::<u8>(bgrt_path.join("type")),
+    };
+    let (x, y) = futures::join! {
+        sysfs_value_opt::<u32>(bgrt_path.join("xoffset")),
+        sysfs_value_opt::<u32>(bgrt_path.join("yoffset")),
+    };
+
+    let status = status?;
+    Some(Bgrt {
+        displayed: status & 1 != 0,
+        orientation: tables::bgrt_orientation(status),
+        image_type: image_type.and_then(tables::bgrt_image_type),
+        x: x?,
+        y: y?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn check_acpi() {
+        let acpi = acpi(root_path()).await;
+
+        let tables = acpi.tables.unwrap();
+        let names = tables.iter().map(|t| t.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            ["APIC", "BGRT", "DMAR", "DSDT", "FACP", "HPET", "LPIT", "SSDT1"]
+        );
+        assert!(tables.iter().all(|t| t.checksum_valid == Some(true)));
+
+        let facp = tables.iter().find(|t| t.name == "FACP").unwrap();
+        assert_eq!(facp.signature.as_deref(), Some("FACP"));
+        assert_eq!(facp.length, Some(276));
+        assert_eq!(facp.oem_id.as_deref(), Some("LENOVO"));
+        assert_eq!(facp.oem_table_id.as_deref(), Some("TP-R1O"));
+        assert_eq!(facp.creator_id.as_deref(), Some("PTEC"));
+
+        let fadt = acpi.fadt.unwrap();
+        assert_eq!(fadt.version.as_deref(), Some("6.5"));
+        assert_eq!(fadt.pm_profile, Some(PmProfile::Mobile));
+        let flags = fadt.flags.unwrap();
+        assert!(flags.contains(&FadtFlag::LowPowerS0Idle));
+        assert!(flags.contains(&FadtFlag::ResetRegister));
+        assert!(!flags.contains(&FadtFlag::HardwareReduced));
+        assert_eq!(fadt.boot_flags, Some(vec![BootArchFlag::Has8042]));
+
+        let madt = acpi.madt.unwrap();
+        assert_eq!(madt.processors, 8);
+        assert_eq!(madt.online_capable_processors, 0);
+        assert_eq!(madt.io_apics, 1);
+        assert_eq!(madt.interrupt_overrides, 2);
+
+        let iommu = acpi.iommu.unwrap();
+        assert_eq!(iommu.kind, IommuKind::IntelVtd);
+        assert_eq!(iommu.units, 2);
+        assert_eq!(iommu.interrupt_remapping, Some(true));
+        assert_eq!(iommu.x2apic_opt_out, Some(false));
+        assert_eq!(iommu.dma_protection, Some(true));
+
+        let lpit = acpi.lpit.unwrap();
+        assert_eq!(lpit.len(), 2);
+        assert_eq!(lpit[1].id, 1);
+        assert!(lpit[1].enabled);
+        assert_eq!(lpit[1].min_residency, 30000);
+        assert_eq!(lpit[1].counter_frequency, Some(8060));
+
+        let bgrt = acpi.bgrt.unwrap();
+        assert!(bgrt.displayed);
+        assert_eq!(bgrt.orientation, Some(0));
+        assert_eq!(bgrt.image_type.as_deref(), Some("BMP"));
+        assert_eq!((bgrt.x, bgrt.y), (760, 360));
+
+        let hpet = acpi.hpet.unwrap();
+        assert_eq!(hpet.vendor_id, "8086");
+        assert_eq!(hpet.comparators, 8);
+        assert!(hpet.counter_64bit);
+        assert!(hpet.legacy_replacement);
+        assert_eq!(hpet.min_tick, 128);
+    }
+
+    #[tokio::test]
+    async fn check_without_root() {
+        let acpi_path = root_path().join("sys/firmware/acpi");
+
+        // without root, we only get what the kernel shares
+        let fadt = fadt_fallback(&acpi_path).await.unwrap();
+        assert_eq!(fadt.pm_profile, Some(PmProfile::Mobile));
+        assert!(fadt.flags.is_none());
+
+        let bgrt = bgrt_fallback(&acpi_path.join("bgrt")).await.unwrap();
+        assert!(bgrt.displayed);
+        assert_eq!((bgrt.x, bgrt.y), (760, 360));
+
+        let table = unreadable("SSDT12".into(), 0);
+        assert_eq!(table.signature.as_deref(), Some("SSDT"));
+        assert!(table.length.is_none());
+    }
+
+    #[tracing::instrument]
+    fn root_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs"))
+    }
+}
@@ -0,0 +1,379 @@
+//! decodes acpi tables.
+//!
+//! every table starts with the same 36-byte header, then has its own layout.
+//! it's all little-endian. the layouts come from the acpi spec, except for
+//! dmar (intel's vt-d spec), ivrs (amd's iommu spec), lpit (intel's lpit
+//! spec), and hpet (the ia-pc hpet spec).
+
+use crate::prelude::internal::*;
+
+/// how long the common table header is
+const HEADER_LEN: usize = 36;
+
+/// each fadt flag, in the order of its bit
+const FADT_FLAGS: &[FadtFlag] = &[
+    FadtFlag::Wbinvd,
+    FadtFlag::WbinvdFlush,
+    FadtFlag::ProcessorC1,
+    FadtFlag::MultiprocessorC2,
+    FadtFlag::PowerButtonControlMethod,
+    FadtFlag::SleepButtonControlMethod,
+    FadtFlag::NoFixedRtcWake,
+    FadtFlag::RtcS4Wake,
+    FadtFlag::TimerValueExtended,
+    FadtFlag::DockingCapable,
+    FadtFlag::ResetRegister,
+    FadtFlag::SealedCase,
+    FadtFlag::Headless,
+    FadtFlag::CpuSoftwareSleep,
+    FadtFlag::PciExpressWake,
+    FadtFlag::UsePlatformClock,
+    FadtFlag::S4RtcStatusValid,
+    FadtFlag::RemotePowerOn,
+    FadtFlag::ForceApicClusterModel,
+    FadtFlag::ForceApicPhysicalDestinationMode,
+    FadtFlag::HardwareReduced,
+    FadtFlag::LowPowerS0Idle,
+];
+
+/// each `IAPC_BOOT_ARCH` flag, in the order of its bit
+const BOOT_ARCH_FLAGS: &[BootArchFlag] = &[
+    BootArchFlag::LegacyDevices,
+    BootArchFlag::Has8042,
+    BootArchFlag::VgaNotPresent,
+    BootArchFlag::MsiNotSupported,
+    BootArchFlag::PcieAspmControls,
+    BootArchFlag::CmosRtcNotPresent,
+];
+
+fn read_u8(table: &[u8], at: usize) -> Option<u8> {
+    table.get(at).copied()
+}
+
+fn read_u16(table: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(table.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn read_u32(table: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(table.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn read_u64(table: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(table.get(at..at + 8)?.try_into().ok()?))
+}
+
+/// reads a fixed-length string, like the oem id, trimming its padding.
+fn read_str(table: &[u8], at: usize, len: usize) -> Option<String> {
+    let bytes = table.get(at..at + len)?;
+    let s = String::from_utf8_lossy(bytes)
+        .trim_matches(|c: char| c == '\0' || c.is_whitespace())
+        .to_string();
+
+    (!s.is_empty()).then_some(s)
+}
+
+/// decodes the header of the table called `name`.
+#[tracing::instrument(skip(table))]
+pub(super) fn header(name: &str, table: &[u8]) -> AcpiTable {
+    // the whole table, checksum included, adds up to zero
+    let checksum_valid = (table.len() >= HEADER_LEN)
+        .then(|| table.iter().fold(0_u8, |sum, byte| sum.wrapping_add(*byte)) == 0);
+
+    AcpiTable {
+        name: name.to_string(),
+        signature: read_str(table, 0, 4),
+        length: read_u32(table, 4).map(u64::from),
+        revision: read_u8(table, 8),
+        checksum_valid,
+        oem_id: read_str(table, 10, 6),
+        oem_table_id: read_str(table, 16, 8),
+        oem_revision: read_u32(table, 24),
+        creator_id: read_str(table, 28, 4),
+        creator_revision: read_u32(table, 32),
+    }
+}
+
+/// decodes the fadt (signature `FACP`).
+#[tracing::instrument(skip(fadt))]
+pub(crate) fn fadt(fadt: &[u8]) -> Option<Fadt> {
+    let revision = read_u8(fadt, 8)?;
+
+    // the minor version came with fadt 5.1
+    let version = match read_u8(fadt, 131) {
+        Some(minor) if revision >= 5 => format!("{revision}.{}", minor & 0xf),
+        _ => revision.to_string(),
+    };
+
+    let flags = read_u32(fadt, 112).map(|flags| {
+        FADT_FLAGS
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| flags & (1 << bit) != 0)
+            .map(|(_, flag)| *flag)
+            .collect()
+    });
+
+    let boot_flags = read_u16(fadt, 109).map(|flags| {
+        BOOT_ARCH_FLAGS
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| flags & (1 << bit) != 0)
+            .map(|(_, flag)| *flag)
+            .collect()
+    });
+
+    Some(Fadt {
+        version: Some(version),
+        pm_profile: read_u8(fadt, 45).map(pm_profile),
+        flags,
+        boot_flags,
+    })
+}
+
+/// converts the fadt's `Preferred_PM_Profile`.
+pub(super) fn pm_profile(profile: u8) -> PmProfile {
+    match profile {
+        0 => PmProfile::Unspecified,
+        1 => PmProfile::Desktop,
+        2 => PmProfile::Mobile,
+        3 => PmProfile::Workstation,
+        4 => PmProfile::EnterpriseServer,
+        5 => PmProfile::SohoServer,
+        6 => PmProfile::AppliancePc,
+        7 => PmProfile::PerformanceServer,
+        8 => PmProfile::Tablet,
+        other => PmProfile::Other(other),
+    }
+}
+
+/// decodes the madt (signature `APIC`), counting what it describes.
+///
+/// after the header, there's the local apic address, flags, then a list of
+/// structures, each starting with its type and length.
+#[tracing::instrument(skip(madt))]
+pub(super) fn madt(madt: &[u8]) -> Option<Madt> {
+    let mut info = Madt {
+        processors: 0,
+        online_capable_processors: 0,
+        io_apics: 0,
+        interrupt_overrides: 0,
+        gic_distributors: 0,
+    };
+
+    let mut at = HEADER_LEN + 8;
+    while at < madt.len() {
+        let kind = read_u8(madt, at)?;
+        let len = read_u8(madt, at + 1)? as usize;
+        if len < 2 {
+            tracing::debug!("MADT has a broken structure. (offset: {at})");
+            break;
+        }
+
+        // processors are either enabled, or might be turned on later
+        let processor_flags = match kind {
+            // local apic
+            0x0 => read_u32(madt, at + 4).map(|flags| (flags & 1, flags & 2)),
+            // local x2apic
+            0x9 => read_u32(madt, at + 8).map(|flags| (flags & 1, flags & 2)),
+            // gic cpu interface
+            0xb => read_u32(madt, at + 12).map(|flags| (flags & 1, flags & 8)),
+            _ => None,
+        };
+
+        match (kind, processor_flags) {
+            (_, Some((enabled, _))) if enabled != 0 => info.processors += 1,
+            (_, Some((_, online_capable))) if online_capable != 0 => {
+                info.online_capable_processors += 1
+            }
+            (0x1, _) => info.io_apics += 1,
+            (0x2, _) => info.interrupt_overrides += 1,
+            (0xc, _) => info.gic_distributors += 1,
+            _ => (),
+        }
+
+        at += len;
+    }
+
+    Some(info)
+}
+
+/// decodes intel's dmar, which describes vt-d.
+///
+/// after the header, there's the host address width, flags, ten reserved
+/// bytes, then a list of structures. each has a 16-bit type and length.
+#[tracing::instrument(skip(dmar))]
+pub(super) fn dmar(dmar: &[u8]) -> Option<AcpiIommu> {
+    let flags = read_u8(dmar, HEADER_LEN + 1)?;
+
+    // "dma remapping hardware unit definitions" are the iommus
+    let mut units = 0;
+    let mut at = HEADER_LEN + 12;
+    while let (Some(kind), Some(len)) = (read_u16(dmar, at), read_u16(dmar, at + 2)) {
+        if len < 4 {
+            tracing::debug!("DMAR has a broken structure. (offset: {at})");
+            break;
+        }
+
+        if kind == 0 {
+            units += 1;
+        }
+        at += len as usize;
+    }
+
+    Some(AcpiIommu {
+        kind: IommuKind::IntelVtd,
+        units,
+        interrupt_remapping: Some(flags & 1 != 0),
+        x2apic_opt_out: Some(flags & 2 != 0),
+        dma_protection: Some(flags & 4 != 0),
+    })
+}
+
+/// decodes amd's ivrs, which describes amd-vi.
+///
+/// after the header, there's the `IVinfo` field, eight reserved bytes, then
+/// a list of blocks. each has an 8-bit type, flags, and a 16-bit length.
+#[tracing::instrument(skip(ivrs))]
+pub(super) fn ivrs(ivrs: &[u8]) -> Option<AcpiIommu> {
+    let info = read_u32(ivrs, HEADER_LEN)?;
+
+    // each iommu gets a hardware definition block for each format the
+    // firmware supports, so we count them by their pci device
+    let mut devices = Vec::new();
+    let mut at = HEADER_LEN + 12;
+    while let (Some(kind), Some(len)) = (read_u8(ivrs, at), read_u16(ivrs, at + 2)) {
+        if len < 4 {
+            tracing::debug!("IVRS has a broken block. (offset: {at})");
+            break;
+        }
+
+        if matches!(kind, 0x10 | 0x11 | 0x40) {
+            if let Some(device) = read_u16(ivrs, at + 4) {
+                if !devices.contains(&device) {
+                    devices.push(device);
+                }
+            }
+        }
+        at += len as usize;
+    }
+
+    Some(AcpiIommu {
+        kind: IommuKind::AmdVi,
+        units: devices.len() as u32,
+        interrupt_remapping: None,
+        x2apic_opt_out: None,
+        dma_protection: Some(info & 2 != 0),
+    })
+}
+
+/// decodes the lpit, which lists the low-power idle states.
+///
+/// after the header, there's a list of structures, each with a 32-bit type
+/// and length.
+#[tracing::instrument(skip(lpit))]
+pub(super) fn lpit(lpit: &[u8]) -> Option<Vec<LowPowerIdleState>> {
+    let mut states = Vec::new();
+    let mut at = HEADER_LEN;
+    while let (Some(kind), Some(len)) = (read_u32(lpit, at), read_u32(lpit, at + 4)) {
+        if len < 8 {
+            tracing::debug!("LPIT has a broken structure. (offset: {at})");
+            break;
+        }
+
+        // only "native c-state" states are defined
+        if kind == 0 {
+            let frequency = read_u64(lpit, at + 48)?;
+            states.push(LowPowerIdleState {
+                id: read_u16(lpit, at + 8)?,
+                enabled: read_u32(lpit, at + 12)? & 1 == 0,
+                min_residency: read_u32(lpit, at + 28)?,
+                latency: read_u32(lpit, at + 32)?,
+                counter_frequency: (frequency != 0).then_some(frequency),
+            });
+        }
+        at += len as usize;
+    }
+
+    Some(states)
+}
+
+/// decodes the bgrt, which describes the boot logo.
+#[tracing::instrument(skip(bgrt))]
+pub(super) fn bgrt(bgrt: &[u8]) -> Option<Bgrt> {
+    let status = read_u8(bgrt, HEADER_LEN + 2)?;
+    let image_type = read_u8(bgrt, HEADER_LEN + 3)?;
+
+    Some(Bgrt {
+        displayed: status & 1 != 0,
+        orientation: bgrt_orientation(status),
+        image_type: bgrt_image_type(image_type),
+        x: read_u32(bgrt, HEADER_LEN + 12)?,
+        y: read_u32(bgrt, HEADER_LEN + 16)?,
+    })
+}
+
+/// finds how far the logo is rotated from the bgrt's status.
+///
+/// this was added in acpi 6.2. before then, these bits were zero.
+pub(super) fn bgrt_orientation(status: u8) -> Option<u16> {
+    Some(u16::from((status >> 1) & 0b11) * 90)
+}
+
+/// names the bgrt's image type.
+pub(super) fn bgrt_image_type(image_type: u8) -> Option<String> {
+    match image_type {
+        0 => Some("BMP".into()),
+        other => {
+            tracing::debug!("Unknown BGRT image type: `{other}`");
+            None
+        }
+    }
+}
+
+/// decodes the hpet table.
+///
+/// the "event timer block id" after the header packs in the hpet's
+/// capabilities.
+#[tracing::instrument(skip(hpet))]
+pub(super) fn hpet(hpet: &[u8]) -> Option<Hpet> {
+    let id = read_u32(hpet, HEADER_LEN)?;
+
+    Some(Hpet {
+        vendor_id: format!("{:04x}", id >> 16),
+        comparators: ((id >> 8) & 0x1f) as u8 + 1,
+        counter_64bit: id & (1 << 13) != 0,
+        legacy_replacement: id & (1 << 15) != 0,
+        min_tick: read_u16(hpet, HEADER_LEN + 17)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_broken_tables() {
+        // a truncated header shouldn't panic
+        let table = header("SSDT1", b"SSDT\x10\0");
+        assert_eq!(table.signature.as_deref(), Some("SSDT"));
+        assert!(table.length.is_none());
+        assert!(table.checksum_valid.is_none());
+
+        // and neither should a structure that claims to be empty
+        let mut madt = vec![0_u8; HEADER_LEN + 8];
+        madt.extend([0x1, 0x0, 0xff, 0xff]);
+        assert_eq!(madt_counts(&madt), (0, 0));
+    }
+
+    #[test]
+    fn check_pm_profile() {
+        assert_eq!(pm_profile(8), PmProfile::Tablet);
+        assert_eq!(pm_profile(42), PmProfile::Other(42));
+    }
+
+    /// grabs the processor and io apic counts from a madt.
+    fn madt_counts(table: &[u8]) -> (u32, u32) {
+        let madt = madt(table).unwrap();
+        (madt.processors, madt.io_apics)
+    }
+}
@@ -0,0 +1,325 @@
+use crate::prelude::internal::*;
+
+#[cfg(target_os = "linux")]
+pub(crate) mod linux;
+
+/// Information from the firmware's ACPI tables.
+///
+/// Most tables are only readable by an administrator. Without those rights,
+/// only the list of tables (and a few values the OS shares) is available.
+#[derive(
+    Clone, Debug, Default, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript,
+)]
+#[non_exhaustive]
+pub struct Acpi {
+    /// Every table the firmware provided.
+    pub tables: Option<Vec<AcpiTable>>,
+
+    /// The Fixed ACPI Description Table, which describes the platform's
+    /// power management hardware.
+    pub fadt: Option<Fadt>,
+
+    /// The Multiple APIC Description Table, which lists the processors and
+    /// interrupt controllers.
+    pub madt: Option<Madt>,
+
+    /// The IOMMU described by the DMAR (Intel) or IVRS (AMD) table.
+    pub iommu: Option<AcpiIommu>,
+
+    /// The low-power idle states from the LPIT, used for S0ix.
+    pub lpit: Option<Vec<LowPowerIdleState>>,
+
+    /// The boot logo from the Boot Graphics Resource Table.
+    pub bgrt: Option<Bgrt>,
+
+    /// The High Precision Event Timer.
+    pub hpet: Option<Hpet>,
+}
+
+/// The header of an ACPI table.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct AcpiTable {
+    /// The table's name, like `SSDT3`.
+    ///
+    /// There can be many tables with the same signature, so the OS numbers
+    /// them.
+    pub name: String,
+
+    /// The table's four-letter signature, like `SSDT`.
+    pub signature: Option<String>,
+
+    /// The table's size, in bytes.
+    pub length: Option<u64>,
+
+    /// The table's revision.
+    pub revision: Option<u8>,
+
+    /// Whether the table's checksum is correct.
+    pub checksum_valid: Option<bool>,
+
+    /// Who made the table, like `LENOVO`.
+    pub oem_id: Option<String>,
+
+    /// The maker's name for the table, like `TP-R1O`.
+    pub oem_table_id: Option<String>,
+
+    /// The maker's revision of the table.
+    pub oem_revision: Option<u32>,
+
+    /// The tool that compiled the table, like `INTL` for Intel's `iasl`.
+    pub creator_id: Option<String>,
+
+    /// The version of the tool that compiled the table.
+    pub creator_revision: Option<u32>,
+}
+
+/// The Fixed ACPI Description Table.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct Fadt {
+    /// The version of the FADT, like `6.5`.
+    pub version: Option<String>,
+
+    /// The kind of computer the firmware says this is.
+    pub pm_profile: Option<PmProfile>,
+
+    /// The platform's feature flags.
+    pub flags: Option<Vec<FadtFlag>>,
+
+    /// The legacy hardware the platform has (or doesn't).
+    pub boot_flags: Option<Vec<BootArchFlag>>,
+}
+
+/// The kind of computer the firmware says this is, which sets the OS' power
+/// management defaults.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum PmProfile {
+    Unspecified,
+    Desktop,
+    Mobile,
+    Workstation,
+    EnterpriseServer,
+    SohoServer,
+    AppliancePc,
+    PerformanceServer,
+    Tablet,
+    Other(u8),
+}
+
+/// A flag in the FADT.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    TypeScript,
+)]
+#[non_exhaustive]
+pub enum FadtFlag {
+    /// The `WBINVD` instruction works correctly.
+    Wbinvd,
+    /// `WBINVD` flushes caches, but might not invalidate them.
+    WbinvdFlush,
+    /// All processors support the C1 power state.
+    ProcessorC1,
+    /// The C2 power state works with more than one processor.
+    MultiprocessorC2,
+    /// The power button is a control method device, not a fixed feature.
+    PowerButtonControlMethod,
+    /// The sleep button is a control method device, or there isn't one.
+    SleepButtonControlMethod,
+    /// The RTC can't wake the computer as a fixed feature.
+    NoFixedRtcWake,
+    /// The RTC can wake the computer from S4.
+    RtcS4Wake,
+    /// The power management timer is 32 bits wide, not 24.
+    TimerValueExtended,
+    /// The platform supports docking.
+    DockingCapable,
+    /// The platform can be reset through the reset register.
+    ResetRegister,
+    /// The case can't be opened or expanded.
+    SealedCase,
+    /// There's no local input or output.
+    Headless,
+    /// The processor has to run an instruction after entering sleep.
+    CpuSoftwareSleep,
+    /// PCIe devices can wake the computer through `PCIEXP_WAKE`.
+    PciExpressWake,
+    /// The OS should use the platform clock, not the processor's.
+    UsePlatformClock,
+    /// The RTC status bit is valid after waking from S4.
+    S4RtcStatusValid,
+    /// The computer can be powered on remotely.
+    RemotePowerOn,
+    /// The OS must use the APIC's cluster model.
+    ForceApicClusterModel,
+    /// The OS must use the APIC's physical destination mode.
+    ForceApicPhysicalDestinationMode,
+    /// There's no fixed power management hardware, like on ARM.
+    HardwareReduced,
+    /// The platform is at least as power efficient in S0 idle as in S3.
+    LowPowerS0Idle,
+}
+
+/// A legacy hardware flag from the FADT's `IAPC_BOOT_ARCH`.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    TypeScript,
+)]
+#[non_exhaustive]
+pub enum BootArchFlag {
+    /// The platform has legacy devices on an LPC or ISA bus.
+    LegacyDevices,
+    /// The platform has an 8042 keyboard controller.
+    Has8042,
+    /// The OS shouldn't probe for VGA hardware.
+    VgaNotPresent,
+    /// The OS shouldn't turn on MSI interrupts.
+    MsiNotSupported,
+    /// The OS shouldn't change PCIe ASPM settings.
+    PcieAspmControls,
+    /// There's no CMOS RTC.
+    CmosRtcNotPresent,
+}
+
+/// What the Multiple APIC Description Table describes.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct Madt {
+    /// How many processors are turned on.
+    pub processors: u32,
+
+    /// How many more processors could be turned on later.
+    pub online_capable_processors: u32,
+
+    /// How many I/O APICs there are.
+    pub io_apics: u32,
+
+    /// How many legacy interrupts were moved to another line.
+    pub interrupt_overrides: u32,
+
+    /// How many GIC distributors there are, on ARM.
+    pub gic_distributors: u32,
+}
+
+/// The IOMMU the firmware describes.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct AcpiIommu {
+    /// What kind of IOMMU it is.
+    pub kind: IommuKind,
+
+    /// How many IOMMU units there are.
+    pub units: u32,
+
+    /// Whether the IOMMU can remap interrupts.
+    pub interrupt_remapping: Option<bool>,
+
+    /// Whether the firmware asked the OS not to use x2APIC mode.
+    pub x2apic_opt_out: Option<bool>,
+
+    /// Whether the firmware protects memory from devices during boot.
+    pub dma_protection: Option<bool>,
+}
+
+/// A kind of IOMMU.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum IommuKind {
+    /// Intel Virtualization Technology for Directed I/O, from the DMAR.
+    IntelVtd,
+    /// AMD I/O Virtualization, from the IVRS.
+    AmdVi,
+}
+
+/// A low-power idle state from the LPIT.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct LowPowerIdleState {
+    /// The state's ID.
+    pub id: u16,
+
+    /// Whether the state can be used.
+    pub enabled: bool,
+
+    /// How long the platform has to stay in the state for it to be worth
+    /// it, in microseconds.
+    pub min_residency: u32,
+
+    /// How long it takes to leave the state, in microseconds.
+    pub latency: u32,
+
+    /// How fast the residency counter ticks, in Hz.
+    ///
+    /// When this isn't set, the counter uses the processor's timestamp
+    /// counter.
+    pub counter_frequency: Option<u64>,
+}
+
+/// The boot logo from the Boot Graphics Resource Table.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct Bgrt {
+    /// Whether the logo is still on screen.
+    pub displayed: bool,
+
+    /// How far the logo is rotated clockwise, in degrees.
+    pub orientation: Option<u16>,
+
+    /// The logo's image format, like `BMP`.
+    pub image_type: Option<String>,
+
+    /// How far the logo is from the left of the screen, in pixels.
+    pub x: u32,
+
+    /// How far the logo is from the top of the screen, in pixels.
+    pub y: u32,
+}
+
+/// The High Precision Event Timer.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct Hpet {
+    /// The PCI vendor ID of the timer's maker, like `8086`.
+    pub vendor_id: String,
+
+    /// How many comparators (timers) the HPET has.
+    pub comparators: u8,
+
+    /// Whether the main counter is 64 bits wide.
+    pub counter_64bit: bool,
+
+    /// Whether the HPET can replace the legacy PIT and RTC interrupts.
+    pub legacy_replacement: bool,
+
+    /// The smallest tick the HPET can do in periodic mode, in counter
+    /// cycles.
+    pub min_tick: u16,
+}
+
+/// Gets information from the firmware's ACPI tables.
+#[tracing::instrument]
+pub async fn get() -> Acpi {
+    #[cfg(target_os = "linux")]
+    return linux::get().await;
+
+    // TODO: use `EnumSystemFirmwareTables` and `GetSystemFirmwareTable`
+    #[cfg(target_os = "windows")]
+    return Acpi::default();
+}
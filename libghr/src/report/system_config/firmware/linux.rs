@@ -0,0 +1,395 @@
+use std::path::Path;
+
+use crate::{
+    prelude::internal::*,
+    report::components::{self, nic::linux::ethtool::Ethtool},
+};
+
+/// gets an inventory of the computer's firmware.
+#[tracing::instrument]
+pub(super) async fn get() -> Firmware {
+    firmware("/", components::nic::linux::ethtool::Ioctl).await
+}
+
+/// gets the firmware inventory, reading `sysfs` and `procfs` under `root`.
+///
+/// network drivers are asked through `ethtool`.
+#[tracing::instrument]
+async fn firmware(root: impl AsRef<Path> + std::fmt::Debug, ethtool: impl Ethtool) -> Firmware {
+    let root = root.as_ref();
+    let (dmi_path, esrt_path) = (
+        root.join("sys/class/dmi/id"),
+        root.join("sys/firmware/efi/esrt/entries"),
+    );
+
+    let (system, esrt, devices) = futures::join! {
+        system(&dmi_path),
+        esrt(&esrt_path),
+        devices(root, ethtool),
+    };
+
+    Firmware {
+        system,
+        esrt,
+        devices,
+    }
+}
+
+/// reads the system firmware's info from smbios.
+#[tracing::instrument]
+async fn system(dmi_path: &Path) -> Option<SystemFirmware> {
+    let (vendor, version, date, release, ec_release) = futures::join! {
+        sysfs_value_opt::<String>(dmi_path.join("bios_vendor")),
+        sysfs_value_opt::<String>(dmi_path.join("bios_version")),
+        sysfs_value_opt::<String>(dmi_path.join("bios_date")),
+        sysfs_value_opt::<String>(dmi_path.join("bios_release")),
+        sysfs_value_opt::<String>(dmi_path.join("ec_firmware_release")),
+    };
+
+    if vendor.is_none() && version.is_none() {
+        tracing::debug!("SMBIOS didn't describe the system firmware.");
+        return None;
+    }
+
+    Some(SystemFirmware {
+        vendor,
+        version,
+        date: date.and_then(|date| chrono::NaiveDate::parse_from_str(&date, "%m/%d/%Y").ok()),
+        release,
+        ec_release,
+    })
+}
+
+/// reads each entry in the efi system resource table.
+///
+/// the table only exists on uefi machines with updatable firmware.
+#[tracing::instrument]
+async fn esrt(entries_path: &Path) -> Option<Vec<EsrtEntry>> {
+    // machines without uefi (or updatable firmware) have no table at all
+    if async_fs::metadata(entries_path).await.is_err() {
+        return None;
+    }
+
+    // entries are named by their position, like `entry0`
+    let mut entries = Vec::new();
+    for path in numbered_entries(entries_path, "entry").await {
+        let (class, kind, version, lowest, attempted, status) = futures::join! {
+            sysfs_value_opt::<String>(path.join("fw_class")),
+            sysfs_value_opt::<u32>(path.join("fw_type")),
+            sysfs_value_opt::<u32>(path.join("fw_version")),
+            sysfs_value_opt::<u32>(path.join("lowest_supported_fw_version")),
+            sysfs_value_opt::<u32>(path.join("last_attempt_version")),
+            sysfs_value_opt::<u32>(path.join("last_attempt_status")),
+        };
+
+        entries.push(EsrtEntry {
+            class,
+            kind: kind.map(esrt_kind),
+            version,
+            lowest_supported_version: lowest,
+            last_attempt_version: attempted,
+            last_attempt_status: status.map(update_status),
+        });
+    }
+
+    Some(entries)
+}
+
+/// converts an esrt `fw_type` to what the firmware runs on.
+fn esrt_kind(kind: u32) -> EsrtKind {
+    match kind {
+        1 => EsrtKind::System,
+        2 => EsrtKind::Device,
+        3 => EsrtKind::Driver,
+        _ => EsrtKind::Unknown,
+    }
+}
+
+/// converts an esrt `last_attempt_status` to how the update went.
+fn update_status(status: u32) -> UpdateStatus {
+    match status {
+        0 => UpdateStatus::Success,
+        1 => UpdateStatus::Unsuccessful,
+        2 => UpdateStatus::InsufficientResources,
+        3 => UpdateStatus::IncorrectVersion,
+        4 => UpdateStatus::InvalidFormat,
+        5 => UpdateStatus::AuthenticationError,
+        6 => UpdateStatus::PowerEventAc,
+        7 => UpdateStatus::PowerEventBattery,
+        8 => UpdateStatus::UnsatisfiedDependencies,
+        other => UpdateStatus::Other(other),
+    }
+}
+
+/// finds the firmware versions that devices report in `sysfs`.
+#[tracing::instrument]
+async fn devices(root: &Path, ethtool: impl Ethtool) -> Option<Vec<DeviceFirmware>> {
+    let (nvme_path, net_path, drm_path, nvidia_path, thunderbolt_path) = (
+        root.join("sys/class/nvme"),
+        root.join("sys/class/net"),
+        root.join("sys/class/drm"),
+        root.join("proc/driver/nvidia/gpus"),
+        root.join("sys/bus/thunderbolt/devices"),
+    );
+
+    let (nvme, net, vbios, nvidia, thunderbolt) = futures::join! {
+        nvme(&nvme_path),
+        net(&net_path, ethtool),
+        vbios(&drm_path),
+        nvidia(&nvidia_path),
+        thunderbolt(&thunderbolt_path),
+    };
+
+    let devices = [nvme, net, vbios, nvidia, thunderbolt]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+    (!devices.is_empty()).then_some(devices)
+}
+
+/// grabs the firmware revision of each nvme controller.
+#[tracing::instrument]
+async fn nvme(path: &Path) -> Vec<DeviceFirmware> {
+    let mut firmware = Vec::new();
+    for entry in components::linux::devices(path).await.unwrap_or_default() {
+        let path = entry.path();
+        let (version, model) = futures::join! {
+            sysfs_value_opt::<String>(path.join("firmware_rev")),
+            sysfs_value_opt::<String>(path.join("model")),
+        };
+
+        if let Some(version) = version {
+            firmware.push(DeviceFirmware {
+                name: Some(entry.file_name().to_string_lossy().to_string()),
+                kind: FirmwareDeviceKind::Storage,
+                model,
+                version,
+            });
+        }
+    }
+
+    firmware.sort_by(|a, b| a.name.cmp(&b.name));
+    firmware
+}
+
+/// asks the driver of each physical network interface for its firmware
+/// version.
+#[tracing::instrument]
+async fn net(path: &Path, ethtool: impl Ethtool) -> Vec<DeviceFirmware> {
+    let mut firmware = Vec::new();
+    for entry in components::linux::devices(path).await.unwrap_or_default() {
+        // virtual interfaces (bridges, tunnels, and so on) have no firmware
+        if async_fs::metadata(entry.path().join("device"))
+            .await
+            .is_err()
+        {
+            continue;
+        }
+
+        // the ioctl blocks, so keep it off the executor
+        let name = entry.file_name().to_string_lossy().to_string();
+        let info = {
+            let (ethtool, name) = (ethtool.clone(), name.clone());
+            blocking::unblock(move || ethtool.driver_info(&name)).await
+        };
+        let Some(info) = info else {
+            continue;
+        };
+
+        // drivers without firmware (or that won't say) leave it blank or
+        // put `N/A`
+        let version = info
+            .firmware_version
+            .filter(|version| !version.is_empty() && version != "N/A");
+
+        if let Some(version) = version {
+            firmware.push(DeviceFirmware {
+                name: Some(name),
+                kind: FirmwareDeviceKind::Network,
+                model: None,
+                version,
+            });
+        }
+    }
+
+    firmware.sort_by(|a, b| a.name.cmp(&b.name));
+    firmware
+}
+
+/// grabs the video bios version of each graphics card that shares it.
+///
+/// `amdgpu` has a `vbios_version` file on the device.
+#[tracing::instrument]
+async fn vbios(path: &Path) -> Vec<DeviceFirmware> {
+    let mut firmware = Vec::new();
+    for entry in components::linux::devices(path).await.unwrap_or_default() {
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        // connectors (like `card1-DP-1`) share their card's device
+        if !name.starts_with("card") || name.contains('-') {
+            continue;
+        }
+
+        let device = entry.path().join("device");
+        if let Some(version) = sysfs_value_opt::<String>(device.join("vbios_version")).await {
+            firmware.push(DeviceFirmware {
+                name: Some(name),
+                kind: FirmwareDeviceKind::Gpu,
+                model: None,
+                version,
+            });
+        }
+    }
+
+    firmware.sort_by(|a, b| a.name.cmp(&b.name));
+    firmware
+}
+
+/// grabs the video bios version of each nvidia card from its proprietary
+/// driver, which doesn't put it in `sysfs`.
+///
+/// each card has an `information` file, with lines like
+/// `Video BIOS: 	 94.02.71.40.c7`.
+#[tracing::instrument]
+async fn nvidia(path: &Path) -> Vec<DeviceFirmware> {
+    let mut firmware = Vec::new();
+    for entry in components::linux::devices(path).await.unwrap_or_default() {
+        let Ok(information) = async_fs::read_to_string(entry.path().join("information")).await
+        else {
+            continue;
+        };
+
+        let field = |key: &str| {
+            information.lines().find_map(|line| {
+                let (k, value) = line.split_once(':')?;
+                (k.trim() == key).then(|| value.trim().to_string())
+            })
+        };
+
+        if let Some(version) = field("Video BIOS") {
+            firmware.push(DeviceFirmware {
+                // named by pci address, like `0000:01:00.0`
+                name: Some(entry.file_name().to_string_lossy().to_string()),
+                kind: FirmwareDeviceKind::Gpu,
+                model: field("Model"),
+                version,
+            });
+        }
+    }
+
+    firmware.sort_by(|a, b| a.name.cmp(&b.name));
+    firmware
+}
+
+/// grabs the nvm version of each thunderbolt router and retimer.
+#[tracing::instrument]
+async fn thunderbolt(path: &Path) -> Vec<DeviceFirmware> {
+    let mut firmware = Vec::new();
+    for entry in components::linux::devices(path).await.unwrap_or_default() {
+        let path = entry.path();
+        let (version, model) = futures::join! {
+            sysfs_value_opt::<String>(path.join("nvm_version")),
+            sysfs_value_opt::<String>(path.join("device_name")),
+        };
+
+        if let Some(version) = version {
+            firmware.push(DeviceFirmware {
+                name: Some(entry.file_name().to_string_lossy().to_string()),
+                kind: FirmwareDeviceKind::Thunderbolt,
+                model,
+                version,
+            });
+        }
+    }
+
+    firmware.sort_by(|a, b| a.name.cmp(&b.name));
+    firmware
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::components::nic::linux::ethtool;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn check_firmware() {
+        let firmware = firmware(root_path(), ethtool::Fixture).await;
+
+        let system = firmware.system.unwrap();
+        assert_eq!(system.vendor.as_deref(), Some("LENOVO"));
+        assert_eq!(system.version.as_deref(), Some("R1OET52W (1.31 )"));
+        assert_eq!(system.date, chrono::NaiveDate::from_ymd_opt(2024, 3, 8));
+        assert_eq!(system.ec_release.as_deref(), Some("1.17"));
+
+        // entries keep the table's order
+        let esrt = firmware.esrt.unwrap();
+        assert_eq!(
+            esrt.iter()
+                .map(|entry| entry.kind.clone())
+                .collect::<Vec<_>>(),
+            [Some(EsrtKind::System), Some(EsrtKind::Device)]
+        );
+        let system_entry = esrt
+            .iter()
+            .find(|entry| entry.kind == Some(EsrtKind::System))
+            .unwrap();
+        assert_eq!(system_entry.version, Some(0x1310));
+        assert_eq!(
+            system_entry.last_attempt_status,
+            Some(UpdateStatus::Success)
+        );
+        let device_entry = esrt
+            .iter()
+            .find(|entry| entry.kind == Some(EsrtKind::Device))
+            .unwrap();
+        assert_eq!(
+            device_entry.last_attempt_status,
+            Some(UpdateStatus::Unsuccessful)
+        );
+
+        let devices = firmware.devices.unwrap();
+        let version = |kind: FirmwareDeviceKind, name: &str| {
+            devices
+                .iter()
+                .find(|device| device.kind == kind && device.name.as_deref() == Some(name))
+                .map(|device| device.version.as_str())
+        };
+        assert_eq!(
+            version(FirmwareDeviceKind::Storage, "nvme0"),
+            Some("1B2QGXA7")
+        );
+        assert_eq!(
+            version(FirmwareDeviceKind::Gpu, "card1"),
+            Some("113-D4120100-100")
+        );
+        assert_eq!(
+            version(FirmwareDeviceKind::Thunderbolt, "0-1"),
+            Some("36.0")
+        );
+        assert_eq!(
+            version(FirmwareDeviceKind::Thunderbolt, "0-0:1.1"),
+            Some("2.7")
+        );
+
+        // only physical nics with a real firmware version are listed
+        assert_eq!(
+            version(FirmwareDeviceKind::Network, "enp7s0"),
+            Some("3.30, 0x800005cc")
+        );
+        assert!(version(FirmwareDeviceKind::Network, "docker0").is_none());
+    }
+
+    #[test]
+    fn check_update_status() {
+        assert_eq!(update_status(6), UpdateStatus::PowerEventAc);
+        assert_eq!(update_status(0x1000), UpdateStatus::Other(0x1000));
+    }
+
+    #[tracing::instrument]
+    fn root_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs"))
+    }
+}
@@ -0,0 +1,141 @@
+use crate::prelude::internal::*;
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+/// An inventory of the firmware running on the computer and its devices.
+#[derive(
+    Clone, Debug, Default, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript,
+)]
+#[non_exhaustive]
+pub struct Firmware {
+    /// The system firmware (BIOS or UEFI) and embedded controller.
+    pub system: Option<SystemFirmware>,
+
+    /// Firmware that UEFI can update with capsules, from the EFI System
+    /// Resource Table (ESRT).
+    pub esrt: Option<Vec<EsrtEntry>>,
+
+    /// The firmware on individual devices, like SSDs and network cards.
+    pub devices: Option<Vec<DeviceFirmware>>,
+}
+
+/// The system firmware, as described by SMBIOS.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct SystemFirmware {
+    /// Who made the firmware, like `LENOVO` or `American Megatrends Inc.`.
+    pub vendor: Option<String>,
+
+    /// The firmware's version, like `R1OET52W (1.31 )`.
+    pub version: Option<String>,
+
+    /// The date the firmware was released.
+    pub date: Option<chrono::NaiveDate>,
+
+    /// The firmware's major and minor release, like `1.31`.
+    pub release: Option<String>,
+
+    /// The embedded controller's firmware release, like `1.17`.
+    pub ec_release: Option<String>,
+}
+
+/// One entry in the EFI System Resource Table.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct EsrtEntry {
+    /// The GUID that identifies which firmware this is.
+    pub class: Option<String>,
+
+    /// What the firmware runs on.
+    pub kind: Option<EsrtKind>,
+
+    /// The firmware's version.
+    ///
+    /// Vendors decide how to format these, so we leave them as numbers.
+    pub version: Option<u32>,
+
+    /// The oldest version the firmware can be downgraded to.
+    pub lowest_supported_version: Option<u32>,
+
+    /// The version the last update tried to install.
+    pub last_attempt_version: Option<u32>,
+
+    /// How the last update went.
+    pub last_attempt_status: Option<UpdateStatus>,
+}
+
+/// What a piece of firmware in the ESRT runs on.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum EsrtKind {
+    /// The system firmware itself.
+    System,
+    /// A device, like a docking station or fingerprint reader.
+    Device,
+    /// A UEFI driver.
+    Driver,
+    /// The firmware didn't say.
+    Unknown,
+}
+
+/// The result of a firmware update.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum UpdateStatus {
+    Success,
+    Unsuccessful,
+    InsufficientResources,
+    IncorrectVersion,
+    InvalidFormat,
+    AuthenticationError,
+    /// The update was stopped because the computer was unplugged.
+    PowerEventAc,
+    /// The update was stopped because the battery was too low.
+    PowerEventBattery,
+    UnsatisfiedDependencies,
+    /// A vendor-specific status.
+    Other(u32),
+}
+
+/// The firmware running on one device.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct DeviceFirmware {
+    /// The device's name, like `nvme0` or `enp7s0`.
+    pub name: Option<String>,
+
+    /// What kind of device this is.
+    pub kind: FirmwareDeviceKind,
+
+    /// The device's model, when it has one.
+    pub model: Option<String>,
+
+    /// The firmware's version.
+    pub version: String,
+}
+
+/// A kind of device with its own firmware.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum FirmwareDeviceKind {
+    /// An SSD or other storage controller.
+    Storage,
+    /// A network card.
+    Network,
+    /// A graphics card's video BIOS.
+    Gpu,
+    /// A Thunderbolt (or USB4) router or retimer.
+    Thunderbolt,
+}
+
+/// Gets an inventory of the computer's firmware.
+#[tracing::instrument]
+pub async fn get() -> Firmware {
+    #[cfg(target_os = "linux")]
+    return linux::get().await;
+
+    // TODO: use `Win32_BIOS` and `Win32_PnPSignedDriver` on windows
+    #[cfg(target_os = "windows")]
+    return Firmware::default();
+}
@@ -0,0 +1,219 @@
+use std::path::Path;
+
+use crate::prelude::internal::*;
+
+/// each taint, in the order of its bit in `/proc/sys/kernel/tainted`, along
+/// with the letter the kernel uses for it
+const TAINTS: &[(Taint, char)] = &[
+    (Taint::ProprietaryModule, 'P'),
+    (Taint::ForcedModule, 'F'),
+    (Taint::OutOfSpec, 'S'),
+    (Taint::ForcedUnload, 'R'),
+    (Taint::MachineCheck, 'M'),
+    (Taint::BadPage, 'B'),
+    (Taint::UserRequest, 'U'),
+    (Taint::Died, 'D'),
+    (Taint::OverriddenAcpiTable, 'A'),
+    (Taint::Warning, 'W'),
+    (Taint::StagingDriver, 'C'),
+    (Taint::FirmwareWorkaround, 'I'),
+    (Taint::OutOfTreeModule, 'O'),
+    (Taint::UnsignedModule, 'E'),
+    (Taint::SoftLockup, 'L'),
+    (Taint::LivePatched, 'K'),
+    (Taint::Auxiliary, 'X'),
+    (Taint::Randstruct, 'T'),
+    (Taint::Test, 'N'),
+];
+
+/// gets information about the running kernel.
+#[tracing::instrument]
+pub(super) async fn get() -> Kernel {
+    kernel("/").await
+}
+
+/// gets kernel info, reading `sysfs` and `procfs` under `root`.
+#[tracing::instrument]
+async fn kernel(root: impl AsRef<Path> + std::fmt::Debug) -> Kernel {
+    let root = root.as_ref();
+    let (module_path, modules_path) = (root.join("sys/module"), root.join("proc/modules"));
+
+    let (cmdline, tainted, modules) = futures::join! {
+        sysfs_value_opt::<String>(root.join("proc/cmdline")),
+        sysfs_value_opt::<u64>(root.join("proc/sys/kernel/tainted")),
+        modules(&modules_path, &module_path),
+    };
+
+    // modules that taint the kernel with their license or origin weren't
+    // built with it
+    let out_of_tree = modules.as_ref().map(|modules| {
+        modules
+            .iter()
+            .filter(|module| {
+                module.taint.as_ref().is_some_and(|taint| {
+                    taint.contains(&Taint::OutOfTreeModule)
+                        || taint.contains(&Taint::ProprietaryModule)
+                })
+            })
+            .map(|module| module.name.clone())
+            .collect()
+    });
+
+    Kernel {
+        cmdline: cmdline.map(Sensitive::new),
+        taint: tainted.map(decode_taint),
+        modules,
+        out_of_tree,
+    }
+}
+
+/// lists the loaded modules in `modules_path` (usually `/proc/modules`),
+/// filling in their versions from `module_path` (usually `/sys/module`).
+#[tracing::instrument]
+async fn modules(modules_path: &Path, module_path: &Path) -> Option<Vec<KernelModule>> {
+    let list = async_fs::read_to_string(modules_path)
+        .await
+        .inspect_err(|e| tracing::debug!("Couldn't read the loaded modules. (err: {e})"))
+        .ok()?;
+
+    let mut modules = Vec::new();
+    for line in list.lines() {
+        let Some(mut module) = parse_module(line) else {
+            tracing::debug!("Skipping odd module line: `{line}`");
+            continue;
+        };
+
+        let path = module_path.join(&module.name);
+        let (version, srcversion) = futures::join! {
+            sysfs_value_opt::<String>(path.join("version")),
+            sysfs_value_opt::<String>(path.join("srcversion")),
+        };
+        module.version = version;
+        module.srcversion = srcversion;
+
+        modules.push(module);
+    }
+
+    modules.sort_by(|a, b| a.name.cmp(&b.name));
+    Some(modules)
+}
+
+/// parses a line from `/proc/modules`, like:
+///
+/// ```text
+/// nvidia 56823808 1234 nvidia_uvm,nvidia_modeset, Live 0x0000000000000000 (POE)
+/// ```
+///
+/// that's the name, size, reference count, users, state, address, then any
+/// taint.
+#[tracing::instrument]
+fn parse_module(line: &str) -> Option<KernelModule> {
+    let mut parts = line.split_whitespace();
+    let name = parts.next()?.to_string();
+    let size = parts.next()?.parse().ok();
+    let _refcount = parts.next()?;
+
+    let used_by = parts
+        .next()
+        .filter(|users| *users != "-")
+        .map(|users| {
+            users
+                .split(',')
+                .filter(|user| !user.is_empty())
+                .map(String::from)
+                .collect::<Vec<_>>()
+        })
+        .filter(|users| !users.is_empty());
+
+    // skip the state and address
+    let taint = parts
+        .nth(2)
+        .and_then(|taint| taint.strip_prefix('(')?.strip_suffix(')'))
+        .map(|letters| {
+            letters
+                .chars()
+                .filter_map(|letter| TAINTS.iter().find(|(_, l)| *l == letter))
+                .map(|(taint, _)| *taint)
+                .collect()
+        });
+
+    Some(KernelModule {
+        name,
+        size,
+        used_by,
+        version: None,
+        srcversion: None,
+        taint,
+    })
+}
+
+/// decodes the bits in `/proc/sys/kernel/tainted`.
+#[tracing::instrument]
+fn decode_taint(tainted: u64) -> Vec<Taint> {
+    TAINTS
+        .iter()
+        .enumerate()
+        .filter(|(bit, _)| tainted & (1 << bit) != 0)
+        .map(|(_, (taint, _))| *taint)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn check_kernel() {
+        let kernel = kernel(root_path()).await;
+
+        let Some(Sensitive::Plain(cmdline)) = kernel.cmdline else {
+            panic!("no cmdline");
+        };
+        assert!(cmdline.contains("amd_pstate=active"));
+        assert_eq!(
+            kernel.taint,
+            Some(vec![Taint::ProprietaryModule, Taint::OutOfTreeModule])
+        );
+        assert_eq!(kernel.out_of_tree, Some(vec!["nvidia".into()]));
+
+        let modules = kernel.modules.unwrap();
+        assert_eq!(modules.len(), 3);
+
+        let nvidia = modules.iter().find(|m| m.name == "nvidia").unwrap();
+        assert_eq!(nvidia.version.as_deref(), Some("550.120"));
+        assert_eq!(
+            nvidia.used_by,
+            Some(vec!["nvidia_uvm".into(), "nvidia_modeset".into()])
+        );
+        assert_eq!(
+            nvidia.taint,
+            Some(vec![
+                Taint::ProprietaryModule,
+                Taint::OutOfTreeModule,
+                Taint::UnsignedModule
+            ])
+        );
+
+        let nvme = modules.iter().find(|m| m.name == "nvme").unwrap();
+        assert!(nvme.version.is_none());
+        assert_eq!(nvme.srcversion.as_deref(), Some("9C4D1F6A2F4E1B2C3D4E5F6"));
+        assert!(nvme.used_by.is_none());
+        assert!(nvme.taint.is_none());
+    }
+
+    #[test]
+    fn check_taint() {
+        assert!(decode_taint(0).is_empty());
+        assert_eq!(
+            decode_taint(1 << 9 | 1 << 13),
+            vec![Taint::Warning, Taint::UnsignedModule]
+        );
+    }
+
+    #[tracing::instrument]
+    fn root_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs"))
+    }
+}
@@ -0,0 +1,121 @@
+use crate::prelude::internal::*;
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+/// Information about the running kernel and its drivers.
+#[derive(
+    Clone, Debug, Default, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript,
+)]
+#[non_exhaustive]
+pub struct Kernel {
+    /// The options the kernel was booted with.
+    ///
+    /// These can name disks by their UUIDs, so they're sensitive.
+    pub cmdline: Option<Sensitive>,
+
+    /// Why the kernel is "tainted", which makes its bugs harder to trust.
+    ///
+    /// An empty list means the kernel isn't tainted.
+    pub taint: Option<Vec<Taint>>,
+
+    /// The kernel modules that are loaded.
+    pub modules: Option<Vec<KernelModule>>,
+
+    /// The names of loaded modules that didn't come with the kernel, like
+    /// `nvidia` or `zfs`.
+    pub out_of_tree: Option<Vec<String>>,
+}
+
+/// A loaded kernel module.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct KernelModule {
+    /// The module's name, like `amdgpu`.
+    pub name: String,
+
+    /// How much memory the module uses, in bytes.
+    pub size: Option<u64>,
+
+    /// The other modules that use this one.
+    pub used_by: Option<Vec<String>>,
+
+    /// The module's version, when it has one.
+    ///
+    /// Modules that come with the kernel usually don't.
+    pub version: Option<String>,
+
+    /// A checksum of the module's source code.
+    pub srcversion: Option<String>,
+
+    /// How the module taints the kernel.
+    pub taint: Option<Vec<Taint>>,
+}
+
+/// A reason the kernel is tainted.
+///
+/// These come from the kernel's `Documentation/admin-guide/tainted-kernels.rst`.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    TypeScript,
+)]
+#[non_exhaustive]
+pub enum Taint {
+    /// A module with a non-GPL license was loaded. (`P`)
+    ProprietaryModule,
+    /// A module was loaded by force. (`F`)
+    ForcedModule,
+    /// The kernel is running on a CPU that's out of spec. (`S`)
+    OutOfSpec,
+    /// A module was unloaded by force. (`R`)
+    ForcedUnload,
+    /// The CPU reported a machine check exception. (`M`)
+    MachineCheck,
+    /// A bad page of memory was found. (`B`)
+    BadPage,
+    /// Userspace asked to taint the kernel. (`U`)
+    UserRequest,
+    /// The kernel died recently, with an `OOPS` or `BUG`. (`D`)
+    Died,
+    /// An ACPI table was overridden. (`A`)
+    OverriddenAcpiTable,
+    /// The kernel printed a warning. (`W`)
+    Warning,
+    /// A driver from `staging` was loaded. (`C`)
+    StagingDriver,
+    /// The kernel is working around a firmware bug. (`I`)
+    FirmwareWorkaround,
+    /// A module from outside the kernel's source tree was loaded. (`O`)
+    OutOfTreeModule,
+    /// An unsigned module was loaded. (`E`)
+    UnsignedModule,
+    /// A soft lockup happened. (`L`)
+    SoftLockup,
+    /// The kernel was live patched. (`K`)
+    LivePatched,
+    /// An auxiliary taint, used by distributions. (`X`)
+    Auxiliary,
+    /// The kernel was built with struct randomization. (`T`)
+    Randstruct,
+    /// A test module was loaded. (`N`)
+    Test,
+}
+
+/// Gets information about the running kernel.
+#[tracing::instrument]
+pub async fn get() -> Kernel {
+    #[cfg(target_os = "linux")]
+    return linux::get().await;
+
+    // TODO: windows has drivers, not modules. `Win32_SystemDriver` lists them
+    #[cfg(target_os = "windows")]
+    return Kernel::default();
+}
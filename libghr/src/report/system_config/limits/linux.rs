@@ -0,0 +1,378 @@
+use std::path::{Path, PathBuf};
+
+use crate::prelude::internal::*;
+
+/// cgroup v1 reports "no memory limit" as the largest page-aligned `i64`.
+/// anything this big is really unlimited
+const V1_UNLIMITED: u64 = 1 << 62;
+
+/// gets the resource limits placed on this process.
+#[tracing::instrument]
+pub(super) async fn get() -> ResourceLimits {
+    limits("/").await
+}
+
+/// gets the limits on this process, reading `sysfs` and `procfs` under
+/// `root`.
+#[tracing::instrument]
+async fn limits(root: impl AsRef<Path> + std::fmt::Debug) -> ResourceLimits {
+    let root = root.as_ref();
+    let hierarchy = root.join("sys/fs/cgroup");
+
+    let Ok(membership) = async_fs::read_to_string(root.join("proc/self/cgroup")).await else {
+        tracing::debug!("Couldn't find this process' cgroups.");
+        return ResourceLimits::default();
+    };
+
+    // the unified hierarchy lists its controllers at the top
+    let mut limits = if async_fs::metadata(hierarchy.join("cgroup.controllers"))
+        .await
+        .is_ok()
+    {
+        v2(&hierarchy, &membership).await
+    } else {
+        v1(&hierarchy, &membership).await
+    };
+
+    limits.constrained = constrained(root, &limits).await;
+    limits
+}
+
+/// reads limits from the unified (v2) hierarchy at `hierarchy`.
+///
+/// `membership` is `/proc/self/cgroup`, which has one line, like
+/// `0::/system.slice/runner.service`.
+#[tracing::instrument(skip(membership))]
+async fn v2(hierarchy: &Path, membership: &str) -> ResourceLimits {
+    let Some(cgroup) = membership.lines().find_map(|line| line.strip_prefix("0::")) else {
+        return ResourceLimits::default();
+    };
+    let dirs = cgroup_dirs(hierarchy, cgroup).await;
+
+    // parents limit their children, so the lowest limit wins
+    let (cpu_quota, memory_max, pids_max, io_max, cpus) = futures::join! {
+        lowest(&dirs, "cpu.max", parse_cpu_max),
+        lowest(&dirs, "memory.max", parse_max),
+        lowest(&dirs, "pids.max", parse_max),
+        io_limits(&dirs, &["io.max"]),
+        sysfs_value_opt::<String>(dirs[0].join("cpuset.cpus.effective")),
+    };
+
+    ResourceLimits {
+        cgroup_version: Some(CgroupVersion::V2),
+        cgroup: Some(cgroup.to_string()),
+        cpu_quota,
+        cpus: cpus.as_deref().and_then(parse_cpu_list),
+        memory_max,
+        io_max,
+        pids_max,
+        constrained: false,
+    }
+}
+
+/// reads limits from the v1 hierarchies under `hierarchy`.
+///
+/// each controller has its own hierarchy, so `membership` has a line for
+/// each, like `4:cpu,cpuacct:/docker/3f4e...`.
+#[tracing::instrument(skip(membership))]
+async fn v1(hierarchy: &Path, membership: &str) -> ResourceLimits {
+    // finds our cgroup in a controller's hierarchy
+    let cgroup_of = |controller: &str| {
+        membership.lines().find_map(|line| {
+            let mut parts = line.splitn(3, ':');
+            let (_, controllers, cgroup) = (parts.next()?, parts.next()?, parts.next()?);
+            controllers
+                .split(',')
+                .any(|c| c == controller)
+                .then_some(cgroup)
+        })
+    };
+    let dirs_of = |controller: &'static str| async move {
+        match cgroup_of(controller) {
+            Some(cgroup) => cgroup_dirs(&hierarchy.join(controller), cgroup).await,
+            None => Vec::new(),
+        }
+    };
+
+    let (cpu, memory, pids, cpuset, blkio) = futures::join! {
+        dirs_of("cpu"),
+        dirs_of("memory"),
+        dirs_of("pids"),
+        dirs_of("cpuset"),
+        dirs_of("blkio"),
+    };
+
+    let (cpu_quota, memory_max, pids_max, io_max) = futures::join! {
+        cpu_quota_v1(&cpu),
+        lowest(&memory, "memory.limit_in_bytes", |limit| {
+            parse_max(limit).filter(|limit| *limit < V1_UNLIMITED)
+        }),
+        lowest(&pids, "pids.max", parse_max),
+        io_limits(&blkio, &[
+            "blkio.throttle.read_bps_device",
+            "blkio.throttle.write_bps_device",
+            "blkio.throttle.read_iops_device",
+            "blkio.throttle.write_iops_device",
+        ]),
+    };
+
+    let cpus = match cpuset.first() {
+        Some(dir) => match sysfs_value_opt::<String>(dir.join("cpuset.effective_cpus")).await {
+            Some(cpus) => Some(cpus),
+            None => sysfs_value_opt::<String>(dir.join("cpuset.cpus")).await,
+        },
+        None => None,
+    };
+
+    ResourceLimits {
+        cgroup_version: Some(CgroupVersion::V1),
+        cgroup: cgroup_of("cpu")
+            .or_else(|| cgroup_of("memory"))
+            .map(String::from),
+        cpu_quota,
+        cpus: cpus.as_deref().and_then(parse_cpu_list),
+        memory_max,
+        io_max,
+        pids_max,
+        constrained: false,
+    }
+}
+
+/// finds the directories for `cgroup` in `hierarchy`, from the cgroup
+/// itself up to the root.
+///
+/// in a cgroup namespace (like most containers), our cgroup is mounted as
+/// the root of the hierarchy, so we start there if it's missing.
+#[tracing::instrument]
+async fn cgroup_dirs(hierarchy: &Path, cgroup: &str) -> Vec<PathBuf> {
+    let leaf = hierarchy.join(cgroup.trim_start_matches('/'));
+    if async_fs::metadata(&leaf).await.is_err() {
+        return vec![hierarchy.to_path_buf()];
+    }
+
+    leaf.ancestors()
+        .take_while(|dir| dir.starts_with(hierarchy))
+        .map(Path::to_path_buf)
+        .collect()
+}
+
+/// reads `file` in each of `dirs`, returning the lowest limit.
+#[tracing::instrument(skip(parse))]
+async fn lowest<T: PartialOrd>(
+    dirs: &[PathBuf],
+    file: &str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Option<T> {
+    let mut lowest = None;
+    for dir in dirs {
+        let Some(limit) = sysfs_value_opt::<String>(dir.join(file))
+            .await
+            .as_deref()
+            .and_then(&parse)
+        else {
+            continue;
+        };
+
+        if lowest.as_ref().map_or(true, |lowest| limit < *lowest) {
+            lowest = Some(limit);
+        }
+    }
+
+    lowest
+}
+
+/// finds the cpu quota on cgroup v1, which splits it into two files.
+#[tracing::instrument]
+async fn cpu_quota_v1(dirs: &[PathBuf]) -> Option<f64> {
+    let mut lowest: Option<f64> = None;
+    for dir in dirs {
+        let (quota, period) = futures::join! {
+            sysfs_value_opt::<i64>(dir.join("cpu.cfs_quota_us")),
+            sysfs_value_opt::<u64>(dir.join("cpu.cfs_period_us")),
+        };
+
+        // a quota of `-1` means there isn't one
+        if let (Some(quota @ 1..), Some(period @ 1..)) = (quota, period) {
+            let cpus = quota as f64 / period as f64;
+            lowest = Some(lowest.map_or(cpus, |lowest| lowest.min(cpus)));
+        }
+    }
+
+    lowest
+}
+
+/// reads the io limits in `files` for each of `dirs`, keeping the lowest
+/// limit for each device.
+#[tracing::instrument]
+async fn io_limits(dirs: &[PathBuf], files: &[&str]) -> Option<Vec<IoLimit>> {
+    let mut limits: Vec<IoLimit> = Vec::new();
+    for dir in dirs {
+        for file in files {
+            let Ok(contents) = async_fs::read_to_string(dir.join(file)).await else {
+                continue;
+            };
+
+            for limit in parse_io_limits(file, &contents) {
+                match limits.iter_mut().find(|l| l.device == limit.device) {
+                    Some(existing) => {
+                        existing.read_bps = min_limit(existing.read_bps, limit.read_bps);
+                        existing.write_bps = min_limit(existing.write_bps, limit.write_bps);
+                        existing.read_iops = min_limit(existing.read_iops, limit.read_iops);
+                        existing.write_iops = min_limit(existing.write_iops, limit.write_iops);
+                    }
+                    None => limits.push(limit),
+                }
+            }
+        }
+    }
+
+    (!limits.is_empty()).then_some(limits)
+}
+
+/// picks the lower of two limits, where `None` is unlimited.
+fn min_limit(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// parses an io limit file.
+///
+/// v2's `io.max` has lines like `259:0 rbps=max wbps=104857600 riops=max
+/// wiops=max`. v1 has a file for each kind, with lines like `8:0 1048576`.
+#[tracing::instrument(skip(contents))]
+fn parse_io_limits(file: &str, contents: &str) -> Vec<IoLimit> {
+    let mut limits = Vec::new();
+    for line in contents.lines() {
+        let mut parts = line.split_whitespace();
+        let Some(device) = parts.next() else {
+            continue;
+        };
+
+        let mut limit = IoLimit {
+            device: device.to_string(),
+            read_bps: None,
+            write_bps: None,
+            read_iops: None,
+            write_iops: None,
+        };
+
+        for part in parts {
+            let (key, value) = part.split_once('=').unwrap_or((file, part));
+            let value = parse_max(value);
+
+            match key {
+                "rbps" | "blkio.throttle.read_bps_device" => limit.read_bps = value,
+                "wbps" | "blkio.throttle.write_bps_device" => limit.write_bps = value,
+                "riops" | "blkio.throttle.read_iops_device" => limit.read_iops = value,
+                "wiops" | "blkio.throttle.write_iops_device" => limit.write_iops = value,
+                _ => (),
+            }
+        }
+
+        limits.push(limit);
+    }
+
+    limits
+}
+
+/// parses a limit that might be `max`.
+fn parse_max(value: &str) -> Option<u64> {
+    match value.trim() {
+        "max" => None,
+        value => value.parse().ok(),
+    }
+}
+
+/// parses v2's `cpu.max`, like `400000 100000`, into a number of cpus.
+fn parse_cpu_max(value: &str) -> Option<f64> {
+    let (quota, period) = value.split_once(' ')?;
+    let quota = parse_max(quota)?;
+    let period = parse_max(period).filter(|period| *period > 0)?;
+
+    Some(quota as f64 / period as f64)
+}
+
+/// checks whether the limits leave us with less than the computer has.
+#[tracing::instrument]
+async fn constrained(root: &Path, limits: &ResourceLimits) -> bool {
+    let (online, meminfo) = futures::join! {
+        sysfs_value_opt::<String>(root.join("sys/devices/system/cpu/online")),
+        async_fs::read_to_string(root.join("proc/meminfo")),
+    };
+
+    let online = online
+        .as_deref()
+        .and_then(parse_cpu_list)
+        .map(|cpus| cpus.len());
+
+    // `MemTotal:       32617040 kB`
+    let mem_total = meminfo.ok().and_then(|meminfo| {
+        let line = meminfo.lines().find(|line| line.starts_with("MemTotal:"))?;
+        let kib = line.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+        Some(kib * 1024)
+    });
+
+    let fewer_cpus = online.is_some_and(|online| {
+        limits.cpu_quota.is_some_and(|quota| quota < online as f64)
+            || limits.cpus.as_ref().is_some_and(|cpus| cpus.len() < online)
+    });
+    let less_memory = mem_total.is_some_and(|total| {
+        limits
+            .memory_max
+            .is_some_and(|memory_max| memory_max < total)
+    });
+
+    fewer_cpus || less_memory
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn check_limits() {
+        let limits = limits(root_path()).await;
+
+        assert_eq!(limits.cgroup_version, Some(CgroupVersion::V2));
+        assert_eq!(
+            limits.cgroup.as_deref(),
+            Some("/system.slice/runner.service")
+        );
+        assert_eq!(limits.cpu_quota, Some(4.0));
+        assert_eq!(limits.cpus, Some(vec![0, 1, 2, 3]));
+
+        // the slice's limit is lower than the service's
+        assert_eq!(limits.memory_max, Some(8 << 30));
+        assert!(limits.pids_max.is_none());
+
+        let io = limits.io_max.unwrap();
+        assert_eq!(io.len(), 1);
+        assert_eq!(io[0].device, "259:0");
+        assert_eq!(io[0].write_bps, Some(104_857_600));
+        assert!(io[0].read_bps.is_none());
+
+        assert!(limits.constrained);
+    }
+
+    #[test]
+    fn check_parsers() {
+        assert_eq!(parse_cpu_max("max 100000"), None);
+        assert_eq!(parse_cpu_max("50000 100000"), Some(0.5));
+        assert_eq!(
+            parse_cpu_list("0-3,8,10-11\n"),
+            Some(vec![0, 1, 2, 3, 8, 10, 11])
+        );
+
+        let v1 = parse_io_limits("blkio.throttle.read_iops_device", "8:0 300\n8:16 500\n");
+        assert_eq!(v1.len(), 2);
+        assert_eq!(v1[1].read_iops, Some(500));
+    }
+
+    #[tracing::instrument]
+    fn root_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs"))
+    }
+}
@@ -0,0 +1,81 @@
+use crate::prelude::internal::*;
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+/// The resources the report's process is allowed to use, which can be less
+/// than the hardware has.
+///
+/// Containers and service managers limit their processes with control
+/// groups ("cgroups"). When they do, the CPU and RAM reported elsewhere
+/// describe the whole computer, not what was actually usable.
+#[derive(
+    Clone, Debug, Default, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript,
+)]
+#[non_exhaustive]
+pub struct ResourceLimits {
+    /// The version of cgroups the system uses.
+    pub cgroup_version: Option<CgroupVersion>,
+
+    /// The process' cgroup, like `/system.slice/runner.service`.
+    pub cgroup: Option<String>,
+
+    /// How many CPUs' worth of time the process can use, like `4.0` or
+    /// `0.5`.
+    pub cpu_quota: Option<f64>,
+
+    /// The CPUs the process is allowed to run on.
+    pub cpus: Option<Vec<u32>>,
+
+    /// The most memory the process can use, in bytes.
+    pub memory_max: Option<u64>,
+
+    /// Limits on reading and writing to block devices.
+    pub io_max: Option<Vec<IoLimit>>,
+
+    /// The most processes (and threads) that can run at once.
+    pub pids_max: Option<u64>,
+
+    /// Whether the limits leave the process with fewer CPUs or less memory
+    /// than the computer has.
+    pub constrained: bool,
+}
+
+/// A version of Linux's control groups.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum CgroupVersion {
+    /// The original cgroups, with a hierarchy for each controller.
+    V1,
+    /// The unified hierarchy.
+    V2,
+}
+
+/// The limits on one block device.
+///
+/// Missing limits are unlimited.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct IoLimit {
+    /// The device's major and minor numbers, like `259:0`.
+    pub device: String,
+    /// Bytes read per second.
+    pub read_bps: Option<u64>,
+    /// Bytes written per second.
+    pub write_bps: Option<u64>,
+    /// Read operations per second.
+    pub read_iops: Option<u64>,
+    /// Write operations per second.
+    pub write_iops: Option<u64>,
+}
+
+/// Gets the resource limits placed on this process.
+#[tracing::instrument]
+pub async fn get() -> ResourceLimits {
+    #[cfg(target_os = "linux")]
+    return linux::get().await;
+
+    // TODO: check for job object limits on windows
+    #[cfg(target_os = "windows")]
+    return ResourceLimits::default();
+}
@@ -0,0 +1,443 @@
+use std::path::{Path, PathBuf};
+
+use crate::{prelude::internal::*, report::components};
+
+/// gets information about the system's memory layout and management.
+#[tracing::instrument]
+pub(super) async fn get() -> Memory {
+    memory("/").await
+}
+
+/// gets memory info, reading `sysfs` and `procfs` under `root`.
+#[tracing::instrument]
+async fn memory(root: impl AsRef<Path> + std::fmt::Debug) -> Memory {
+    let root = root.as_ref();
+    let (node_path, swaps_path) = (
+        root.join("sys/devices/system/node"),
+        root.join("proc/swaps"),
+    );
+    let (block_path, zswap_path) = (
+        root.join("sys/block"),
+        root.join("sys/module/zswap/parameters"),
+    );
+    let (thp_path, hugepages_path, edac_path) = (
+        root.join("sys/kernel/mm/transparent_hugepage"),
+        root.join("sys/kernel/mm/hugepages"),
+        root.join("sys/devices/system/edac/mc"),
+    );
+
+    let (numa_nodes, swap, zram, zswap) = futures::join! {
+        numa_nodes(&node_path),
+        swap(&swaps_path),
+        zram(&block_path),
+        zswap(&zswap_path),
+    };
+    let (transparent_hugepages, hugepages, ecc) = futures::join! {
+        transparent_hugepages(&thp_path),
+        hugepages(&hugepages_path),
+        ecc(&edac_path),
+    };
+
+    Memory {
+        numa_nodes,
+        swap,
+        zram,
+        zswap,
+        transparent_hugepages,
+        hugepages,
+        ecc,
+    }
+}
+
+/// lists the entries in `path` named `prefix` and a number, like `node0`,
+/// in order.
+#[tracing::instrument]
+async fn numbered(path: &Path, prefix: &str) -> Vec<(u32, PathBuf)> {
+    numbered_entries(path, prefix)
+        .await
+        .into_iter()
+        .filter_map(|path| {
+            let name = path.file_name()?.to_string_lossy();
+            let number = name.strip_prefix(prefix)?.parse().ok()?;
+            Some((number, path))
+        })
+        .collect()
+}
+
+/// reads each numa node in `node_path` (usually `/sys/devices/system/node`).
+#[tracing::instrument]
+async fn numa_nodes(node_path: &Path) -> Option<Vec<NumaNode>> {
+    let mut nodes = Vec::new();
+
+    for (id, path) in numbered(node_path, "node").await {
+        let (meminfo, cpus, distances) = futures::join! {
+            async_fs::read_to_string(path.join("meminfo")),
+            sysfs_value_opt::<String>(path.join("cpulist")),
+            sysfs_value_opt::<String>(path.join("distance")),
+        };
+
+        // like `Node 0 MemTotal:       32617040 kB`
+        let meminfo = meminfo.unwrap_or_default();
+        let field = |key: &str| {
+            let line = meminfo.lines().find(|line| line.contains(key))?;
+            let kib = line.split_whitespace().nth(3)?.parse::<u64>().ok()?;
+            Some(kib * 1024)
+        };
+
+        nodes.push(NumaNode {
+            id,
+            memory_total: field("MemTotal:"),
+            memory_free: field("MemFree:"),
+            cpus: cpus.as_deref().and_then(parse_cpu_list),
+            distances: distances.and_then(|distances| {
+                distances
+                    .split_whitespace()
+                    .map(|d| d.parse().ok())
+                    .collect()
+            }),
+        });
+    }
+
+    (!nodes.is_empty()).then_some(nodes)
+}
+
+/// reads the swap in use from `swaps_path` (usually `/proc/swaps`).
+#[tracing::instrument]
+async fn swap(swaps_path: &Path) -> Option<Vec<SwapDevice>> {
+    let swaps = async_fs::read_to_string(swaps_path)
+        .await
+        .inspect_err(|e| tracing::debug!("Couldn't read the swap devices. (err: {e})"))
+        .ok()?;
+
+    Some(parse_swaps(&swaps))
+}
+
+/// parses `/proc/swaps`, like:
+///
+/// ```text
+/// Filename        Type        Size        Used    Priority
+/// /dev/nvme0n1p3  partition   16777212    0       -2
+/// ```
+///
+/// sizes are in kibibytes.
+#[tracing::instrument(skip(swaps))]
+fn parse_swaps(swaps: &str) -> Vec<SwapDevice> {
+    swaps
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+
+            // spaces in the path are escaped as `\040`
+            let path = parts.next()?.replace("\\040", " ");
+
+            Some(SwapDevice {
+                path,
+                kind: parts.next().map(String::from),
+                size: parts
+                    .next()
+                    .and_then(|kib| kib.parse::<u64>().ok())
+                    .map(|kib| kib * 1024),
+                used: parts
+                    .next()
+                    .and_then(|kib| kib.parse::<u64>().ok())
+                    .map(|kib| kib * 1024),
+                priority: parts.next().and_then(|priority| priority.parse().ok()),
+            })
+        })
+        .collect()
+}
+
+/// reads each zram device in `block_path` (usually `/sys/block`).
+#[tracing::instrument]
+async fn zram(block_path: &Path) -> Option<Vec<ZramDevice>> {
+    let mut devices = Vec::new();
+
+    for (id, path) in numbered(block_path, "zram").await {
+        let (disk_size, algorithm, mm_stat) = futures::join! {
+            sysfs_value_opt::<u64>(path.join("disksize")),
+            sysfs_value_opt::<String>(path.join("comp_algorithm")),
+            sysfs_value_opt::<String>(path.join("mm_stat")),
+        };
+
+        // `mm_stat` starts with the original and compressed sizes
+        let mut stats = mm_stat
+            .iter()
+            .flat_map(|stat| stat.split_whitespace())
+            .map(|stat| stat.parse::<u64>().ok());
+
+        devices.push(ZramDevice {
+            name: format!("zram{id}"),
+            // an unused device has a size of zero
+            disk_size: disk_size.filter(|size| *size > 0),
+            algorithm: algorithm.as_deref().and_then(current_choice),
+            original_size: stats.next().flatten(),
+            compressed_size: stats.next().flatten(),
+        });
+    }
+
+    (!devices.is_empty()).then_some(devices)
+}
+
+/// reads the zswap settings in `zswap_path` (usually
+/// `/sys/module/zswap/parameters`).
+#[tracing::instrument]
+async fn zswap(zswap_path: &Path) -> Option<Zswap> {
+    let (enabled, compressor, zpool, max_pool_percent) = futures::join! {
+        sysfs_value_opt::<String>(zswap_path.join("enabled")),
+        sysfs_value_opt::<String>(zswap_path.join("compressor")),
+        sysfs_value_opt::<String>(zswap_path.join("zpool")),
+        sysfs_value_opt::<u8>(zswap_path.join("max_pool_percent")),
+    };
+
+    // without `enabled`, zswap isn't built into the kernel
+    let enabled = enabled?;
+
+    Some(Zswap {
+        enabled: Some(enabled == "Y"),
+        compressor,
+        zpool,
+        max_pool_percent,
+    })
+}
+
+/// reads the transparent huge page settings in `thp_path` (usually
+/// `/sys/kernel/mm/transparent_hugepage`).
+#[tracing::instrument]
+async fn transparent_hugepages(thp_path: &Path) -> Option<TransparentHugepages> {
+    let (enabled, defrag) = futures::join! {
+        sysfs_value_opt::<String>(thp_path.join("enabled")),
+        sysfs_value_opt::<String>(thp_path.join("defrag")),
+    };
+
+    if enabled.is_none() && defrag.is_none() {
+        return None;
+    }
+
+    let enabled = enabled.and_then(|enabled| {
+        parse_choices(&enabled, |mode| match mode {
+            "always" => Some(ThpMode::Always),
+            "madvise" => Some(ThpMode::Madvise),
+            "never" => Some(ThpMode::Never),
+            other => {
+                tracing::debug!("Unknown transparent huge page mode: `{other}`");
+                None
+            }
+        })
+        .1
+    });
+
+    Some(TransparentHugepages {
+        enabled,
+        defrag: defrag.as_deref().and_then(current_choice),
+    })
+}
+
+/// reads each huge page pool in `hugepages_path` (usually
+/// `/sys/kernel/mm/hugepages`).
+///
+/// each pool is named after its page size, like `hugepages-2048kB`.
+#[tracing::instrument]
+async fn hugepages(hugepages_path: &Path) -> Option<Vec<HugepagePool>> {
+    let entries = components::linux::devices(hugepages_path).await.ok()?;
+
+    let mut pools = Vec::new();
+    for path in entries.iter().map(|entry| entry.path()) {
+        let Some(page_size) = path
+            .file_name()
+            .and_then(|name| {
+                let name = name.to_string_lossy();
+                name.strip_prefix("hugepages-")?
+                    .strip_suffix("kB")?
+                    .parse::<u64>()
+                    .ok()
+            })
+            .map(|kib| kib * 1024)
+        else {
+            continue;
+        };
+
+        let (total, free, reserved, surplus) = futures::join! {
+            sysfs_value_opt::<u64>(path.join("nr_hugepages")),
+            sysfs_value_opt::<u64>(path.join("free_hugepages")),
+            sysfs_value_opt::<u64>(path.join("resv_hugepages")),
+            sysfs_value_opt::<u64>(path.join("surplus_hugepages")),
+        };
+
+        pools.push(HugepagePool {
+            page_size,
+            total,
+            free,
+            reserved,
+            surplus,
+        });
+    }
+
+    pools.sort_by_key(|pool| pool.page_size);
+    (!pools.is_empty()).then_some(pools)
+}
+
+/// reads each memory controller in `edac_path` (usually
+/// `/sys/devices/system/edac/mc`).
+///
+/// there are only controllers here when an edac driver is loaded, and most
+/// only load when the memory has ecc.
+#[tracing::instrument]
+async fn ecc(edac_path: &Path) -> Option<Ecc> {
+    let mut controllers = Vec::new();
+
+    for (id, path) in numbered(edac_path, "mc").await {
+        let (model, size_mib, correctable_errors, uncorrectable_errors) = futures::join! {
+            sysfs_value_opt::<String>(path.join("mc_name")),
+            sysfs_value_opt::<u64>(path.join("size_mb")),
+            sysfs_value_opt::<u64>(path.join("ce_count")),
+            sysfs_value_opt::<u64>(path.join("ue_count")),
+        };
+
+        // drivers that track chip-select rows list ranks instead of dimms,
+        // with the same files
+        let mut dimms = Vec::new();
+        for prefix in ["dimm", "rank"] {
+            for (_, dimm) in numbered(&path, prefix).await {
+                dimms.push(edac_dimm(&dimm).await);
+            }
+        }
+
+        controllers.push(MemoryController {
+            name: format!("mc{id}"),
+            model,
+            size: size_mib.map(|mib| mib * 1024 * 1024),
+            correctable_errors,
+            uncorrectable_errors,
+            dimms: (!dimms.is_empty()).then_some(dimms),
+        });
+    }
+
+    if controllers.is_empty() {
+        return None;
+    }
+
+    let total = |count: fn(&MemoryController) -> Option<u64>| {
+        controllers.iter().map(count).sum::<Option<u64>>()
+    };
+
+    Some(Ecc {
+        correctable_errors: total(|mc| mc.correctable_errors),
+        uncorrectable_errors: total(|mc| mc.uncorrectable_errors),
+        controllers,
+    })
+}
+
+/// reads one memory module (or rank) under a controller, like `mc0/dimm0`.
+#[tracing::instrument]
+async fn edac_dimm(path: &Path) -> EdacDimm {
+    let (label, size_mib, memory_type) = futures::join! {
+        sysfs_value_opt::<String>(path.join("dimm_label")),
+        sysfs_value_opt::<u64>(path.join("size")),
+        sysfs_value_opt::<String>(path.join("dimm_mem_type")),
+    };
+    let (edac_mode, correctable_errors, uncorrectable_errors) = futures::join! {
+        sysfs_value_opt::<String>(path.join("dimm_edac_mode")),
+        sysfs_value_opt::<u64>(path.join("dimm_ce_count")),
+        sysfs_value_opt::<u64>(path.join("dimm_ue_count")),
+    };
+
+    EdacDimm {
+        label: label.filter(|label| !label.is_empty()),
+        size: size_mib.map(|mib| mib * 1024 * 1024),
+        memory_type,
+        edac_mode,
+        correctable_errors,
+        uncorrectable_errors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn check_memory() {
+        let memory = memory(root_path()).await;
+
+        // a two-socket server
+        let nodes = memory.numa_nodes.unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[1].id, 1);
+        assert_eq!(nodes[1].memory_total, Some(16_308_520 * 1024));
+        assert_eq!(nodes[1].memory_free, Some(10_066_422 * 1024));
+        assert_eq!(nodes[1].cpus, Some((4..8).collect()));
+        assert_eq!(nodes[1].distances, Some(vec![21, 10]));
+
+        let swap = memory.swap.unwrap();
+        assert_eq!(swap.len(), 2);
+        assert_eq!(swap[0].path, "/dev/zram0");
+        assert_eq!(swap[1].path, "/swap file");
+        assert_eq!(swap[1].kind.as_deref(), Some("file"));
+        assert_eq!(swap[1].size, Some(8_388_604 * 1024));
+        assert_eq!(swap[1].priority, Some(-2));
+
+        let zram = memory.zram.unwrap();
+        assert_eq!(zram[0].name, "zram0");
+        assert_eq!(zram[0].algorithm.as_deref(), Some("zstd"));
+        assert_eq!(zram[0].disk_size, Some(8_589_934_592));
+        assert_eq!(zram[0].original_size, Some(104_857_600));
+        assert_eq!(zram[0].compressed_size, Some(26_214_400));
+
+        let zswap = memory.zswap.unwrap();
+        assert_eq!(zswap.enabled, Some(false));
+        assert_eq!(zswap.compressor.as_deref(), Some("lzo"));
+        assert_eq!(zswap.max_pool_percent, Some(20));
+
+        let thp = memory.transparent_hugepages.unwrap();
+        assert_eq!(thp.enabled, Some(ThpMode::Madvise));
+        assert_eq!(thp.defrag.as_deref(), Some("madvise"));
+
+        let hugepages = memory.hugepages.unwrap();
+        assert_eq!(hugepages.len(), 2);
+        assert_eq!(hugepages[0].page_size, 2 * 1024 * 1024);
+        assert_eq!(hugepages[0].total, Some(512));
+        assert_eq!(hugepages[0].free, Some(500));
+        assert_eq!(hugepages[1].page_size, 1024 * 1024 * 1024);
+        assert_eq!(hugepages[1].total, Some(0));
+
+        // one of the modules has corrected a few errors
+        let ecc = memory.ecc.unwrap();
+        assert_eq!(ecc.correctable_errors, Some(3));
+        assert_eq!(ecc.uncorrectable_errors, Some(0));
+        assert_eq!(ecc.controllers.len(), 3);
+
+        let mc0 = &ecc.controllers[0];
+        assert_eq!(mc0.model.as_deref(), Some("Skylake Socket#0 IMC#0"));
+        assert_eq!(mc0.size, Some(16_384 * 1024 * 1024));
+
+        let dimms = mc0.dimms.clone().unwrap();
+        assert_eq!(dimms.len(), 2);
+        assert_eq!(dimms[1].edac_mode.as_deref(), Some("SECDED"));
+        assert_eq!(dimms[1].memory_type.as_deref(), Some("Registered-DDR4"));
+        assert_eq!(dimms[1].correctable_errors, Some(3));
+
+        // this controller lists its memory by rank
+        let ranks = ecc.controllers[2].dimms.clone().unwrap();
+        assert_eq!(ranks.len(), 2);
+        assert_eq!(ranks[1].label.as_deref(), Some("mc#2csrow#1channel#0"));
+        assert_eq!(ranks[1].size, Some(16_384 * 1024 * 1024));
+        assert_eq!(ranks[1].memory_type.as_deref(), Some("Unbuffered-DDR4"));
+    }
+
+    #[test]
+    fn check_parse_swaps() {
+        let swaps = "Filename\tType\tSize\tUsed\tPriority\n/dev/sda2 partition 1024 512 -2\n";
+        let swaps = parse_swaps(swaps);
+
+        assert_eq!(swaps.len(), 1);
+        assert_eq!(swaps[0].size, Some(1024 * 1024));
+        assert_eq!(swaps[0].used, Some(512 * 1024));
+    }
+
+    #[tracing::instrument]
+    fn root_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs"))
+    }
+}
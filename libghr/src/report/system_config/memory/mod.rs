@@ -0,0 +1,247 @@
+use crate::prelude::internal::*;
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+/// How the system's memory is laid out and managed.
+///
+/// All sizes are in bytes.
+#[derive(
+    Clone, Debug, Default, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript,
+)]
+#[non_exhaustive]
+pub struct Memory {
+    /// The NUMA nodes, each with its own memory and processors.
+    ///
+    /// Most desktops and laptops have only one.
+    pub numa_nodes: Option<Vec<NumaNode>>,
+
+    /// The partitions and files used for swap.
+    pub swap: Option<Vec<SwapDevice>>,
+
+    /// Compressed block devices in memory, often used for swap.
+    pub zram: Option<Vec<ZramDevice>>,
+
+    /// The compressed cache for pages on their way to swap.
+    pub zswap: Option<Zswap>,
+
+    /// How transparent huge pages are used.
+    pub transparent_hugepages: Option<TransparentHugepages>,
+
+    /// The pools of reserved huge pages, one for each page size.
+    pub hugepages: Option<Vec<HugepagePool>>,
+
+    /// Error correction, from the memory controllers' error detection and
+    /// correction (EDAC) drivers.
+    pub ecc: Option<Ecc>,
+}
+
+/// A NUMA node.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct NumaNode {
+    /// The node's number.
+    pub id: u32,
+
+    /// How much memory the node has.
+    pub memory_total: Option<u64>,
+
+    /// How much of the node's memory is free.
+    pub memory_free: Option<u64>,
+
+    /// The processors in the node.
+    pub cpus: Option<Vec<u32>>,
+
+    /// How far each node's memory is from this one, in node order.
+    ///
+    /// A node is `10` from itself. Higher numbers are slower to reach.
+    pub distances: Option<Vec<u32>>,
+}
+
+/// A partition or file used for swap.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct SwapDevice {
+    /// The swap's path, like `/dev/nvme0n1p3` or `/swapfile`.
+    pub path: String,
+
+    /// Whether the swap is a partition or a file.
+    pub kind: Option<String>,
+
+    /// The swap's size.
+    pub size: Option<u64>,
+
+    /// How much of the swap is in use.
+    pub used: Option<u64>,
+
+    /// The swap's priority. Higher priority swap is used first.
+    pub priority: Option<i32>,
+}
+
+/// A compressed block device in memory.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct ZramDevice {
+    /// The device's name, like `zram0`.
+    pub name: String,
+
+    /// The most (uncompressed) data the device can hold.
+    pub disk_size: Option<u64>,
+
+    /// The compression algorithm in use, like `zstd`.
+    pub algorithm: Option<String>,
+
+    /// How much data is stored, before compression.
+    pub original_size: Option<u64>,
+
+    /// How much memory the stored data takes, after compression.
+    pub compressed_size: Option<u64>,
+}
+
+/// The compressed swap cache.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct Zswap {
+    /// Whether zswap is turned on.
+    pub enabled: Option<bool>,
+
+    /// The compression algorithm, like `zstd`.
+    pub compressor: Option<String>,
+
+    /// The allocator that stores compressed pages, like `zsmalloc`.
+    pub zpool: Option<String>,
+
+    /// The largest the cache can get, as a percentage of memory.
+    pub max_pool_percent: Option<u8>,
+}
+
+/// How transparent huge pages (THP) are used.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct TransparentHugepages {
+    /// When processes get huge pages.
+    pub enabled: Option<ThpMode>,
+
+    /// When the kernel compacts memory to make huge pages, like
+    /// `defer+madvise`.
+    pub defrag: Option<String>,
+}
+
+/// When processes get transparent huge pages.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    TypeScript,
+)]
+#[non_exhaustive]
+pub enum ThpMode {
+    /// Whenever possible.
+    Always,
+    /// Only for memory that asks for them with `madvise`.
+    Madvise,
+    /// Never.
+    Never,
+}
+
+/// A pool of reserved huge pages.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct HugepagePool {
+    /// The size of each page, like 2 MiB or 1 GiB.
+    pub page_size: u64,
+
+    /// How many pages are in the pool.
+    pub total: Option<u64>,
+
+    /// How many pages aren't in use.
+    pub free: Option<u64>,
+
+    /// How many pages are promised to processes, but not yet in use.
+    pub reserved: Option<u64>,
+
+    /// How many pages were made beyond the pool's size.
+    pub surplus: Option<u64>,
+}
+
+/// The error correction the memory controllers report.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct Ecc {
+    /// Each memory controller with an EDAC driver.
+    pub controllers: Vec<MemoryController>,
+
+    /// How many errors were corrected, across all controllers.
+    pub correctable_errors: Option<u64>,
+
+    /// How many errors couldn't be corrected, across all controllers.
+    ///
+    /// Any of these means memory was corrupted.
+    pub uncorrectable_errors: Option<u64>,
+}
+
+/// A memory controller.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct MemoryController {
+    /// The controller's name, like `mc0`.
+    pub name: String,
+
+    /// The controller's model, like `Skylake Socket#0 IMC#0`.
+    pub model: Option<String>,
+
+    /// How much memory the controller manages.
+    pub size: Option<u64>,
+
+    /// How many errors the controller corrected.
+    pub correctable_errors: Option<u64>,
+
+    /// How many errors the controller couldn't correct.
+    pub uncorrectable_errors: Option<u64>,
+
+    /// The memory modules on the controller.
+    pub dimms: Option<Vec<EdacDimm>>,
+}
+
+/// A memory module, as its memory controller sees it.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct EdacDimm {
+    /// The module's slot, like `CPU_SrcID#0_MC#0_Chan#0_DIMM#0`.
+    pub label: Option<String>,
+
+    /// The module's size.
+    pub size: Option<u64>,
+
+    /// The kind of memory, like `Registered-DDR4`.
+    pub memory_type: Option<String>,
+
+    /// The error correction in use, like `SECDED`.
+    ///
+    /// `None` (as a string) means the module doesn't have ECC.
+    pub edac_mode: Option<String>,
+
+    /// How many errors were corrected on this module.
+    pub correctable_errors: Option<u64>,
+
+    /// How many errors couldn't be corrected on this module.
+    pub uncorrectable_errors: Option<u64>,
+}
+
+/// Gets information about the system's memory layout and management.
+#[tracing::instrument]
+pub async fn get() -> Memory {
+    #[cfg(target_os = "linux")]
+    return linux::get().await;
+
+    // TODO: `GetNumaHighestNodeNumber` and `Win32_PageFileUsage` have some
+    // of this
+    #[cfg(target_os = "windows")]
+    return Memory::default();
+}
@@ -1,9 +1,29 @@
 //! `system_config`: Info about the system's configuration
 
+pub mod acpi;
+pub mod firmware;
+pub mod kernel;
+pub mod limits;
+pub mod memory;
+pub mod powercap;
+pub mod security;
 pub mod sleep;
+pub mod thermal;
+pub mod virtualization;
 
 use crate::prelude::internal::*;
+use acpi::Acpi;
+use firmware::Firmware;
+use kernel::Kernel;
+use limits::ResourceLimits;
+use memory::Memory;
+use powercap::Powercap;
+use security::Security;
 use sleep::Sleep;
+use thermal::Thermal;
+use virtualization::Virtualization;
+
+use std::time::Duration;
 
 use futures::FutureExt as _;
 
@@ -11,18 +31,49 @@ use futures::FutureExt as _;
 #[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
 #[non_exhaustive]
 pub struct SystemConfInfo {
+    pub acpi: Acpi,
+    pub firmware: Firmware,
+    pub kernel: Kernel,
+    pub limits: ResourceLimits,
+    pub memory: Memory,
+    pub powercap: Powercap,
+    pub security: Security,
     pub sleep: Sleep,
+    pub thermal: Thermal,
+    pub virtualization: Virtualization,
 }
 
 impl SystemConfInfo {
     /// Gathers information about the system configuration.
+    ///
+    /// When `power_sampling` is given, power draw is measured for that long.
     #[tracing::instrument]
-    pub async fn new() -> Self {
+    pub async fn new(power_sampling: Option<Duration>) -> Self {
         // we'll get all info using heap-allocated futures.
         //
         // this avoids overflowing the stack.
+        let acpi = acpi::get().boxed().await;
+        let firmware = firmware::get().boxed().await;
+        let kernel = kernel::get().boxed().await;
+        let limits = limits::get().boxed().await;
+        let memory = memory::get().boxed().await;
+        let powercap = powercap::get(power_sampling).boxed().await;
+        let security = security::get().boxed().await;
         let sleep = sleep::get().boxed().await;
+        let thermal = thermal::get().boxed().await;
+        let virtualization = virtualization::get().boxed().await;
 
-        SystemConfInfo { sleep }
+        SystemConfInfo {
+            acpi,
+            firmware,
+            kernel,
+            limits,
+            memory,
+            powercap,
+            security,
+            sleep,
+            thermal,
+            virtualization,
+        }
     }
 }
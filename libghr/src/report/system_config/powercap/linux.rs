@@ -0,0 +1,334 @@
+use std::{
+    collections::BTreeMap,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::prelude::internal::*;
+use futures::StreamExt as _;
+
+/// the path to all power zones on linux
+const POWERCAP_PATH: &str = "/sys/class/powercap";
+
+/// gets the platform's power zones and their limits.
+#[tracing::instrument]
+pub(super) async fn get(window: Option<Duration>) -> Powercap {
+    powercap(POWERCAP_PATH, window).await
+}
+
+/// reads the power zones in `class_path`, measuring their power over
+/// `window` when it's given.
+///
+/// `/sys/class/powercap` has each control type (like `intel-rapl`) and each
+/// of its zones, named after their parents: `intel-rapl:0` is the first
+/// package, and `intel-rapl:0:0` is its cores.
+#[tracing::instrument]
+async fn powercap(
+    class_path: impl AsRef<Path> + std::fmt::Debug,
+    window: Option<Duration>,
+) -> Powercap {
+    let class_path = class_path.as_ref();
+    let ids = zone_ids(class_path).await;
+
+    // measure first, so reading the zones doesn't skew it
+    let power = match window {
+        Some(window) => sample(class_path, &ids, window).await,
+        None => BTreeMap::new(),
+    };
+
+    let mut zones = BTreeMap::new();
+    for id in &ids {
+        let zone = zone(&class_path.join(id), id, power.get(id).copied()).await;
+        zones.insert(id.clone(), zone);
+    }
+
+    Powercap {
+        zones: (!zones.is_empty()).then(|| nest(zones)),
+    }
+}
+
+/// lists the zone ids in `class_path`, skipping the control types.
+#[tracing::instrument]
+async fn zone_ids(class_path: &Path) -> Vec<String> {
+    let Ok(entries) = async_fs::read_dir(class_path).await else {
+        tracing::debug!("There's no powercap class. The drivers might not be loaded.");
+        return Vec::new();
+    };
+
+    let mut ids = entries
+        .filter_map(|entry| async move {
+            let name = entry.ok()?.file_name().to_string_lossy().to_string();
+            name.contains(':').then_some(name)
+        })
+        .collect::<Vec<_>>()
+        .await;
+
+    ids.sort();
+    ids
+}
+
+/// reads one zone, like `/sys/class/powercap/intel-rapl:0`.
+#[tracing::instrument]
+async fn zone(path: &Path, id: &str, power: Option<f64>) -> PowerZone {
+    let (name, enabled, max_energy_range_uj, limits) = futures::join! {
+        sysfs_value_opt::<String>(path.join("name")),
+        sysfs_value_opt::<u8>(path.join("enabled")),
+        sysfs_value_opt::<u64>(path.join("max_energy_range_uj")),
+        limits(path),
+    };
+
+    PowerZone {
+        id: id.to_string(),
+        control_type: id.split(':').next().map(String::from),
+        domain: name.as_deref().map(domain),
+        name,
+        enabled: enabled.map(|enabled| enabled != 0),
+        limits: (!limits.is_empty()).then_some(limits),
+        max_energy_range: max_energy_range_uj.map(|uj| uj as f64 / 1_000_000.0),
+        power,
+        subzones: None,
+    }
+}
+
+/// reads a zone's constraints, like `constraint_0_power_limit_uw`.
+#[tracing::instrument]
+async fn limits(path: &Path) -> Vec<PowerLimit> {
+    let mut limits = Vec::new();
+
+    for n in 0.. {
+        let constraint = |file: &str| path.join(format!("constraint_{n}_{file}"));
+        let (name, power_limit_uw, time_window_us, max_power_uw) = futures::join! {
+            sysfs_value_opt::<String>(constraint("name")),
+            sysfs_value_opt::<u64>(constraint("power_limit_uw")),
+            sysfs_value_opt::<u64>(constraint("time_window_us")),
+            sysfs_value_opt::<u64>(constraint("max_power_uw")),
+        };
+
+        // constraints are numbered from zero, without gaps
+        if name.is_none() && power_limit_uw.is_none() {
+            break;
+        }
+
+        let kind = match name.as_deref() {
+            Some("long_term") => Some(PowerLimitKind::LongTerm),
+            Some("short_term") => Some(PowerLimitKind::ShortTerm),
+            Some("peak_power") => Some(PowerLimitKind::Peak),
+            _ => None,
+        };
+
+        limits.push(PowerLimit {
+            name,
+            kind,
+            power_limit: power_limit_uw.map(|uw| uw as f64 / 1_000_000.0),
+            time_window: time_window_us.map(|us| us as f64 / 1_000_000.0),
+            // zero means the driver doesn't know
+            max_power: max_power_uw
+                .filter(|uw| *uw > 0)
+                .map(|uw| uw as f64 / 1_000_000.0),
+        });
+    }
+
+    limits
+}
+
+/// finds what a zone covers from its name.
+#[tracing::instrument]
+fn domain(name: &str) -> PowerDomain {
+    match name {
+        package if package.starts_with("package") => PowerDomain::Package,
+        "core" => PowerDomain::Core,
+        "uncore" => PowerDomain::Uncore,
+        "dram" => PowerDomain::Dram,
+        "psys" => PowerDomain::Platform,
+        other => PowerDomain::Other(other.to_string()),
+    }
+}
+
+/// measures each zone's power draw over `window`, in watts.
+///
+/// the energy counters are usually only readable by root, so this is often
+/// empty.
+#[tracing::instrument]
+async fn sample(class_path: &Path, ids: &[String], window: Duration) -> BTreeMap<String, f64> {
+    let before = energies(class_path, ids).await;
+    if before.is_empty() {
+        return BTreeMap::new();
+    }
+    let start = Instant::now();
+
+    // the executor can wake us late, so use how long it really took
+    blocking::unblock(move || std::thread::sleep(window)).await;
+    let after = energies(class_path, ids).await;
+    let elapsed = start.elapsed();
+
+    before
+        .into_iter()
+        .filter_map(|(id, (before, max_range))| {
+            let (after, _) = after.get(&id)?;
+            let watts = power(before, *after, max_range, elapsed)?;
+            Some((id, watts))
+        })
+        .collect()
+}
+
+/// reads each zone's energy counter (and where it wraps around), in
+/// microjoules.
+#[tracing::instrument]
+async fn energies(class_path: &Path, ids: &[String]) -> BTreeMap<String, (u64, Option<u64>)> {
+    let mut energies = BTreeMap::new();
+
+    for id in ids {
+        let path = class_path.join(id);
+        let (energy, max_range) = futures::join! {
+            sysfs_value_opt::<u64>(path.join("energy_uj")),
+            sysfs_value_opt::<u64>(path.join("max_energy_range_uj")),
+        };
+
+        if let Some(energy) = energy {
+            energies.insert(id.clone(), (energy, max_range));
+        }
+    }
+
+    energies
+}
+
+/// finds the average power between two energy readings, in watts.
+///
+/// a counter that went backwards without a known range (or past it) can't
+/// be trusted, so it doesn't get a reading.
+#[tracing::instrument]
+fn power(
+    before_uj: u64,
+    after_uj: u64,
+    max_range_uj: Option<u64>,
+    elapsed: Duration,
+) -> Option<f64> {
+    // the counter wraps around to zero after `max_energy_range_uj`
+    let used_uj = match after_uj.checked_sub(before_uj) {
+        Some(used_uj) => used_uj,
+        None => max_range_uj?
+            .checked_sub(before_uj)?
+            .checked_add(after_uj)?,
+    };
+
+    (!elapsed.is_zero()).then(|| used_uj as f64 / 1_000_000.0 / elapsed.as_secs_f64())
+}
+
+/// nests each zone under its parent, like `intel-rapl:0:0` under
+/// `intel-rapl:0`.
+#[tracing::instrument(skip(zones))]
+fn nest(mut zones: BTreeMap<String, PowerZone>) -> Vec<PowerZone> {
+    // move the deepest zones first, so their own children come along
+    let mut ids = zones.keys().cloned().collect::<Vec<_>>();
+    ids.sort_by_key(|id| std::cmp::Reverse(id.matches(':').count()));
+
+    for id in ids {
+        let Some((parent, _)) = id.rsplit_once(':') else {
+            continue;
+        };
+        if !zones.contains_key(parent) {
+            continue;
+        }
+
+        if let Some(zone) = zones.remove(&id) {
+            if let Some(parent) = zones.get_mut(parent) {
+                let subzones = parent.subzones.get_or_insert_with(Vec::new);
+                subzones.push(zone);
+                subzones.sort_by(|a, b| a.id.cmp(&b.id));
+            }
+        }
+    }
+
+    zones.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn check_powercap() {
+        let zones = powercap(powercap_path(), None).await.zones.unwrap();
+
+        // one package, and the whole platform
+        assert_eq!(zones.len(), 2);
+
+        let package = &zones[0];
+        assert_eq!(package.id, "intel-rapl:0");
+        assert_eq!(package.control_type.as_deref(), Some("intel-rapl"));
+        assert_eq!(package.domain, Some(PowerDomain::Package));
+        assert_eq!(package.enabled, Some(true));
+        assert!(almost::equal(
+            package.max_energy_range.unwrap(),
+            262143.328850
+        ));
+        assert!(package.power.is_none());
+
+        // this laptop's firmware holds its 45 watt processor to 15 watts
+        let limits = package.limits.clone().unwrap();
+        assert_eq!(limits.len(), 3);
+        assert_eq!(limits[0].kind, Some(PowerLimitKind::LongTerm));
+        assert!(almost::equal(limits[0].power_limit.unwrap(), 15.0));
+        assert!(almost::equal(limits[0].time_window.unwrap(), 27.983872));
+        assert!(almost::equal(limits[0].max_power.unwrap(), 45.0));
+        assert_eq!(limits[1].kind, Some(PowerLimitKind::ShortTerm));
+        assert!(almost::equal(limits[1].power_limit.unwrap(), 30.0));
+        assert_eq!(limits[2].kind, Some(PowerLimitKind::Peak));
+        assert!(limits[2].max_power.is_none());
+
+        let subzones = package.subzones.clone().unwrap();
+        let domains = subzones
+            .iter()
+            .map(|z| z.domain.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            domains,
+            [
+                Some(PowerDomain::Core),
+                Some(PowerDomain::Uncore),
+                Some(PowerDomain::Dram)
+            ]
+        );
+
+        assert_eq!(zones[1].id, "intel-rapl:1");
+        assert_eq!(zones[1].domain, Some(PowerDomain::Platform));
+        assert!(zones[1].subzones.is_none());
+    }
+
+    #[tokio::test]
+    async fn check_power_sampling() {
+        let window = Duration::from_millis(1);
+        let zones = powercap(powercap_path(), Some(window)).await.zones.unwrap();
+
+        // the samples don't change, so nothing was used
+        assert_eq!(zones[0].power, Some(0.0));
+
+        // but a package that used 15 joules in a second drew 15 watts, even
+        // when its counter wrapped around
+        let window = Duration::from_secs(1);
+        assert!(almost::equal(
+            power(1_000_000, 16_000_000, None, window).unwrap(),
+            15.0
+        ));
+        assert!(almost::equal(
+            power(262_000_000, 14_856_000, Some(262_143_328), window).unwrap(),
+            15.0 - 0.000_672
+        ));
+
+        // counters that went backwards past their range are nonsense
+        assert_eq!(power(262_000_000, 14_856_000, None, window), None);
+        assert_eq!(
+            power(300_000_000, 14_856_000, Some(262_143_328), window),
+            None
+        );
+    }
+
+    #[tracing::instrument]
+    fn powercap_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!(
+            "{root}/tests/assets/linux/sysfs/sys/class/powercap"
+        ))
+    }
+}
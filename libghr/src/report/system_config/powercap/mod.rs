@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+use crate::prelude::internal::*;
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+/// The longest a report will measure power draw for.
+pub const MAX_POWER_SAMPLING: Duration = Duration::from_secs(10);
+
+/// The power limits the platform places on its processors and memory.
+///
+/// Firmware often limits a processor to less power than it's rated for,
+/// especially in thin laptops. These limits come from RAPL ("Running Average
+/// Power Limit") on Intel and AMD, or DTPM on some ARM systems.
+#[derive(
+    Clone, Debug, Default, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript,
+)]
+#[non_exhaustive]
+pub struct Powercap {
+    /// The top-level power zones, like each processor package.
+    pub zones: Option<Vec<PowerZone>>,
+}
+
+/// Something whose power is measured or limited, like a processor package.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct PowerZone {
+    /// The zone's ID, like `intel-rapl:0`.
+    pub id: String,
+
+    /// The interface that controls the zone, like `intel-rapl` or `dtpm`.
+    pub control_type: Option<String>,
+
+    /// The zone's name, like `package-0` or `dram`.
+    pub name: Option<String>,
+
+    /// What the zone covers.
+    pub domain: Option<PowerDomain>,
+
+    /// Whether the zone's limits are being enforced.
+    pub enabled: Option<bool>,
+
+    /// The limits on the zone's power draw.
+    pub limits: Option<Vec<PowerLimit>>,
+
+    /// The largest value the zone's energy counter reaches before wrapping
+    /// around, in joules.
+    pub max_energy_range: Option<f64>,
+
+    /// How much power the zone drew while the report was created, in watts.
+    ///
+    /// This is only measured when a sampling window is set with
+    /// [`crate::ReportBuilder::power_sampling`], and usually needs
+    /// administrator rights.
+    pub power: Option<f64>,
+
+    /// The parts of this zone that are measured on their own, like the
+    /// processor's cores.
+    pub subzones: Option<Vec<PowerZone>>,
+}
+
+/// What a power zone covers.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum PowerDomain {
+    /// A whole processor package.
+    Package,
+    /// The processor's cores.
+    Core,
+    /// The parts of the processor outside its cores, usually the integrated
+    /// GPU.
+    Uncore,
+    /// The memory.
+    Dram,
+    /// The whole platform (system on a chip, memory, and more).
+    Platform,
+    Other(String),
+}
+
+/// A limit on how much power a zone can draw.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct PowerLimit {
+    /// The limit's name, like `long_term`.
+    pub name: Option<String>,
+
+    /// Which limit this is.
+    pub kind: Option<PowerLimitKind>,
+
+    /// The most power the zone can draw, in watts.
+    pub power_limit: Option<f64>,
+
+    /// How long the zone's average power is measured over, in seconds.
+    pub time_window: Option<f64>,
+
+    /// The highest this limit can be set, in watts.
+    pub max_power: Option<f64>,
+}
+
+/// A kind of RAPL power limit.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    TypeScript,
+)]
+#[non_exhaustive]
+pub enum PowerLimitKind {
+    /// The sustained limit, "PL1".
+    LongTerm,
+    /// The turbo limit, "PL2", which can only be held for a short time.
+    ShortTerm,
+    /// The instantaneous limit, "PL4".
+    Peak,
+}
+
+/// Gets the platform's power zones and their limits, measuring their power
+/// draw over `window` when it's given.
+#[tracing::instrument]
+pub async fn get(window: Option<Duration>) -> Powercap {
+    let window = window.map(|window| window.min(MAX_POWER_SAMPLING));
+
+    #[cfg(target_os = "linux")]
+    return linux::get(window).await;
+
+    // TODO: windows only shares these through vendor drivers
+    #[cfg(target_os = "windows")]
+    return Powercap::default();
+}
@@ -0,0 +1,176 @@
+use std::path::Path;
+
+use crate::prelude::internal::*;
+
+mod tpm;
+
+/// the guid of efi global variables, like `SecureBoot`
+const EFI_GLOBAL_VARIABLE: &str = "8be4df61-93ca-11d2-aa0d-00e098032b8c";
+
+/// gets info about the platform's security features.
+#[tracing::instrument]
+pub(super) async fn get() -> Security {
+    security("/").await
+}
+
+/// gets security info, reading `sysfs` and `procfs` under `root`.
+#[tracing::instrument]
+async fn security(root: impl AsRef<Path> + std::fmt::Debug) -> Security {
+    let root = root.as_ref();
+    let efi_path = root.join("sys/firmware/efi");
+    let (tpm_path, dev_path, lockdown_path, iommu_path) = (
+        root.join("sys/class/tpm"),
+        root.join("dev"),
+        root.join("sys/kernel/security/lockdown"),
+        root.join("sys/kernel/iommu_groups"),
+    );
+
+    let (tpm, uefi, secure_boot, setup_mode, lockdown, iommu, memory_encryption) = futures::join! {
+        tpm::tpm(&tpm_path, &dev_path),
+        async_fs::metadata(&efi_path),
+        efi_bool(&efi_path, "SecureBoot"),
+        efi_bool(&efi_path, "SetupMode"),
+        lockdown(&lockdown_path),
+        iommu(&iommu_path),
+        memory_encryption(root),
+    };
+
+    // without `sysfs`, we can't tell one way or the other
+    let boot_mode = if uefi.is_ok() {
+        Some(BootMode::Uefi)
+    } else if async_fs::metadata(root.join("sys/firmware")).await.is_ok() {
+        Some(BootMode::Legacy)
+    } else {
+        None
+    };
+
+    Security {
+        tpm,
+        boot_mode,
+        secure_boot,
+        setup_mode,
+        lockdown,
+        iommu,
+        memory_encryption,
+    }
+}
+
+/// reads a boolean efi global variable, like `SecureBoot`.
+///
+/// `efivarfs` puts four bytes of attributes before the value.
+#[tracing::instrument]
+async fn efi_bool(efi_path: &Path, name: &str) -> Option<bool> {
+    let path = efi_path.join(format!("efivars/{name}-{EFI_GLOBAL_VARIABLE}"));
+    let bytes = async_fs::read(&path).await.ok()?;
+
+    bytes.get(4).map(|value| *value == 1)
+}
+
+/// reads the kernel's lockdown level, like `none [integrity] confidentiality`.
+#[tracing::instrument]
+async fn lockdown(path: &Path) -> Option<Lockdown> {
+    let levels = sysfs_value_opt::<String>(path).await?;
+    let (_, rest) = levels.split_once('[')?;
+    let (level, _) = rest.split_once(']')?;
+
+    match level {
+        "none" => Some(Lockdown::None),
+        "integrity" => Some(Lockdown::Integrity),
+        "confidentiality" => Some(Lockdown::Confidentiality),
+        other => {
+            tracing::debug!("Unknown lockdown level `{other}`.");
+            None
+        }
+    }
+}
+
+/// checks whether the kernel is using an iommu.
+///
+/// devices are only put in iommu groups when one is on.
+#[tracing::instrument]
+async fn iommu(groups_path: &Path) -> Option<bool> {
+    use futures::StreamExt as _;
+
+    let mut groups = async_fs::read_dir(groups_path).await.ok()?;
+    Some(groups.next().await.is_some())
+}
+
+/// finds the memory encryption features that are turned on.
+#[tracing::instrument]
+async fn memory_encryption(root: &Path) -> Option<Vec<MemoryEncryption>> {
+    let (cpuinfo, sev, sev_es, sev_snp, tdx) = futures::join! {
+        sysfs_value_opt::<String>(root.join("proc/cpuinfo")),
+        module_bool(root, "kvm_amd", "sev"),
+        module_bool(root, "kvm_amd", "sev_es"),
+        module_bool(root, "kvm_amd", "sev_snp"),
+        module_bool(root, "kvm_intel", "tdx"),
+    };
+    let cpuinfo = cpuinfo?;
+    let flags = cpu_flags(&cpuinfo);
+
+    // the kernel hides the `sme` and `tme` flags unless the firmware turned
+    // them on. the sev flags just mean the cpu supports it, so we ask kvm
+    let mut features = Vec::new();
+    for (on, feature) in [
+        (flags.contains(&"sme"), MemoryEncryption::Sme),
+        (sev, MemoryEncryption::Sev),
+        (sev_es, MemoryEncryption::SevEs),
+        (sev_snp, MemoryEncryption::SevSnp),
+        (flags.contains(&"tme"), MemoryEncryption::Tme),
+        (tdx, MemoryEncryption::TdxHost),
+        (flags.contains(&"tdx_guest"), MemoryEncryption::TdxGuest),
+    ] {
+        if on {
+            features.push(feature);
+        }
+    }
+
+    Some(features)
+}
+
+/// reads a boolean kernel module parameter, like `kvm_amd`'s `sev`.
+///
+/// these are `Y` or `N`, though older kernels used `1` and `0`.
+#[tracing::instrument]
+async fn module_bool(root: &Path, module: &str, parameter: &str) -> bool {
+    let path = root.join(format!("sys/module/{module}/parameters/{parameter}"));
+
+    matches!(
+        sysfs_value_opt::<String>(path).await.as_deref(),
+        Some("Y" | "1")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn check_security() {
+        let security = security(root_path()).await;
+
+        // there's no `/dev` in the samples, so we only get the version
+        let tpm = security.tpm.unwrap();
+        assert_eq!(tpm.version.as_deref(), Some("2.0"));
+        assert!(tpm.manufacturer.is_none());
+
+        assert_eq!(security.boot_mode, Some(BootMode::Uefi));
+        assert_eq!(security.secure_boot, Some(true));
+        assert_eq!(security.setup_mode, Some(false));
+        assert_eq!(security.lockdown, Some(Lockdown::Integrity));
+        assert_eq!(security.iommu, Some(true));
+
+        // the cpu supports sev-es, but kvm doesn't have it on
+        assert_eq!(
+            security.memory_encryption,
+            Some(vec![MemoryEncryption::Sme, MemoryEncryption::Sev])
+        );
+    }
+
+    #[tracing::instrument]
+    fn root_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs"))
+    }
+}
@@ -0,0 +1,274 @@
+//! finds out about the tpm.
+//!
+//! `sysfs` only has the tpm's major version (and, on tpm 1.2 chips, a `caps`
+//! file). tpm 2.0 chips keep the rest behind `TPM2_GetCapability`, which we
+//! send through the kernel's resource manager. that's usually only open to
+//! root and the `tss` group, so we might not get far.
+
+use std::{
+    io::{Read as _, Write as _},
+    path::Path,
+};
+
+use crate::prelude::internal::*;
+
+/// `TPM_ST_NO_SESSIONS`
+const TPM_ST_NO_SESSIONS: u16 = 0x8001;
+/// `TPM_CC_GetCapability`
+const TPM_CC_GET_CAPABILITY: u32 = 0x0000_017a;
+/// `TPM_CAP_TPM_PROPERTIES`
+const TPM_CAP_TPM_PROPERTIES: u32 = 0x0000_0006;
+
+// `TPM_PT_*` properties. they're all next to each other, so we ask for them
+// in one go
+const TPM_PT_MANUFACTURER: u32 = 0x0000_0105;
+const TPM_PT_FIRMWARE_VERSION_1: u32 = 0x0000_010b;
+const PROPERTY_COUNT: u32 = TPM_PT_FIRMWARE_VERSION_1 - TPM_PT_MANUFACTURER + 1;
+
+/// gets info about the first tpm in `class_path` (usually `/sys/class/tpm`).
+///
+/// tpm 2.0 chips are asked for more through `dev_path`, which is usually
+/// `/dev`.
+#[tracing::instrument]
+pub(super) async fn tpm(class_path: &Path, dev_path: &Path) -> Option<Tpm> {
+    let path = class_path.join("tpm0");
+    if !async_fs::metadata(&path)
+        .await
+        .is_ok_and(|meta| meta.is_dir())
+    {
+        tracing::debug!("No TPM was found.");
+        return None;
+    }
+
+    let major = sysfs_value_opt::<u8>(path.join("tpm_version_major")).await;
+
+    // tpm 1.2 chips describe themselves in `caps`, which moved over time
+    if major != Some(2) {
+        let caps = match sysfs_value_opt::<String>(path.join("caps")).await {
+            Some(caps) => Some(caps),
+            None => sysfs_value_opt::<String>(path.join("device/caps")).await,
+        };
+
+        if let Some(caps) = caps {
+            return Some(parse_caps(&caps));
+        }
+    }
+
+    // talking to the tpm blocks, so keep it off the executor
+    let tpmrm = dev_path.join("tpmrm0");
+    let properties = blocking::unblock(move || properties(&tpmrm)).await;
+
+    let (manufacturer, firmware_version) = match properties {
+        Some(properties) => (
+            property(&properties, TPM_PT_MANUFACTURER).map(manufacturer_name),
+            property(&properties, TPM_PT_FIRMWARE_VERSION_1).map(firmware_version),
+        ),
+        None => (None, None),
+    };
+
+    Some(Tpm {
+        version: major.map(|major| format!("{major}.0")),
+        manufacturer,
+        firmware_version,
+    })
+}
+
+/// parses a tpm 1.2 `caps` file, like:
+///
+/// ```text
+/// Manufacturer: 0x49465800
+/// TCG version: 1.2
+/// Firmware version: 3.17
+/// ```
+#[tracing::instrument(skip(caps))]
+fn parse_caps(caps: &str) -> Tpm {
+    let mut tpm = Tpm {
+        version: None,
+        manufacturer: None,
+        firmware_version: None,
+    };
+
+    for line in caps.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+
+        match key.trim() {
+            "Manufacturer" => {
+                tpm.manufacturer = u32::from_str_radix(value.trim_start_matches("0x"), 16)
+                    .ok()
+                    .map(manufacturer_name)
+            }
+            "TCG version" => tpm.version = Some(value.to_string()),
+            "Firmware version" => tpm.firmware_version = Some(value.to_string()),
+            _ => (),
+        }
+    }
+
+    tpm
+}
+
+/// sends `TPM2_GetCapability` to the tpm at `path`, returning the properties
+/// it gave back.
+#[tracing::instrument]
+fn properties(path: &Path) -> Option<Vec<(u32, u32)>> {
+    let mut command = Vec::with_capacity(22);
+    command.extend(TPM_ST_NO_SESSIONS.to_be_bytes());
+    command.extend(22_u32.to_be_bytes());
+    command.extend(TPM_CC_GET_CAPABILITY.to_be_bytes());
+    command.extend(TPM_CAP_TPM_PROPERTIES.to_be_bytes());
+    command.extend(TPM_PT_MANUFACTURER.to_be_bytes());
+    command.extend(PROPERTY_COUNT.to_be_bytes());
+
+    let mut file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .inspect_err(|e| tracing::debug!("Couldn't open the TPM. (err: {e})"))
+        .ok()?;
+
+    file.write_all(&command)
+        .inspect_err(|e| tracing::debug!("Couldn't send a TPM command. (err: {e})"))
+        .ok()?;
+
+    let mut response = vec![0_u8; 4096];
+    let len = file
+        .read(&mut response)
+        .inspect_err(|e| tracing::debug!("Couldn't read a TPM response. (err: {e})"))
+        .ok()?;
+
+    parse_properties(&response[..len])
+}
+
+/// parses the response to `TPM2_GetCapability` for tpm properties.
+///
+/// after the header and "more data" flag, there's the capability, a count,
+/// then that many property/value pairs. it's all big-endian.
+#[tracing::instrument(skip(response))]
+fn parse_properties(response: &[u8]) -> Option<Vec<(u32, u32)>> {
+    let read_u32 = |at: usize| {
+        let bytes = response.get(at..at + 4)?;
+        Some(u32::from_be_bytes(bytes.try_into().ok()?))
+    };
+
+    let code = read_u32(6)?;
+    if code != 0 {
+        tracing::debug!("TPM command failed. (code: {code:#x})");
+        return None;
+    }
+
+    if read_u32(11)? != TPM_CAP_TPM_PROPERTIES {
+        return None;
+    }
+
+    let count = read_u32(15)? as usize;
+    (0..count)
+        .map(|n| Some((read_u32(19 + n * 8)?, read_u32(23 + n * 8)?)))
+        .collect()
+}
+
+/// finds the value of a tpm property.
+fn property(properties: &[(u32, u32)], property: u32) -> Option<u32> {
+    properties
+        .iter()
+        .find(|(p, _)| *p == property)
+        .map(|(_, value)| *value)
+}
+
+/// converts the tpm's firmware version property to a string, like `7.85`.
+fn firmware_version(version: u32) -> String {
+    format!("{}.{}", version >> 16, version & 0xffff)
+}
+
+/// converts a tpm manufacturer id (four ascii characters, like `IFX`) to
+/// the manufacturer's name.
+///
+/// these come from the tcg's vendor id registry.
+#[tracing::instrument]
+fn manufacturer_name(id: u32) -> String {
+    let code = String::from_utf8_lossy(&id.to_be_bytes())
+        .trim_matches(|c: char| c == '\0' || c.is_whitespace())
+        .to_string();
+
+    let name = match code.as_str() {
+        "AMD" => "AMD",
+        "ATML" => "Atmel",
+        "BRCM" => "Broadcom",
+        "CSCO" => "Cisco",
+        "GOOG" => "Google",
+        "HISI" => "HiSilicon",
+        "HPE" => "Hewlett Packard Enterprise",
+        "IBM" => "IBM",
+        "IFX" => "Infineon",
+        "INTC" => "Intel",
+        "LEN" => "Lenovo",
+        "MSFT" => "Microsoft",
+        "NSM" => "National Semiconductor",
+        "NTC" => "Nuvoton",
+        "NTZ" => "Nationz",
+        "QCOM" => "Qualcomm",
+        "ROCC" => "Fuzhou Rockchip",
+        "SMSC" => "SMSC",
+        "SNS" => "Sinosun",
+        "STM" => "STMicroelectronics",
+        "TXN" => "Texas Instruments",
+        "WEC" => "Winbond",
+        _ => return code,
+    };
+
+    name.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_tpm2_properties() {
+        // the response from an infineon slb9672
+        let mut response = Vec::new();
+        response.extend(TPM_ST_NO_SESSIONS.to_be_bytes());
+        response.extend((19_u32 + 8 * PROPERTY_COUNT).to_be_bytes());
+        response.extend(0_u32.to_be_bytes()); // success
+        response.push(0); // no more data
+        response.extend(TPM_CAP_TPM_PROPERTIES.to_be_bytes());
+        response.extend(PROPERTY_COUNT.to_be_bytes());
+        for n in 0..PROPERTY_COUNT {
+            let property = TPM_PT_MANUFACTURER + n;
+            let value = match property {
+                TPM_PT_MANUFACTURER => u32::from_be_bytes(*b"IFX\0"),
+                TPM_PT_FIRMWARE_VERSION_1 => (16 << 16) | 13,
+                _ => 0,
+            };
+            response.extend(property.to_be_bytes());
+            response.extend(value.to_be_bytes());
+        }
+
+        let properties = parse_properties(&response).unwrap();
+        assert_eq!(properties.len(), PROPERTY_COUNT as usize);
+        assert_eq!(
+            property(&properties, TPM_PT_MANUFACTURER).map(manufacturer_name),
+            Some("Infineon".into())
+        );
+        assert_eq!(
+            property(&properties, TPM_PT_FIRMWARE_VERSION_1).map(firmware_version),
+            Some("16.13".into())
+        );
+
+        // `TPM_RC_INITIALIZE`, when the firmware didn't start the tpm
+        let mut failed = response[..10].to_vec();
+        failed[6..10].copy_from_slice(&0x100_u32.to_be_bytes());
+        assert!(parse_properties(&failed).is_none());
+    }
+
+    #[test]
+    fn check_tpm12_caps() {
+        let tpm =
+            parse_caps("Manufacturer: 0x53544d20\nTCG version: 1.2\nFirmware version: 13.12\n");
+
+        assert_eq!(tpm.manufacturer.as_deref(), Some("STMicroelectronics"));
+        assert_eq!(tpm.version.as_deref(), Some("1.2"));
+        assert_eq!(tpm.firmware_version.as_deref(), Some("13.12"));
+    }
+}
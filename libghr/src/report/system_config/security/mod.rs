@@ -0,0 +1,113 @@
+use crate::prelude::internal::*;
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+/// Information about the platform's security features.
+#[derive(
+    Clone, Debug, Default, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript,
+)]
+#[non_exhaustive]
+pub struct Security {
+    /// The computer's Trusted Platform Module (TPM), if it has one.
+    pub tpm: Option<Tpm>,
+
+    /// How the firmware booted the operating system.
+    pub boot_mode: Option<BootMode>,
+
+    /// Whether UEFI Secure Boot is turned on.
+    pub secure_boot: Option<bool>,
+
+    /// Whether the firmware is in Setup Mode, where anyone can change the
+    /// Secure Boot keys.
+    pub setup_mode: Option<bool>,
+
+    /// How locked down the kernel is against changes from userspace, even
+    /// by root.
+    pub lockdown: Option<Lockdown>,
+
+    /// Whether an IOMMU is protecting memory from devices.
+    pub iommu: Option<bool>,
+
+    /// The memory encryption features that are turned on.
+    pub memory_encryption: Option<Vec<MemoryEncryption>>,
+}
+
+/// A Trusted Platform Module, which stores keys and measures the boot
+/// process.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct Tpm {
+    /// The version of the TPM spec it follows, like `2.0`.
+    pub version: Option<String>,
+
+    /// The TPM's manufacturer, like `Infineon`.
+    pub manufacturer: Option<String>,
+
+    /// The version of the TPM's firmware.
+    pub firmware_version: Option<String>,
+}
+
+/// How the firmware booted the operating system.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum BootMode {
+    /// Booted with UEFI.
+    Uefi,
+    /// Booted with a legacy BIOS (or UEFI's compatibility mode, "CSM").
+    Legacy,
+}
+
+/// A kernel lockdown level.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum Lockdown {
+    /// Not locked down.
+    None,
+    /// Userspace can't modify the running kernel.
+    Integrity,
+    /// Userspace also can't read secrets out of the kernel.
+    Confidentiality,
+}
+
+/// A memory encryption feature.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    TypeScript,
+)]
+#[non_exhaustive]
+pub enum MemoryEncryption {
+    /// AMD Secure Memory Encryption, turned on by the firmware.
+    Sme,
+    /// The computer can run VMs using AMD Secure Encrypted Virtualization.
+    Sev,
+    /// The computer can run VMs using SEV with Encrypted State.
+    SevEs,
+    /// The computer can run VMs using SEV with Secure Nested Paging.
+    SevSnp,
+    /// Intel Total Memory Encryption, turned on by the firmware.
+    Tme,
+    /// The computer can run VMs using Intel Trust Domain Extensions.
+    TdxHost,
+    /// The computer is itself an Intel TDX VM (a "trust domain").
+    TdxGuest,
+}
+
+/// Gets information about the platform's security features.
+#[tracing::instrument]
+pub async fn get() -> Security {
+    #[cfg(target_os = "linux")]
+    return linux::get().await;
+
+    // TODO: `Win32_Tpm` and `Confirm-SecureBootUEFI` need admin on windows
+    #[cfg(target_os = "windows")]
+    return Security::default();
+}
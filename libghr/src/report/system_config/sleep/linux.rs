@@ -1,13 +1,11 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::prelude::internal::*;
+use crate::{prelude::internal::*, report::system_config::acpi::linux::tables};
 
 /// gets info about the computer's sleep states.
 #[tracing::instrument]
 pub(super) async fn get() -> Sleep {
-    let paths = SleepPaths {
-        state: PathBuf::from("/sys/power/state"),
-    };
+    let paths = SleepPaths::new("/");
 
     linux_sleep_info(&paths).await
 }
@@ -23,6 +21,54 @@ async fn linux_sleep_info(paths: &SleepPaths) -> Sleep {
         parse_state_file(&mut sleep, &states);
     }
 
+    let (mem_sleep, disk, resume, s0ix, wakeup) = futures::join! {
+        sysfs_value_opt::<String>(&paths.mem_sleep),
+        sysfs_value_opt::<String>(&paths.disk),
+        resume_device(paths),
+        s0ix(paths),
+        async_fs::read_to_string(&paths.acpi_wakeup),
+    };
+
+    // `mem_sleep` says what "mem" in the `state` file really means
+    if let Some(mem_sleep) = mem_sleep {
+        let (modes, default) = parse_choices(&mem_sleep, |mode| match mode {
+            "s2idle" => Some(MemSleep::S2Idle),
+            "shallow" => Some(MemSleep::Shallow),
+            "deep" => Some(MemSleep::Deep),
+            other => {
+                tracing::debug!("Unknown `mem_sleep` mode: `{other}`");
+                None
+            }
+        });
+
+        sleep.s1 = modes.contains(&MemSleep::Shallow).into();
+        sleep.s3 = modes.contains(&MemSleep::Deep).into();
+        sleep.mem_sleep = Some(modes);
+        sleep.mem_sleep_default = default;
+    }
+
+    // and `disk` says how hibernation ends
+    if let Some(disk) = disk {
+        let (modes, default) = parse_choices(&disk, |mode| match mode {
+            "platform" => Some(HibernationMode::Platform),
+            "shutdown" => Some(HibernationMode::Shutdown),
+            "reboot" => Some(HibernationMode::Reboot),
+            "suspend" => Some(HibernationMode::Suspend),
+            "test_resume" => Some(HibernationMode::TestResume),
+            other => {
+                tracing::debug!("Unknown hibernation mode: `{other}`");
+                None
+            }
+        });
+
+        sleep.hibernation_modes = Some(modes);
+        sleep.hibernation_mode = default;
+    }
+
+    sleep.resume_device = resume;
+    (sleep.s0ix, sleep.s0ix_residency) = s0ix;
+    sleep.wakeup_sources = wakeup.ok().map(|wakeup| parse_wakeup(&wakeup));
+
     sleep
 }
 
@@ -39,9 +85,9 @@ fn parse_state_file(sleep: &mut Sleep, states: &str) {
 
     // s2: naive standby with cpu powered down.
     //
-    // linux doesn't seem to report this:
+    // linux doesn't implement this one:
     // https://www.kernel.org/doc/Documentation/power/states.txt
-    sleep.s2 = SleepMode::Unknown;
+    sleep.s2 = SleepMode::Unsupported;
 
     // s3: suspend-to-ram
     sleep.s3 = states.contains("deep").into();
@@ -50,11 +96,131 @@ fn parse_state_file(sleep: &mut Sleep, states: &str) {
     sleep.s4 = states.contains("disk").into();
 }
 
+/// finds the device the computer resumes from hibernation with.
+///
+/// `/sys/power/resume` has its major and minor numbers, like `259:2`, or
+/// `0:0` if there isn't one.
+#[tracing::instrument]
+async fn resume_device(paths: &SleepPaths) -> Option<String> {
+    let resume = sysfs_value_opt::<String>(&paths.resume).await?;
+    if resume == "0:0" {
+        return None;
+    }
+
+    // `/sys/dev/block` links each number to its device
+    match async_fs::canonicalize(paths.dev_block.join(&resume)).await {
+        Ok(device) => device
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string()),
+        Err(_) => Some(resume),
+    }
+}
+
+/// checks whether the platform supports s0ix, and how long it's spent
+/// there.
+///
+/// the firmware says so with a flag in the fadt, and by describing its
+/// low-power idle states in the lpit. the kernel only shares the residency
+/// counters when it found the lpit.
+#[tracing::instrument]
+async fn s0ix(paths: &SleepPaths) -> (SleepMode, Option<S0ixResidency>) {
+    let (fadt, lpit, cpu, system) = futures::join! {
+        async_fs::read(&paths.fadt),
+        async_fs::metadata(&paths.lpit),
+        sysfs_value_opt::<u64>(paths.cpuidle.join("low_power_idle_cpu_residency_us")),
+        sysfs_value_opt::<u64>(paths.cpuidle.join("low_power_idle_system_residency_us")),
+    };
+
+    // the fadt is only readable by root. its `LOW_POWER_S0_IDLE_CAPABLE`
+    // flag says the platform supports s0ix
+    let low_power_s0 = fadt
+        .ok()
+        .and_then(|fadt| tables::fadt(&fadt)?.flags)
+        .map(|flags| flags.contains(&FadtFlag::LowPowerS0Idle));
+
+    let residency = (cpu.is_some() || system.is_some()).then_some(S0ixResidency { cpu, system });
+
+    let mode = if low_power_s0 == Some(true) || lpit.is_ok() || residency.is_some() {
+        SleepMode::Supported
+    } else if low_power_s0 == Some(false) {
+        SleepMode::Unsupported
+    } else {
+        SleepMode::Unknown
+    };
+
+    (mode, residency)
+}
+
+/// parses `/proc/acpi/wakeup`, like:
+///
+/// ```text
+/// Device	S-state	  Status   Sysfs node
+/// XHC0	  S3	*enabled   pci:0000:00:14.0
+/// ```
+#[tracing::instrument(skip(wakeup))]
+fn parse_wakeup(wakeup: &str) -> Vec<WakeupSource> {
+    wakeup
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?.to_string();
+            let sleep_state = parts.next().map(String::from);
+            let enabled = parts.next()?.trim_start_matches('*') == "enabled";
+
+            // some devices have a few nodes, while others have none
+            let devices = parts.map(String::from).collect::<Vec<_>>();
+
+            Some(WakeupSource {
+                name,
+                sleep_state,
+                enabled,
+                devices: (!devices.is_empty()).then_some(devices),
+            })
+        })
+        .collect()
+}
+
 #[derive(Debug)]
 #[non_exhaustive]
 struct SleepPaths {
     /// the path to `/sys/power/state`.
     state: PathBuf,
+    /// the path to `/sys/power/mem_sleep`.
+    mem_sleep: PathBuf,
+    /// the path to `/sys/power/disk`.
+    disk: PathBuf,
+    /// the path to `/sys/power/resume`.
+    resume: PathBuf,
+    /// the path to `/sys/dev/block`.
+    dev_block: PathBuf,
+    /// the path to the acpi fadt, `/sys/firmware/acpi/tables/FACP`.
+    fadt: PathBuf,
+    /// the path to the acpi lpit, `/sys/firmware/acpi/tables/LPIT`.
+    lpit: PathBuf,
+    /// the path to `/sys/devices/system/cpu/cpuidle`.
+    cpuidle: PathBuf,
+    /// the path to `/proc/acpi/wakeup`.
+    acpi_wakeup: PathBuf,
+}
+
+impl SleepPaths {
+    /// finds each path under `root`.
+    fn new(root: impl AsRef<Path>) -> Self {
+        let root = root.as_ref();
+
+        Self {
+            state: root.join("sys/power/state"),
+            mem_sleep: root.join("sys/power/mem_sleep"),
+            disk: root.join("sys/power/disk"),
+            resume: root.join("sys/power/resume"),
+            dev_block: root.join("sys/dev/block"),
+            fadt: root.join("sys/firmware/acpi/tables/FACP"),
+            lpit: root.join("sys/firmware/acpi/tables/LPIT"),
+            cpuidle: root.join("sys/devices/system/cpu/cpuidle"),
+            acpi_wakeup: root.join("proc/acpi/wakeup"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -64,7 +230,8 @@ mod tests {
     #[tokio::test]
     async fn check_states() {
         let paths = SleepPaths {
-            state: sysfs_power_path().join("state"),
+            state: sysfs_root_path().join("sys/power/state"),
+            ..SleepPaths::new("/nonexistent")
         };
 
         // grab our possible sleep states
@@ -73,12 +240,13 @@ mod tests {
         // https://www.kernel.org/doc/Documentation/power/states.txt
         //
         // - freeze: s0/software suspend
-        // - mem: currently unused.
+        // - mem: depends on `mem_sleep`, which we don't have here
         // - disk: s4/hibernation
 
         let expected = Sleep {
             s0: SleepMode::Supported,
             s1: SleepMode::Unsupported,
+            s2: SleepMode::Unsupported,
             s3: SleepMode::Unsupported,
             s4: SleepMode::Supported,
             ..Default::default()
@@ -87,9 +255,49 @@ mod tests {
         assert_eq!(expected, sleep);
     }
 
+    #[tokio::test]
+    async fn check_power_management() {
+        let sleep = linux_sleep_info(&SleepPaths::new(sysfs_root_path())).await;
+
+        // `mem` is s3 on this machine
+        assert_eq!(sleep.s1, SleepMode::Unsupported);
+        assert_eq!(sleep.s3, SleepMode::Supported);
+        assert_eq!(
+            sleep.mem_sleep,
+            Some(vec![MemSleep::S2Idle, MemSleep::Deep])
+        );
+        assert_eq!(sleep.mem_sleep_default, Some(MemSleep::Deep));
+
+        assert_eq!(sleep.hibernation_modes.map(|modes| modes.len()), Some(5));
+        assert_eq!(sleep.hibernation_mode, Some(HibernationMode::Platform));
+        assert_eq!(sleep.resume_device.as_deref(), Some("nvme0n1p2"));
+
+        // but it can do s0ix too
+        assert_eq!(sleep.s0ix, SleepMode::Supported);
+        assert_eq!(sleep.s0ix_residency.unwrap().system, Some(97_031_877));
+
+        let wakeup = sleep.wakeup_sources.unwrap();
+        assert_eq!(wakeup.len(), 4);
+        assert!(!wakeup[0].enabled);
+        assert_eq!(wakeup[1].name, "XHC0");
+        assert_eq!(wakeup[1].sleep_state.as_deref(), Some("S3"));
+        assert_eq!(wakeup[1].devices, Some(vec!["pci:0000:00:14.0".into()]));
+    }
+
+    #[tokio::test]
+    async fn check_s0ix_from_fadt() {
+        // without the lpit or residency counters, only the fadt's flag says so
+        let paths = SleepPaths {
+            fadt: sysfs_root_path().join("sys/firmware/acpi/tables/FACP"),
+            ..SleepPaths::new("/nonexistent")
+        };
+
+        assert_eq!(s0ix(&paths).await, (SleepMode::Supported, None));
+    }
+
     #[tracing::instrument]
-    fn sysfs_power_path() -> PathBuf {
+    fn sysfs_root_path() -> PathBuf {
         let root = env!("CARGO_MANIFEST_DIR");
-        PathBuf::from(format!("{root}/tests/assets/linux/sysfs/sys/power"))
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs"))
     }
 }
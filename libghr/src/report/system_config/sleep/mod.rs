@@ -31,6 +31,25 @@ pub struct Sleep {
     /// "Suspend-to-disk", or "hibernation". Everything is turned off and the
     /// system may fully power down.
     s4: SleepMode,
+
+    /// The kinds of suspend the kernel can use for "mem" sleep.
+    mem_sleep: Option<Vec<MemSleep>>,
+    /// The kind of suspend used by default.
+    mem_sleep_default: Option<MemSleep>,
+
+    /// How the computer can power off after hibernating.
+    hibernation_modes: Option<Vec<HibernationMode>>,
+    /// How the computer powers off after hibernating by default.
+    hibernation_mode: Option<HibernationMode>,
+    /// The device the computer resumes from hibernation with, like
+    /// `nvme0n1p2`.
+    resume_device: Option<String>,
+
+    /// How long the computer has spent in S0ix since boot.
+    s0ix_residency: Option<S0ixResidency>,
+
+    /// The devices that can wake the computer from sleep.
+    wakeup_sources: Option<Vec<WakeupSource>>,
 }
 
 impl Default for Sleep {
@@ -43,6 +62,13 @@ impl Default for Sleep {
             s0ix: SleepMode::Unknown,
             s3: SleepMode::Unknown,
             s4: SleepMode::Unknown,
+            mem_sleep: None,
+            mem_sleep_default: None,
+            hibernation_modes: None,
+            hibernation_mode: None,
+            resume_device: None,
+            s0ix_residency: None,
+            wakeup_sources: None,
         }
     }
 }
@@ -60,6 +86,72 @@ pub enum SleepMode {
     Unknown,
 }
 
+/// A kind of suspend the kernel can use for "mem" sleep.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    TypeScript,
+)]
+#[non_exhaustive]
+pub enum MemSleep {
+    /// Suspend-to-idle, which is S0ix on hardware that supports it.
+    S2Idle,
+    /// Standby, or S1.
+    Shallow,
+    /// Suspend-to-RAM, or S3.
+    Deep,
+}
+
+/// How the computer powers off after hibernating.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum HibernationMode {
+    /// The firmware powers the computer off, so it can wake it (S4).
+    Platform,
+    /// The computer shuts down.
+    Shutdown,
+    /// The computer reboots.
+    Reboot,
+    /// The computer suspends, so it can resume quickly unless power is lost.
+    Suspend,
+    /// The computer resumes right away, for testing.
+    TestResume,
+}
+
+/// How long the computer has spent in S0ix.
+///
+/// If these don't go up after suspending, the computer isn't reaching its
+/// lowest power state.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct S0ixResidency {
+    /// Time the CPU package spent in its deepest state, in microseconds.
+    pub cpu: Option<u64>,
+    /// Time the whole platform spent in S0ix, in microseconds.
+    pub system: Option<u64>,
+}
+
+/// A device that can wake the computer, from the ACPI firmware.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct WakeupSource {
+    /// The firmware's name for the device, like `XHC0` or `LID`.
+    pub name: String,
+    /// The deepest sleep state the device can wake from, like `S4`.
+    pub sleep_state: Option<String>,
+    /// Whether the device is allowed to wake the computer.
+    pub enabled: bool,
+    /// The devices in `sysfs` this refers to, like `pci:0000:00:14.0`.
+    pub devices: Option<Vec<String>>,
+}
+
 impl From<bool> for SleepMode {
     fn from(value: bool) -> Self {
         if value {
@@ -0,0 +1,121 @@
+use std::path::Path;
+
+use crate::{prelude::internal::*, report::components::sensors::linux as sensors};
+
+/// gets info about the computer's thermal zones and cooling devices.
+#[tracing::instrument]
+pub(super) async fn get() -> Thermal {
+    thermal(sensors::THERMAL_PATH, sensors::HWMON_PATH).await
+}
+
+/// gets thermal info using the given class directories.
+#[tracing::instrument]
+async fn thermal(
+    thermal_path: impl AsRef<Path> + std::fmt::Debug,
+    hwmon_path: impl AsRef<Path> + std::fmt::Debug,
+) -> Thermal {
+    let thermal_path = thermal_path.as_ref();
+
+    let mut zones = Vec::new();
+    for path in numbered_entries(thermal_path, "thermal_zone").await {
+        zones.push(zone(&path).await);
+    }
+
+    let mut cooling_devices = Vec::new();
+    for path in numbered_entries(thermal_path, "cooling_device").await {
+        cooling_devices.push(cooling_device(&path).await);
+    }
+
+    Thermal {
+        zones: (!zones.is_empty()).then_some(zones),
+        cooling_devices: (!cooling_devices.is_empty()).then_some(cooling_devices),
+        sensors: sensors::board_sensors(hwmon_path).await,
+    }
+}
+
+/// reads one thermal zone, like `/sys/class/thermal/thermal_zone0`.
+#[tracing::instrument]
+async fn zone(path: &Path) -> ThermalZone {
+    let (kind, temperature, policy, trip_points) = futures::join! {
+        sysfs_value_opt::<String>(path.join("type")),
+        sysfs_value_opt::<f64>(path.join("temp")),
+        sysfs_value_opt::<String>(path.join("policy")),
+        sensors::trip_points(path),
+    };
+
+    ThermalZone {
+        name: path.file_name().map(|n| n.to_string_lossy().to_string()),
+        kind,
+        temperature: temperature.map(|t| t / 1_000.0),
+        policy,
+        trip_points: (!trip_points.is_empty()).then_some(trip_points),
+    }
+}
+
+/// reads one cooling device, like `/sys/class/thermal/cooling_device0`.
+#[tracing::instrument]
+async fn cooling_device(path: &Path) -> CoolingDevice {
+    let (kind, current_state, max_state) = futures::join! {
+        sysfs_value_opt::<String>(path.join("type")),
+        sysfs_value_opt::<u32>(path.join("cur_state")),
+        sysfs_value_opt::<u32>(path.join("max_state")),
+    };
+
+    CoolingDevice {
+        name: path.file_name().map(|n| n.to_string_lossy().to_string()),
+        kind,
+        current_state,
+        max_state,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn check_thermal() {
+        let thermal = thermal(thermal_path(), hwmon_path()).await;
+
+        let zones = thermal.zones.unwrap();
+        assert_eq!(zones.len(), 2);
+        assert_eq!(zones[0].kind.as_deref(), Some("acpitz"));
+        assert_eq!(zones[0].temperature, Some(45.0));
+        assert_eq!(zones[0].policy.as_deref(), Some("step_wise"));
+
+        let trips = zones[0].trip_points.clone().unwrap();
+        assert_eq!(trips[0].kind.as_deref(), Some("critical"));
+        assert_eq!(trips[0].temperature, Some(128.0));
+
+        // disabled trip points don't have a temperature
+        assert!(zones[1]
+            .trip_points
+            .clone()
+            .unwrap()
+            .iter()
+            .all(|trip| trip.temperature.is_none()));
+
+        let cooling = thermal.cooling_devices.unwrap();
+        assert_eq!(cooling.len(), 2);
+        assert_eq!(cooling[0].kind.as_deref(), Some("Processor"));
+        assert_eq!(cooling[0].max_state, Some(3));
+
+        // powerclamp says `-1` when it's idle
+        assert_eq!(cooling[1].current_state, None);
+
+        assert_eq!(thermal.sensors.unwrap().len(), 2);
+    }
+
+    #[tracing::instrument]
+    fn thermal_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs/sys/class/thermal"))
+    }
+
+    #[tracing::instrument]
+    fn hwmon_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs/sys/class/hwmon"))
+    }
+}
@@ -0,0 +1,81 @@
+use crate::prelude::internal::*;
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+/// Information about the computer's thermal management.
+#[derive(
+    Clone, Debug, Default, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript,
+)]
+#[non_exhaustive]
+pub struct Thermal {
+    /// The thermal zones the firmware (or drivers) define.
+    pub zones: Option<Vec<ThermalZone>>,
+
+    /// Things the system can slow down (or speed up) to cool itself, like
+    /// fans or the processor.
+    pub cooling_devices: Option<Vec<CoolingDevice>>,
+
+    /// Sensors that don't belong to any one component, like motherboard fans.
+    pub sensors: Option<Vec<Sensor>>,
+}
+
+/// An area of the computer whose temperature is managed.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct ThermalZone {
+    /// The zone's name, like `thermal_zone0`.
+    pub name: Option<String>,
+
+    /// What the zone is, like `acpitz` or `x86_pkg_temp`.
+    pub kind: Option<String>,
+
+    /// The zone's temperature in degrees Celsius.
+    pub temperature: Option<f64>,
+
+    /// The governor managing the zone, like `step_wise`.
+    pub policy: Option<String>,
+
+    /// The temperatures where the system takes action.
+    pub trip_points: Option<Vec<TripPoint>>,
+}
+
+/// A temperature where the system starts cooling a thermal zone.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct TripPoint {
+    /// What happens at this point, like `passive` (throttling) or `critical`
+    /// (shutting down).
+    pub kind: Option<String>,
+
+    /// The trip temperature in degrees Celsius.
+    pub temperature: Option<f64>,
+}
+
+/// Something that can be adjusted to cool the system.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct CoolingDevice {
+    /// The device's name, like `cooling_device0`.
+    pub name: Option<String>,
+
+    /// What the device is, like `Processor` or `Fan`.
+    pub kind: Option<String>,
+
+    /// How hard the device is cooling, from zero to `max_state`.
+    pub current_state: Option<u32>,
+
+    /// The device's highest cooling state.
+    pub max_state: Option<u32>,
+}
+
+/// Gets information about thermal zones, cooling devices, and other sensors.
+#[tracing::instrument]
+pub async fn get() -> Thermal {
+    #[cfg(target_os = "linux")]
+    return linux::get().await;
+
+    // TODO: `MSAcpi_ThermalZoneTemperature` needs admin on windows
+    #[cfg(target_os = "windows")]
+    return Thermal::default();
+}
@@ -0,0 +1,399 @@
+use std::path::Path;
+
+use crate::prelude::internal::*;
+
+/// the asset tag azure gives all of its virtual machines
+const AZURE_ASSET_TAG: &str = "7783-7084-3265-9085-8269-3286-77";
+
+/// finds out whether the computer is virtual.
+#[tracing::instrument]
+pub(super) async fn get() -> Virtualization {
+    virtualization("/", cpuid_hypervisor()).await
+}
+
+/// checks for virtualization, reading files under `root`.
+///
+/// `cpuid` is the hypervisor the cpu says it's running under, if any.
+#[tracing::instrument]
+async fn virtualization(
+    root: impl AsRef<Path> + std::fmt::Debug,
+    cpuid: Option<Hypervisor>,
+) -> Virtualization {
+    let root = root.as_ref();
+    let dmi_path = root.join("sys/class/dmi/id");
+
+    let (cpuinfo, dmi, xen, container) = futures::join! {
+        sysfs_value_opt::<String>(root.join("proc/cpuinfo")),
+        Dmi::new(&dmi_path),
+        sysfs_value_opt::<String>(root.join("sys/hypervisor/type")),
+        container(root),
+    };
+
+    // the cpu knows best, but it only tells us on x86. otherwise, we'll
+    // trust the firmware's strings
+    let hypervisor = cpuid
+        .or_else(|| dmi.hypervisor())
+        .or_else(|| (xen.as_deref() == Some("xen")).then_some(Hypervisor::Xen));
+
+    // linux sets the `hypervisor` flag whenever it's running in a vm
+    let flagged = cpuinfo
+        .as_deref()
+        .map(|cpuinfo| cpu_flags(cpuinfo).contains(&"hypervisor"));
+    let bare_metal = match (flagged, &hypervisor) {
+        (_, Some(_)) | (Some(true), _) => Some(false),
+        (Some(false), None) => Some(true),
+        (None, None) if !dmi.is_empty() => Some(true),
+        (None, None) => None,
+    };
+
+    Virtualization {
+        bare_metal,
+        hypervisor,
+        cloud: dmi.cloud(),
+        container,
+    }
+}
+
+/// asks the cpu which hypervisor it's running under.
+///
+/// hypervisors set bit 31 of `ecx` in leaf 1, then put a 12-byte signature
+/// in leaf `0x4000_0000`.
+#[tracing::instrument]
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn cpuid_hypervisor() -> Option<Hypervisor> {
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::__cpuid;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::__cpuid;
+
+    // newer versions of rust made `__cpuid` safe
+    #[allow(unused_unsafe)]
+    // SAFETY: every cpu that runs linux on x86 has `cpuid`, and these leaves
+    // are only read when the cpu says they exist
+    let signature = unsafe {
+        if __cpuid(1).ecx & (1 << 31) == 0 {
+            return None;
+        }
+
+        let leaf = __cpuid(0x4000_0000);
+        let mut signature = [0_u8; 12];
+        signature[0..4].copy_from_slice(&leaf.ebx.to_le_bytes());
+        signature[4..8].copy_from_slice(&leaf.ecx.to_le_bytes());
+        signature[8..12].copy_from_slice(&leaf.edx.to_le_bytes());
+        signature
+    };
+
+    Some(hypervisor_from_signature(&signature))
+}
+
+/// other architectures don't have `cpuid`.
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+fn cpuid_hypervisor() -> Option<Hypervisor> {
+    None
+}
+
+/// converts a `cpuid` hypervisor signature, like `KVMKVMKVM\0\0\0`, to the
+/// hypervisor.
+#[tracing::instrument]
+fn hypervisor_from_signature(signature: &[u8; 12]) -> Hypervisor {
+    match signature {
+        b"KVMKVMKVM\0\0\0" => Hypervisor::Kvm,
+        b"TCGTCGTCGTCG" => Hypervisor::Qemu,
+        b"Microsoft Hv" => Hypervisor::HyperV,
+        b"VMwareVMware" => Hypervisor::VMware,
+        b"XenVMMXenVMM" => Hypervisor::Xen,
+        b"VBoxVBoxVBox" => Hypervisor::VirtualBox,
+        b" lrpepyh  vr" | b"prl hyperv  " => Hypervisor::Parallels,
+        b"bhyve bhyve " => Hypervisor::Bhyve,
+        b"ACRNACRNACRN" => Hypervisor::Acrn,
+        other => Hypervisor::Other(
+            String::from_utf8_lossy(other)
+                .trim_matches(|c: char| c == '\0' || c.is_whitespace())
+                .to_string(),
+        ),
+    }
+}
+
+/// the smbios strings that say who made the computer.
+#[derive(Debug)]
+struct Dmi {
+    sys_vendor: Option<String>,
+    product_name: Option<String>,
+    board_vendor: Option<String>,
+    bios_vendor: Option<String>,
+    bios_version: Option<String>,
+    chassis_asset_tag: Option<String>,
+}
+
+impl Dmi {
+    /// reads the strings from the `dmi` directory at `path`.
+    #[tracing::instrument]
+    async fn new(path: &Path) -> Self {
+        let value = |name: &str| sysfs_value_opt::<String>(path.join(name));
+
+        let (sys_vendor, product_name, board_vendor) = futures::join! {
+            value("sys_vendor"),
+            value("product_name"),
+            value("board_vendor"),
+        };
+        let (bios_vendor, bios_version, chassis_asset_tag) = futures::join! {
+            value("bios_vendor"),
+            value("bios_version"),
+            value("chassis_asset_tag"),
+        };
+
+        Self {
+            sys_vendor,
+            product_name,
+            board_vendor,
+            bios_vendor,
+            bios_version,
+            chassis_asset_tag,
+        }
+    }
+
+    /// whether the firmware didn't tell us anything.
+    fn is_empty(&self) -> bool {
+        self.sys_vendor.is_none() && self.product_name.is_none() && self.bios_vendor.is_none()
+    }
+
+    /// guesses the hypervisor from the virtual firmware's strings.
+    #[tracing::instrument]
+    fn hypervisor(&self) -> Option<Hypervisor> {
+        let sys_vendor = self.sys_vendor.as_deref().unwrap_or_default();
+        let product_name = self.product_name.as_deref().unwrap_or_default();
+        let vendors = [
+            sys_vendor,
+            self.board_vendor.as_deref().unwrap_or_default(),
+            self.bios_vendor.as_deref().unwrap_or_default(),
+        ];
+        let any_vendor = |name: &str| vendors.iter().any(|vendor| vendor.starts_with(name));
+
+        if product_name == "KVM" || any_vendor("Amazon EC2") || any_vendor("Google") {
+            Some(Hypervisor::Kvm)
+        } else if any_vendor("QEMU") {
+            Some(Hypervisor::Qemu)
+        } else if sys_vendor == "Microsoft Corporation" && product_name == "Virtual Machine" {
+            Some(Hypervisor::HyperV)
+        } else if any_vendor("VMware") {
+            Some(Hypervisor::VMware)
+        } else if any_vendor("Xen") {
+            Some(Hypervisor::Xen)
+        } else if any_vendor("innotek GmbH") || product_name == "VirtualBox" {
+            Some(Hypervisor::VirtualBox)
+        } else if any_vendor("Parallels") {
+            Some(Hypervisor::Parallels)
+        } else if any_vendor("BHYVE") {
+            Some(Hypervisor::Bhyve)
+        } else if sys_vendor == "Apple Inc." && product_name.contains("Virtualization") {
+            Some(Hypervisor::Apple)
+        } else {
+            None
+        }
+    }
+
+    /// figures out which cloud the computer is in.
+    #[tracing::instrument]
+    fn cloud(&self) -> Option<Cloud> {
+        let sys_vendor = self.sys_vendor.as_deref().unwrap_or_default();
+        let asset_tag = self.chassis_asset_tag.as_deref().unwrap_or_default();
+
+        // older instances ran on xen, and only mention amazon in the bios
+        let older_aws = self
+            .bios_version
+            .as_deref()
+            .is_some_and(|version| version.contains("amazon"));
+
+        let provider = if sys_vendor == "Amazon EC2" || older_aws {
+            // newer instances put their type in the product name
+            return Some(Cloud {
+                provider: CloudProvider::Aws,
+                instance_type: self
+                    .product_name
+                    .clone()
+                    .filter(|name| sys_vendor == "Amazon EC2" && name.contains('.')),
+            });
+        } else if asset_tag == AZURE_ASSET_TAG {
+            CloudProvider::Azure
+        } else if sys_vendor == "Google"
+            || self.product_name.as_deref() == Some("Google Compute Engine")
+        {
+            CloudProvider::GoogleCloud
+        } else if asset_tag == "OracleCloud.com" {
+            CloudProvider::Oracle
+        } else if sys_vendor == "DigitalOcean" {
+            CloudProvider::DigitalOcean
+        } else if sys_vendor == "Hetzner" {
+            CloudProvider::Hetzner
+        } else if sys_vendor == "Alibaba Cloud" {
+            CloudProvider::Alibaba
+        } else {
+            return None;
+        };
+
+        Some(Cloud {
+            provider,
+            instance_type: None,
+        })
+    }
+}
+
+/// checks whether we're in a container.
+#[tracing::instrument]
+async fn container(root: &Path) -> Option<Container> {
+    let (systemd, dockerenv, containerenv, cgroup, osrelease) = futures::join! {
+        sysfs_value_opt::<String>(root.join("run/systemd/container")),
+        async_fs::metadata(root.join(".dockerenv")),
+        async_fs::metadata(root.join("run/.containerenv")),
+        async_fs::read_to_string(root.join("proc/1/cgroup")),
+        sysfs_value_opt::<String>(root.join("proc/sys/kernel/osrelease")),
+    };
+
+    // systemd writes down what it found when it started
+    if let Some(systemd) = systemd {
+        return Some(container_from_name(&systemd));
+    }
+
+    // container engines leave their own files behind
+    if dockerenv.is_ok() {
+        return Some(Container::Docker);
+    }
+    if containerenv.is_ok() {
+        return Some(Container::Podman);
+    }
+
+    // wsl's kernel says who made it
+    if osrelease.is_some_and(|release| release.to_lowercase().contains("microsoft")) {
+        return Some(Container::Wsl);
+    }
+
+    // otherwise, the init process' cgroup might give it away
+    cgroup.ok().as_deref().and_then(container_from_cgroup)
+}
+
+/// converts the name systemd gives a container, like `docker`.
+#[tracing::instrument]
+fn container_from_name(name: &str) -> Container {
+    match name {
+        "docker" => Container::Docker,
+        "podman" => Container::Podman,
+        "lxc" | "lxc-libvirt" => Container::Lxc,
+        "systemd-nspawn" => Container::SystemdNspawn,
+        "wsl" => Container::Wsl,
+        other => Container::Other(other.to_string()),
+    }
+}
+
+/// guesses the container from the cgroups in `/proc/1/cgroup`, like
+/// `0::/docker/3f4e...`.
+#[tracing::instrument(skip(cgroup))]
+fn container_from_cgroup(cgroup: &str) -> Option<Container> {
+    cgroup.lines().find_map(|line| {
+        let (_, path) = line.rsplit_once(':')?;
+
+        if path.contains("/docker") {
+            Some(Container::Docker)
+        } else if path.contains("libpod") {
+            Some(Container::Podman)
+        } else if path.contains("/lxc") {
+            Some(Container::Lxc)
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn check_virtualization() {
+        let virt = virtualization(root_path(), None).await;
+
+        assert_eq!(virt.bare_metal, Some(true));
+        assert!(virt.hypervisor.is_none());
+        assert!(virt.cloud.is_none());
+        assert!(virt.container.is_none());
+        assert!(!virt.is_virtual());
+
+        // but the cpu knows better
+        let virt = virtualization(root_path(), Some(Hypervisor::Kvm)).await;
+        assert_eq!(virt.bare_metal, Some(false));
+        assert!(virt.is_virtual());
+    }
+
+    #[test]
+    fn check_signatures() {
+        assert_eq!(
+            hypervisor_from_signature(b"KVMKVMKVM\0\0\0"),
+            Hypervisor::Kvm
+        );
+        assert_eq!(
+            hypervisor_from_signature(b"Microsoft Hv"),
+            Hypervisor::HyperV
+        );
+        assert_eq!(
+            hypervisor_from_signature(b"Jailhouse\0\0\0"),
+            Hypervisor::Other("Jailhouse".into())
+        );
+    }
+
+    #[test]
+    fn check_cloud() {
+        let dmi = Dmi {
+            sys_vendor: Some("Amazon EC2".into()),
+            product_name: Some("m6i.large".into()),
+            board_vendor: Some("Amazon EC2".into()),
+            bios_vendor: Some("Amazon EC2".into()),
+            bios_version: Some("1.0".into()),
+            chassis_asset_tag: Some("Amazon EC2".into()),
+        };
+        assert_eq!(dmi.hypervisor(), Some(Hypervisor::Kvm));
+        assert_eq!(
+            dmi.cloud(),
+            Some(Cloud {
+                provider: CloudProvider::Aws,
+                instance_type: Some("m6i.large".into()),
+            })
+        );
+
+        let dmi = Dmi {
+            sys_vendor: Some("Microsoft Corporation".into()),
+            product_name: Some("Virtual Machine".into()),
+            board_vendor: Some("Microsoft Corporation".into()),
+            bios_vendor: Some("Microsoft Corporation".into()),
+            bios_version: Some("Hyper-V UEFI Release v4.1".into()),
+            chassis_asset_tag: Some(AZURE_ASSET_TAG.into()),
+        };
+        assert_eq!(dmi.hypervisor(), Some(Hypervisor::HyperV));
+        assert_eq!(
+            dmi.cloud().map(|cloud| cloud.provider),
+            Some(CloudProvider::Azure)
+        );
+    }
+
+    #[test]
+    fn check_containers() {
+        assert_eq!(
+            container_from_cgroup("0::/docker/3f4e8a2b1c\n"),
+            Some(Container::Docker)
+        );
+        assert_eq!(
+            container_from_cgroup("0::/lxc.payload.web/init.scope\n"),
+            Some(Container::Lxc)
+        );
+        assert_eq!(container_from_cgroup("0::/init.scope\n"), None);
+        assert_eq!(
+            container_from_name("systemd-nspawn"),
+            Container::SystemdNspawn
+        );
+    }
+
+    #[tracing::instrument]
+    fn root_path() -> PathBuf {
+        let root = env!("CARGO_MANIFEST_DIR");
+        PathBuf::from(format!("{root}/tests/assets/linux/sysfs"))
+    }
+}
@@ -0,0 +1,103 @@
+use crate::prelude::internal::*;
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+/// Whether the report came from real hardware, a virtual machine, or a
+/// container.
+///
+/// Virtual hardware doesn't tell us much about real computers, so it's
+/// useful to filter these reports out.
+#[derive(
+    Clone, Debug, Default, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript,
+)]
+#[non_exhaustive]
+pub struct Virtualization {
+    /// Whether the computer is running directly on its hardware.
+    pub bare_metal: Option<bool>,
+
+    /// The hypervisor running the computer, when it's a virtual machine.
+    pub hypervisor: Option<Hypervisor>,
+
+    /// The cloud the virtual machine runs in, if any.
+    pub cloud: Option<Cloud>,
+
+    /// The container the report was made in, if any.
+    pub container: Option<Container>,
+}
+
+impl Virtualization {
+    /// Whether the report came from a virtual machine or container.
+    pub fn is_virtual(&self) -> bool {
+        self.bare_metal == Some(false) || self.hypervisor.is_some() || self.container.is_some()
+    }
+}
+
+/// A hypervisor, which runs virtual machines.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum Hypervisor {
+    Kvm,
+    /// QEMU without hardware acceleration.
+    Qemu,
+    HyperV,
+    VMware,
+    Xen,
+    VirtualBox,
+    Parallels,
+    Bhyve,
+    Acrn,
+    /// Apple's Virtualization framework.
+    Apple,
+    /// A hypervisor we don't know by name.
+    Other(String),
+}
+
+/// A cloud computing provider.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub struct Cloud {
+    /// Who runs the cloud.
+    pub provider: CloudProvider,
+
+    /// The kind of instance, like `m6i.large`, when the provider says.
+    pub instance_type: Option<String>,
+}
+
+/// A cloud computing provider.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum CloudProvider {
+    Aws,
+    Azure,
+    GoogleCloud,
+    Oracle,
+    DigitalOcean,
+    Hetzner,
+    Alibaba,
+}
+
+/// A kind of container.
+#[derive(Clone, Debug, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize, TypeScript)]
+#[non_exhaustive]
+pub enum Container {
+    Docker,
+    Podman,
+    Lxc,
+    SystemdNspawn,
+    /// The Windows Subsystem for Linux.
+    Wsl,
+    /// A container we don't know by name.
+    Other(String),
+}
+
+/// Finds out whether the computer is virtual.
+#[tracing::instrument]
+pub async fn get() -> Virtualization {
+    #[cfg(target_os = "linux")]
+    return linux::get().await;
+
+    // TODO: check `Win32_ComputerSystem`'s `HypervisorPresent` on windows
+    #[cfg(target_os = "windows")]
+    return Virtualization::default();
+}
@@ -1,10 +1,10 @@
 use std::{
     fmt::{Debug, Display},
-    path::Path,
+    path::{Path, PathBuf},
     str::FromStr,
 };
 
-use crate::prelude::internal::*;
+use crate::{prelude::internal::*, report::components};
 
 /// returns a value of type `V` from the file at `path`.
 ///
@@ -45,6 +45,187 @@ where
     sysfs_value::<V>(path).await.ok()
 }
 
+/// finds the name of the kernel driver bound to the `sysfs` device at `path`.
+///
+/// the `driver` entry is a symlink to the driver's directory, so we just
+/// return that directory's name.
+#[tracing::instrument]
+pub(crate) async fn driver_name(path: impl AsRef<Path> + Debug) -> Option<String> {
+    let driver_path = async_fs::read_link(path.as_ref().join("driver"))
+        .await
+        .ok()?;
+
+    driver_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+/// finds the name of the kernel module providing the driver bound to the
+/// `sysfs` device at `path`.
+///
+/// drivers built into the kernel don't have one.
+#[tracing::instrument]
+pub(crate) async fn driver_module(path: impl AsRef<Path> + Debug) -> Option<String> {
+    let module_path = async_fs::read_link(path.as_ref().join("driver/module"))
+        .await
+        .ok()?;
+
+    module_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+/// finds the name of the subsystem (bus or class) of the `sysfs` device at
+/// `path`, like `pci` or `usb`.
+#[tracing::instrument]
+pub(crate) async fn subsystem(path: impl AsRef<Path> + Debug) -> Option<String> {
+    let subsystem = async_fs::read_link(path.as_ref().join("subsystem"))
+        .await
+        .ok()?;
+
+    subsystem
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+/// reads the rfkill state of the radio device at `path`.
+///
+/// the kernel puts an `rfkillN` directory under each radio device, like
+/// `/sys/class/ieee80211/phy0/rfkill1`. it's also linked in
+/// `/sys/class/rfkill`.
+#[tracing::instrument]
+pub(crate) async fn rfkill(path: impl AsRef<Path> + Debug) -> Option<Rfkill> {
+    use futures::StreamExt as _;
+
+    let entries = async_fs::read_dir(path.as_ref())
+        .await
+        .ok()?
+        .filter_map(|entry| async { entry.ok() })
+        .collect::<Vec<_>>()
+        .await;
+
+    let rfkill_path = entries.iter().map(|entry| entry.path()).find(|path| {
+        path.file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with("rfkill"))
+    })?;
+
+    let (soft, hard) = futures::join! {
+        sysfs_value_opt::<u8>(rfkill_path.join("soft")),
+        sysfs_value_opt::<u8>(rfkill_path.join("hard")),
+    };
+
+    Some(Rfkill {
+        soft_blocked: soft? != 0,
+        hard_blocked: hard? != 0,
+    })
+}
+
+/// reads the runtime power management status of the `sysfs` device at
+/// `path`.
+#[tracing::instrument]
+pub(crate) async fn runtime_status(path: impl AsRef<Path> + Debug) -> Option<RuntimeStatus> {
+    match sysfs_value_opt::<String>(path.as_ref().join("power/runtime_status"))
+        .await?
+        .as_str()
+    {
+        "active" => Some(RuntimeStatus::Active),
+        "suspended" => Some(RuntimeStatus::Suspended),
+        "suspending" => Some(RuntimeStatus::Suspending),
+        "resuming" => Some(RuntimeStatus::Resuming),
+        "error" => Some(RuntimeStatus::Error),
+        "unsupported" => Some(RuntimeStatus::Unsupported),
+        other => {
+            tracing::debug!("Unknown runtime power status: `{other}`");
+            None
+        }
+    }
+}
+
+/// grabs the cpu flags from the first processor in `/proc/cpuinfo`.
+#[tracing::instrument(skip(cpuinfo))]
+pub(crate) fn cpu_flags(cpuinfo: &str) -> Vec<&str> {
+    cpuinfo
+        .lines()
+        .find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            (key.trim() == "flags").then_some(value)
+        })
+        .map(|flags| flags.split_whitespace().collect())
+        .unwrap_or_default()
+}
+
+/// lists the entries in `path` named `prefix` and a number, like
+/// `thermal_zone0` or `node1`, in order.
+#[tracing::instrument]
+pub(crate) async fn numbered_entries(path: impl AsRef<Path> + Debug, prefix: &str) -> Vec<PathBuf> {
+    let Ok(entries) = components::linux::devices(path).await else {
+        return Vec::new();
+    };
+
+    let mut numbered = entries
+        .iter()
+        .filter_map(|e| {
+            let number = e
+                .file_name()
+                .to_string_lossy()
+                .strip_prefix(prefix)?
+                .parse::<u32>()
+                .ok()?;
+            Some((number, e.path()))
+        })
+        .collect::<Vec<_>>();
+
+    // sort by number, so `thermal_zone10` comes after `thermal_zone9`
+    numbered.sort();
+    numbered.into_iter().map(|(_, path)| path).collect()
+}
+
+/// parses a list of cpus, like `0-3,8,10-11`.
+#[tracing::instrument]
+pub(crate) fn parse_cpu_list(list: &str) -> Option<Vec<u32>> {
+    let mut cpus = Vec::new();
+    for range in list.trim().split(',').filter(|range| !range.is_empty()) {
+        match range.split_once('-') {
+            Some((start, end)) => cpus.extend(start.parse::<u32>().ok()?..=end.parse().ok()?),
+            None => cpus.push(range.parse().ok()?),
+        }
+    }
+
+    Some(cpus)
+}
+
+/// parses a `sysfs` file listing choices, with the current one in brackets,
+/// like `s2idle [deep]`.
+#[tracing::instrument(skip(parse))]
+pub(crate) fn parse_choices<T: Clone>(
+    choices: &str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> (Vec<T>, Option<T>) {
+    let mut all = Vec::new();
+    let mut current = None;
+
+    for choice in choices.split_whitespace() {
+        let bare = choice.trim_start_matches('[').trim_end_matches(']');
+        let Some(parsed) = parse(bare) else {
+            continue;
+        };
+
+        if bare != choice {
+            current = Some(parsed.clone());
+        }
+        all.push(parsed);
+    }
+
+    (all, current)
+}
+
+/// finds the current choice in a `sysfs` file, like `zstd` in
+/// `lzo lz4 [zstd]`.
+#[tracing::instrument]
+pub(crate) fn current_choice(choices: &str) -> Option<String> {
+    parse_choices(choices, |choice| Some(choice.to_string())).1
+}
+
 /// class, id, vendor
 ///
 /// (assumes that the given device is pci or uses pci values)